@@ -13,6 +13,7 @@ fn main() {
     let is_deterministic = env::var("CARGO_FEATURE_DETERMINISTIC").is_ok();
     let is_profile = env::var("CARGO_FEATURE_PROFILE").is_ok();
     let is_debug_renderer = env::var("CARGO_FEATURE_DEBUG_RENDERER").is_ok();
+    let is_debug_frame = env::var("CARGO_FEATURE_DEBUG_FRAME").is_ok();
     let is_debug_print = env::var("CARGO_FEATURE_DEBUG_PRINT").is_ok();
 
     let mut rs_file = vec![
@@ -21,6 +22,11 @@ fn main() {
         "src/body.rs",
         "src/system.rs",
         "src/character.rs",
+        "src/constraint.rs",
+        "src/query.rs",
+        "src/soft_body.rs",
+        "src/vehicle.rs",
+        "src/scene.rs",
         "src/test_callback.rs",
     ];
 
@@ -29,6 +35,11 @@ fn main() {
         "src/body.cpp",
         "src/system.cpp",
         "src/character.cpp",
+        "src/constraint.cpp",
+        "src/query.cpp",
+        "src/soft_body.cpp",
+        "src/vehicle.cpp",
+        "src/scene.cpp",
         "src/test_callback.cpp",
     ];
 
@@ -37,8 +48,19 @@ fn main() {
     println!("cargo:rerun-if-changed=src/body.cpp");
     println!("cargo:rerun-if-changed=src/system.cpp");
     println!("cargo:rerun-if-changed=src/character.cpp");
+    println!("cargo:rerun-if-changed=src/constraint.cpp");
+    println!("cargo:rerun-if-changed=src/query.cpp");
+    println!("cargo:rerun-if-changed=src/soft_body.cpp");
+    println!("cargo:rerun-if-changed=src/vehicle.cpp");
+    println!("cargo:rerun-if-changed=src/scene.cpp");
     println!("cargo:rerun-if-changed=src/test_callback.cpp");
 
+    if is_debug_frame {
+        rs_file.push("src/debug_frame.rs");
+        cpp_file.push("src/debug_frame.cpp");
+        println!("cargo:rerun-if-changed=src/debug_frame.cpp");
+    }
+
     if is_windows && is_debug_renderer {
         rs_file.push("src/debug.rs");
         cpp_file.push("src/debug.cpp");
@@ -85,6 +107,10 @@ fn main() {
         cxx.define("JPH_DEBUG_PRINT", "1");
     }
 
+    if is_debug_renderer || is_debug_frame {
+        cxx.define("JPH_DEBUG_RENDERER", "1");
+    }
+
     if is_debug_renderer {
         let mut files = list_source_files("./JoltPhysics/TestFramework");
         if is_windows {
@@ -105,7 +131,7 @@ fn main() {
 
         cxx.include("./JoltPhysics/TestFramework")
             .files(files)
-            .define("JPH_DEBUG_RENDERER", "1");
+            .define("JOLT_RS_DEBUG_APP", "1");
     }
 
     if is_windows && !is_clang {
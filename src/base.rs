@@ -186,6 +186,7 @@ pub(crate) mod ffi {
         type Plane = crate::base::Plane;
         type AABox = crate::base::AABox;
         type IndexedTriangle = crate::base::IndexedTriangle;
+        type Color = crate::base::Color;
         type BodyID = crate::base::BodyID;
         type SubShapeID = crate::base::SubShapeID;
         type CharacterID = crate::base::CharacterID;
@@ -577,10 +578,52 @@ impl IndexedTriangle {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+const_assert_eq!(mem::size_of::<Color>(), 4);
+
+unsafe impl ExternType for Color {
+    type Id = type_id!("Color");
+    type Kind = kind::Trivial;
+}
+
+impl Default for Color {
+    #[inline]
+    fn default() -> Self {
+        Color::WHITE
+    }
+}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0, 0, 0, 255);
+    pub const WHITE: Color = Color::new(255, 255, 255, 255);
+    pub const GREY: Color = Color::new(128, 128, 128, 255);
+    pub const RED: Color = Color::new(255, 0, 0, 255);
+    pub const GREEN: Color = Color::new(0, 255, 0, 255);
+    pub const BLUE: Color = Color::new(0, 0, 255, 255);
+
+    #[inline]
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color { r, g, b, a }
+    }
+}
+
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), rkyv(derive(Debug)))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug))
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BodyID(pub u32);
 const_assert_eq!(mem::size_of::<BodyID>(), 4);
 
@@ -630,8 +673,12 @@ impl fmt::Debug for BodyID {
 
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), rkyv(derive(Debug)))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug))
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterID(pub u32);
 const_assert_eq!(mem::size_of::<CharacterID>(), 4);
 
@@ -697,7 +744,8 @@ unsafe impl ExternType for SubShapeID {
 }
 
 impl SubShapeID {
-    pub const EMPTY: SubShapeID = SubShapeID(0);
+    pub const EMPTY: SubShapeID = SubShapeID(u32::MAX);
+    pub const MAX_BITS: u32 = 32;
 
     #[inline]
     pub fn new(id: u32) -> SubShapeID {
@@ -708,6 +756,16 @@ impl SubShapeID {
     pub fn is_empty(&self) -> bool {
         *self == Self::EMPTY
     }
+
+    /// Pops the lowest `bits` bits, returns the popped value and the remainder of the id.
+    #[inline]
+    pub fn pop_id(&self, bits: u32) -> (u32, SubShapeID) {
+        debug_assert!(bits <= Self::MAX_BITS);
+        let mask = ((1u64 << bits) - 1) as u32;
+        let fill = (u64::from(u32::MAX) << (Self::MAX_BITS - bits)) as u32;
+        let remainder = ((u64::from(self.0) >> bits) as u32) | fill;
+        (self.0 & mask, SubShapeID(remainder))
+    }
 }
 
 impl From<u32> for SubShapeID {
@@ -730,6 +788,52 @@ impl fmt::Debug for SubShapeID {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubShapeIDCreator {
+    id: SubShapeID,
+    current_bit: u32,
+}
+
+impl Default for SubShapeIDCreator {
+    #[inline]
+    fn default() -> SubShapeIDCreator {
+        SubShapeIDCreator::new()
+    }
+}
+
+impl SubShapeIDCreator {
+    #[inline]
+    pub fn new() -> SubShapeIDCreator {
+        SubShapeIDCreator {
+            id: SubShapeID::EMPTY,
+            current_bit: 0,
+        }
+    }
+
+    /// Pushes `value` into the next `bits` bits, the inverse of `SubShapeID::pop_id`.
+    #[inline]
+    pub fn push_id(&self, value: u32, bits: u32) -> SubShapeIDCreator {
+        debug_assert!(self.current_bit + bits <= SubShapeID::MAX_BITS);
+        debug_assert!(u64::from(value) < (1u64 << bits));
+        let mask = ((1u64 << bits) - 1) << self.current_bit;
+        let id = (u64::from(self.id.0) & !mask) | (u64::from(value) << self.current_bit);
+        SubShapeIDCreator {
+            id: SubShapeID(id as u32),
+            current_bit: self.current_bit + bits,
+        }
+    }
+
+    #[inline]
+    pub fn get_id(&self) -> SubShapeID {
+        self.id
+    }
+
+    #[inline]
+    pub fn get_num_bits_written(&self) -> u32 {
+        self.current_bit
+    }
+}
+
 pub unsafe trait JRefTarget {
     type JRaw: Sized + fmt::Debug;
 
@@ -903,3 +1007,32 @@ impl<T: fmt::Debug, const N: usize> fmt::Debug for StaticArray<T, N> {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sub_shape_id_round_trip() {
+        assert!(SubShapeIDCreator::new().get_id().is_empty());
+        assert_eq!(SubShapeID::EMPTY.pop_id(5), (0b11111, SubShapeID::EMPTY));
+
+        let creator = SubShapeIDCreator::new().push_id(5, 3).push_id(0, 1).push_id(1000, 10);
+        assert_eq!(creator.get_num_bits_written(), 14);
+        let (value, id) = creator.get_id().pop_id(3);
+        assert_eq!(value, 5);
+        let (value, id) = id.pop_id(1);
+        assert_eq!(value, 0);
+        let (value, id) = id.pop_id(10);
+        assert_eq!(value, 1000);
+        assert!(id.is_empty());
+
+        let creator = SubShapeIDCreator::new().push_id(0x1234_5678, 30).push_id(2, 2);
+        assert_eq!(creator.get_num_bits_written(), SubShapeID::MAX_BITS);
+        let (value, id) = creator.get_id().pop_id(30);
+        assert_eq!(value, 0x1234_5678);
+        let (value, id) = id.pop_id(2);
+        assert_eq!(value, 2);
+        assert!(id.is_empty());
+    }
+}
@@ -201,7 +201,7 @@ impl JoltDemo {
         self.update_dyn_mutable_compound();
 
         self.system
-            .update_with_listeners::<_, ()>(1.0 / FPS, 1, Some(self.contact_listener.as_mut()), None)
+            .update_with_listeners::<_, ()>(1.0 / FPS, 1, Some(self.contact_listener.as_ref()), None)
             .unwrap();
     }
 
@@ -636,12 +636,12 @@ impl CharacterContactListener for CharacterContactListenerImpl {
 #[vdata(ContactListenerVTable)]
 #[derive(Default)]
 pub struct ContactListenerImpl {
-    pub pairs: Vec<SubShapeIDPair>,
+    pub pairs: Mutex<Vec<SubShapeIDPair>>,
 }
 
 impl ContactListener for ContactListenerImpl {
     fn on_contact_validate(
-        &mut self,
+        &self,
         _body1: &Body,
         _body2: &Body,
         _base_offset: JVec3,
@@ -651,7 +651,7 @@ impl ContactListener for ContactListenerImpl {
     }
 
     fn on_contact_added(
-        &mut self,
+        &self,
         body1: &Body,
         body2: &Body,
         manifold: &ContactManifold,
@@ -663,12 +663,13 @@ impl ContactListener for ContactListenerImpl {
             sub_shape_id1: manifold.sub_shape_id1,
             sub_shape_id2: manifold.sub_shape_id2,
         };
-        self.pairs.push(pair);
-        println!("on_contact_added {} | {:?}", self.pairs.len(), pair);
+        let mut pairs = self.pairs.lock().unwrap();
+        pairs.push(pair);
+        println!("on_contact_added {} | {:?}", pairs.len(), pair);
     }
 
     fn on_contact_persisted(
-        &mut self,
+        &self,
         _body1: &Body,
         _body2: &Body,
         _manifold: &ContactManifold,
@@ -676,9 +677,10 @@ impl ContactListener for ContactListenerImpl {
     ) {
     }
 
-    fn on_contact_removed(&mut self, pair: &SubShapeIDPair) {
-        self.pairs.retain(|p| *p != *pair);
-        println!("on_contact_removed {} | {:?}", self.pairs.len(), pair);
+    fn on_contact_removed(&self, pair: &SubShapeIDPair) {
+        let mut pairs = self.pairs.lock().unwrap();
+        pairs.retain(|p| *p != *pair);
+        println!("on_contact_removed {} | {:?}", pairs.len(), pair);
     }
 }
 
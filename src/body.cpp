@@ -5,3 +5,16 @@ static_assert(sizeof(BodyCreationSettings) == 256, "BodyCreationSettings size");
 static_assert(sizeof(Body) == 128, "Body size");
 static_assert(sizeof(CollisionGroup) == 16, "CollisionGroup size");
 static_assert(sizeof(MassProperties) == 80, "MassProperties size");
+
+void SaveBodyState(const Body& body, rust::Vec<uint8>& data) {
+	StateRecorderImpl recorder;
+	body.SaveState(recorder);
+	StateRecorderToVec(recorder, data);
+}
+
+bool RestoreBodyState(Body& body, rust::Slice<const uint8> data) {
+	StateRecorderImpl recorder;
+	StateRecorderFromSlice(recorder, data);
+	body.RestoreState(recorder);
+	return !recorder.IsFailed();
+}
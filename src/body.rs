@@ -1,4 +1,5 @@
 use core::fmt;
+use cxx::{kind, type_id, ExternType};
 use glam::{Mat4, Quat, Vec3A};
 use static_assertions::const_assert_eq;
 use std::mem;
@@ -7,6 +8,7 @@ use std::pin::Pin;
 use crate::base::{
     AABox, AllowedDOFs, BodyID, BodyType, JRef, JVec3, MotionQuality, MotionType, OverrideMassProperties, SubShapeID,
 };
+use crate::error::{JoltError, JoltResult};
 use crate::shape::Shape;
 use crate::ObjectLayer;
 
@@ -160,8 +162,9 @@ pub(crate) mod ffi {
             inMaxMovement: f32,
             inTimeBeforeSleep: f32,
         ) -> CanSleep;
-        // SaveState
-        // RestoreState
+
+        fn SaveBodyState(body: &Body, data: &mut Vec<u8>);
+        fn RestoreBodyState(body: Pin<&mut Body>, data: &[u8]) -> bool;
     }
 }
 
@@ -186,14 +189,23 @@ impl Default for CollisionGroup {
 
 #[repr(C)]
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MassProperties {
     pub mass: f32,
     pub inertia: Mat4,
 }
 const_assert_eq!(mem::size_of::<MassProperties>(), 80);
 
+unsafe impl ExternType for MassProperties {
+    type Id = type_id!("MassProperties");
+    type Kind = kind::Trivial;
+}
+
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BodyCreationSettings {
     pub position: Vec3A,
     pub rotation: Quat,
@@ -201,6 +213,8 @@ pub struct BodyCreationSettings {
     pub angular_velocity: Vec3A,
     pub user_data: u64,
     pub object_layer: ObjectLayer,
+    #[cfg_attr(feature = "rkyv", rkyv(with = rkyv::with::Skip))]
+    #[cfg_attr(feature = "serde", serde(skip))]
     _collision_group: CollisionGroup,
     pub motion_type: MotionType,
     pub allowed_dofs: AllowedDOFs,
@@ -224,7 +238,11 @@ pub struct BodyCreationSettings {
     pub override_mass_properties: OverrideMassProperties,
     pub inertia_multiplier: f32,
     pub mass_properties: MassProperties,
+    #[cfg_attr(feature = "rkyv", rkyv(with = rkyv::with::Skip))]
+    #[cfg_attr(feature = "serde", serde(skip))]
     _shape_settings: usize,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::ShapeDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_shape"))]
     pub shape: Option<JRef<Shape>>,
 }
 const_assert_eq!(mem::size_of::<BodyCreationSettings>(), 256);
@@ -329,7 +347,7 @@ impl fmt::Debug for Body {
 
 impl Body {
     #[inline]
-    fn as_ref(&self) -> &ffi::Body {
+    pub(crate) fn as_ref(&self) -> &ffi::Body {
         &self.0
     }
 
@@ -791,4 +809,17 @@ impl Body {
             .UpdateSleepStateInternal(delta_time, max_movement, time_before_sleep)
             .into()
     }
+
+    #[inline]
+    pub fn save_state(&self, data: &mut Vec<u8>) {
+        ffi::SaveBodyState(self.as_ref(), data);
+    }
+
+    #[inline]
+    pub fn restore_state(&mut self, data: &[u8]) -> JoltResult<()> {
+        match ffi::RestoreBodyState(self.as_mut(), data) {
+            true => Ok(()),
+            false => Err(JoltError::RestoreState),
+        }
+    }
 }
@@ -97,11 +97,13 @@ XCharacterVirtual::XCharacterVirtual(
 	CharacterVirtual(settings, position, rotation, &system->PhySys()),
 	_rustCleanUp(rustCleanUp),
 	_system(system) {
+	_system->AddCharacterVirtual(this);
 	RENDERER_ONLY(_system->AddRenderable(this));
 }
 
 XCharacterVirtual::~XCharacterVirtual() {
 	_rustCleanUp(*this);
+	_system->RemoveCharacterVirtual(this);
 	RENDERER_ONLY(_system->RemoveRenderable(this));
 	PRINT_ONLY(printf("~XCharacterVirtual %d system %d\n", GetRefCount(), _system->GetRefCount() - 1));
 }
@@ -186,6 +188,19 @@ bool XCharacterVirtual::SetShape(ObjectLayer chara_layer, const Shape* shape, fl
 	);
 }
 
+void XCharacterVirtual::SaveState(rust::Vec<uint8>& data) const {
+	StateRecorderImpl recorder;
+	CharacterVirtual::SaveState(recorder);
+	StateRecorderToVec(recorder, data);
+}
+
+bool XCharacterVirtual::RestoreState(rust::Slice<const uint8> data) {
+	StateRecorderImpl recorder;
+	StateRecorderFromSlice(recorder, data);
+	CharacterVirtual::RestoreState(recorder);
+	return !recorder.IsFailed();
+}
+
 #if defined(JPH_DEBUG_RENDERER)
 void XCharacterVirtual::Render(DebugRenderer* render) const {
 	const CharacterVirtual* chara = dynamic_cast<const CharacterVirtual*>(this);
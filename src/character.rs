@@ -12,7 +12,9 @@ use crate::base::{
     AllowedDOFs, BodyID, CharacterID, JMut, JMutTarget, JQuat, JRef, JRefTarget, JVec3, ObjectLayer, Plane, SubShapeID,
 };
 use crate::body::Body;
+use crate::error::{JoltError, JoltResult};
 use crate::shape::{PhysicsMaterial, Shape};
+use crate::soft_body::SoftBodyContactListener;
 use crate::system::{BodyActivationListener, ContactListener, PhysicsSystem};
 use crate::vtable::{VBox, VPair};
 
@@ -216,6 +218,8 @@ pub(crate) mod ffi {
             max_penetration_depth: f32,
         ) -> bool;
         unsafe fn SetInnerBodyShape(self: Pin<&mut XCharacterVirtual>, shape: *const Shape);
+        fn SaveState(self: &XCharacterVirtual, data: &mut Vec<u8>);
+        fn RestoreState(self: Pin<&mut XCharacterVirtual>, data: &[u8]) -> bool;
         // GetTransformedShape
         // CheckCollision
     }
@@ -274,11 +278,15 @@ impl CharacterSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterVirtualSettings {
     pub up: Vec3A,
     pub supporting_volume: Plane,
     pub max_slope_angle: f32,
     pub enhanced_internal_edge_removal: bool,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::ShapeDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_shape"))]
     pub shape: Option<JRef<Shape>>,
     pub character_id: CharacterID,
     pub mass: f32,
@@ -294,6 +302,8 @@ pub struct CharacterVirtualSettings {
     pub max_num_hits: u32,
     pub hit_reduction_cos_max_angle: f32,
     pub penetration_recovery_speed: f32,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::ShapeDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_shape"))]
     pub inner_body_shape: Option<JRef<Shape>>,
     pub inner_body_id_override: BodyID,
     pub inner_body_layer: ObjectLayer,
@@ -460,8 +470,8 @@ impl JMut<Character> {
 }
 
 impl Character {
-    pub fn new<CL: ContactListener, BAL: BodyActivationListener>(
-        system: &mut PhysicsSystem<CL, BAL>,
+    pub fn new<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+        system: &mut PhysicsSystem<CL, BAL, SBCL>,
         settings: &CharacterSettings,
         position: Vec3A,
         rotation: Quat,
@@ -478,8 +488,8 @@ impl Character {
         }
     }
 
-    pub fn new_add<CL: ContactListener, BAL: BodyActivationListener>(
-        system: &mut PhysicsSystem<CL, BAL>,
+    pub fn new_add<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+        system: &mut PhysicsSystem<CL, BAL, SBCL>,
         settings: &CharacterSettings,
         position: Vec3A,
         rotation: Quat,
@@ -773,8 +783,8 @@ impl<CCL: CharacterContactListener> JMut<CharacterVirtual<CCL>> {
 }
 
 impl<CCL: CharacterContactListener> CharacterVirtual<CCL> {
-    pub fn new<CL: ContactListener, BAL: BodyActivationListener>(
-        system: &mut PhysicsSystem<CL, BAL>,
+    pub fn new<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+        system: &mut PhysicsSystem<CL, BAL, SBCL>,
         settings: &CharacterVirtualSettings,
         position: Vec3A,
         rotation: Quat,
@@ -1142,6 +1152,19 @@ impl<CCL: CharacterContactListener> CharacterVirtual<CCL> {
     pub fn set_inner_body_shape(&mut self, shape: &Shape) {
         unsafe { self.as_mut().SetInnerBodyShape(&shape.0) };
     }
+
+    #[inline]
+    pub fn save_state(&self, data: &mut Vec<u8>) {
+        self.as_ref().SaveState(data);
+    }
+
+    #[inline]
+    pub fn restore_state(&mut self, data: &[u8]) -> JoltResult<()> {
+        match self.as_mut().RestoreState(data) {
+            true => Ok(()),
+            false => Err(JoltError::RestoreState),
+        }
+    }
 }
 
 #[vtable(allow_empty)]
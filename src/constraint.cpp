@@ -0,0 +1,349 @@
+#include "jolt-physics-rs/src/ffi.h"
+#include "jolt-physics-rs/src/constraint.rs.h"
+
+static_assert(sizeof(SpringSettings) == 12, "SpringSettings size");
+static_assert(sizeof(MotorSettings) == 28, "MotorSettings size");
+
+template <class S, class X>
+static void CopyConstraintSettings(S& settings, const X& st) {
+	settings.mUserData = st.userData;
+	settings.mEnabled = st.enabled;
+	settings.mConstraintPriority = st.constraintPriority;
+	settings.mNumVelocityStepsOverride = st.numVelocityStepsOverride;
+	settings.mNumPositionStepsOverride = st.numPositionStepsOverride;
+}
+
+template <class C, class S>
+static C* CreateTwoBodyConstraint(
+	XPhysicsSystem* system,
+	const S& settings,
+	const BodyID& body1,
+	const BodyID& body2
+) {
+	BodyID bodyIds[] = { body1, body2 };
+	BodyLockMultiWrite lock(system->PhySys().GetBodyLockInterface(), bodyIds, 2);
+	Body* b1 = lock.GetBody(0);
+	Body* b2 = lock.GetBody(1);
+	if (b1 == b2) {
+		return nullptr;
+	}
+	Ref<C> constraint = new XConstraint<C>(
+		Ref(system),
+		b1 != nullptr ? *b1 : Body::sFixedToWorld,
+		b2 != nullptr ? *b2 : Body::sFixedToWorld,
+		settings
+	);
+	return LeakRefT<C>(constraint);
+}
+
+struct XFixedConstraintSettings {
+	uint64 userData;
+	bool enabled;
+	uint32 constraintPriority;
+	uint32 numVelocityStepsOverride;
+	uint32 numPositionStepsOverride;
+	EConstraintSpace space;
+	bool autoDetectPoint;
+	Vec3 point1;
+	Vec3 axisX1;
+	Vec3 axisY1;
+	Vec3 point2;
+	Vec3 axisX2;
+	Vec3 axisY2;
+};
+static_assert(sizeof(XFixedConstraintSettings) == 128, "XFixedConstraintSettings size");
+
+Constraint* CreateFixedConstraint(
+	XPhysicsSystem* system,
+	const XFixedConstraintSettings& st,
+	const BodyID& body1,
+	const BodyID& body2
+) {
+	FixedConstraintSettings settings;
+	CopyConstraintSettings(settings, st);
+	settings.mSpace = st.space;
+	settings.mAutoDetectPoint = st.autoDetectPoint;
+	settings.mPoint1 = st.point1;
+	settings.mAxisX1 = st.axisX1;
+	settings.mAxisY1 = st.axisY1;
+	settings.mPoint2 = st.point2;
+	settings.mAxisX2 = st.axisX2;
+	settings.mAxisY2 = st.axisY2;
+	return CreateTwoBodyConstraint<FixedConstraint>(system, settings, body1, body2);
+}
+
+struct XPointConstraintSettings {
+	uint64 userData;
+	bool enabled;
+	uint32 constraintPriority;
+	uint32 numVelocityStepsOverride;
+	uint32 numPositionStepsOverride;
+	EConstraintSpace space;
+	Vec3 point1;
+	Vec3 point2;
+};
+static_assert(sizeof(XPointConstraintSettings) == 64, "XPointConstraintSettings size");
+
+Constraint* CreatePointConstraint(
+	XPhysicsSystem* system,
+	const XPointConstraintSettings& st,
+	const BodyID& body1,
+	const BodyID& body2
+) {
+	PointConstraintSettings settings;
+	CopyConstraintSettings(settings, st);
+	settings.mSpace = st.space;
+	settings.mPoint1 = st.point1;
+	settings.mPoint2 = st.point2;
+	return CreateTwoBodyConstraint<PointConstraint>(system, settings, body1, body2);
+}
+
+struct XHingeConstraintSettings {
+	uint64 userData;
+	bool enabled;
+	uint32 constraintPriority;
+	uint32 numVelocityStepsOverride;
+	uint32 numPositionStepsOverride;
+	EConstraintSpace space;
+	Vec3 point1;
+	Vec3 hingeAxis1;
+	Vec3 normalAxis1;
+	Vec3 point2;
+	Vec3 hingeAxis2;
+	Vec3 normalAxis2;
+	float limitsMin;
+	float limitsMax;
+	SpringSettings limitsSpringSettings;
+	float maxFrictionTorque;
+};
+static_assert(sizeof(XHingeConstraintSettings) == 160, "XHingeConstraintSettings size");
+
+Constraint* CreateHingeConstraint(
+	XPhysicsSystem* system,
+	const XHingeConstraintSettings& st,
+	const BodyID& body1,
+	const BodyID& body2
+) {
+	HingeConstraintSettings settings;
+	CopyConstraintSettings(settings, st);
+	settings.mSpace = st.space;
+	settings.mPoint1 = st.point1;
+	settings.mHingeAxis1 = st.hingeAxis1;
+	settings.mNormalAxis1 = st.normalAxis1;
+	settings.mPoint2 = st.point2;
+	settings.mHingeAxis2 = st.hingeAxis2;
+	settings.mNormalAxis2 = st.normalAxis2;
+	settings.mLimitsMin = st.limitsMin;
+	settings.mLimitsMax = st.limitsMax;
+	settings.mLimitsSpringSettings = st.limitsSpringSettings;
+	settings.mMaxFrictionTorque = st.maxFrictionTorque;
+	return CreateTwoBodyConstraint<HingeConstraint>(system, settings, body1, body2);
+}
+
+struct XSliderConstraintSettings {
+	uint64 userData;
+	bool enabled;
+	uint32 constraintPriority;
+	uint32 numVelocityStepsOverride;
+	uint32 numPositionStepsOverride;
+	EConstraintSpace space;
+	bool autoDetectPoint;
+	Vec3 point1;
+	Vec3 sliderAxis1;
+	Vec3 normalAxis1;
+	Vec3 point2;
+	Vec3 sliderAxis2;
+	Vec3 normalAxis2;
+	float limitsMin;
+	float limitsMax;
+	SpringSettings limitsSpringSettings;
+	float maxFrictionForce;
+};
+static_assert(sizeof(XSliderConstraintSettings) == 160, "XSliderConstraintSettings size");
+
+Constraint* CreateSliderConstraint(
+	XPhysicsSystem* system,
+	const XSliderConstraintSettings& st,
+	const BodyID& body1,
+	const BodyID& body2
+) {
+	SliderConstraintSettings settings;
+	CopyConstraintSettings(settings, st);
+	settings.mSpace = st.space;
+	settings.mAutoDetectPoint = st.autoDetectPoint;
+	settings.mPoint1 = st.point1;
+	settings.mSliderAxis1 = st.sliderAxis1;
+	settings.mNormalAxis1 = st.normalAxis1;
+	settings.mPoint2 = st.point2;
+	settings.mSliderAxis2 = st.sliderAxis2;
+	settings.mNormalAxis2 = st.normalAxis2;
+	settings.mLimitsMin = st.limitsMin;
+	settings.mLimitsMax = st.limitsMax;
+	settings.mLimitsSpringSettings = st.limitsSpringSettings;
+	settings.mMaxFrictionForce = st.maxFrictionForce;
+	return CreateTwoBodyConstraint<SliderConstraint>(system, settings, body1, body2);
+}
+
+struct XDistanceConstraintSettings {
+	uint64 userData;
+	bool enabled;
+	uint32 constraintPriority;
+	uint32 numVelocityStepsOverride;
+	uint32 numPositionStepsOverride;
+	EConstraintSpace space;
+	Vec3 point1;
+	Vec3 point2;
+	float minDistance;
+	float maxDistance;
+	SpringSettings limitsSpringSettings;
+};
+static_assert(sizeof(XDistanceConstraintSettings) == 96, "XDistanceConstraintSettings size");
+
+Constraint* CreateDistanceConstraint(
+	XPhysicsSystem* system,
+	const XDistanceConstraintSettings& st,
+	const BodyID& body1,
+	const BodyID& body2
+) {
+	DistanceConstraintSettings settings;
+	CopyConstraintSettings(settings, st);
+	settings.mSpace = st.space;
+	settings.mPoint1 = st.point1;
+	settings.mPoint2 = st.point2;
+	settings.mMinDistance = st.minDistance;
+	settings.mMaxDistance = st.maxDistance;
+	settings.mLimitsSpringSettings = st.limitsSpringSettings;
+	return CreateTwoBodyConstraint<DistanceConstraint>(system, settings, body1, body2);
+}
+
+struct XConeConstraintSettings {
+	uint64 userData;
+	bool enabled;
+	uint32 constraintPriority;
+	uint32 numVelocityStepsOverride;
+	uint32 numPositionStepsOverride;
+	EConstraintSpace space;
+	Vec3 point1;
+	Vec3 twistAxis1;
+	Vec3 point2;
+	Vec3 twistAxis2;
+	float halfConeAngle;
+};
+static_assert(sizeof(XConeConstraintSettings) == 112, "XConeConstraintSettings size");
+
+Constraint* CreateConeConstraint(
+	XPhysicsSystem* system,
+	const XConeConstraintSettings& st,
+	const BodyID& body1,
+	const BodyID& body2
+) {
+	ConeConstraintSettings settings;
+	CopyConstraintSettings(settings, st);
+	settings.mSpace = st.space;
+	settings.mPoint1 = st.point1;
+	settings.mTwistAxis1 = st.twistAxis1;
+	settings.mPoint2 = st.point2;
+	settings.mTwistAxis2 = st.twistAxis2;
+	settings.mHalfConeAngle = st.halfConeAngle;
+	return CreateTwoBodyConstraint<ConeConstraint>(system, settings, body1, body2);
+}
+
+struct XSwingTwistConstraintSettings {
+	uint64 userData;
+	bool enabled;
+	uint32 constraintPriority;
+	uint32 numVelocityStepsOverride;
+	uint32 numPositionStepsOverride;
+	EConstraintSpace space;
+	Vec3 position1;
+	Vec3 twistAxis1;
+	Vec3 planeAxis1;
+	Vec3 position2;
+	Vec3 twistAxis2;
+	Vec3 planeAxis2;
+	ESwingType swingType;
+	float normalHalfConeAngle;
+	float planeHalfConeAngle;
+	float twistMinAngle;
+	float twistMaxAngle;
+	float maxFrictionTorque;
+	MotorSettings swingMotorSettings;
+	MotorSettings twistMotorSettings;
+};
+static_assert(sizeof(XSwingTwistConstraintSettings) == 208, "XSwingTwistConstraintSettings size");
+
+SwingTwistConstraint* CreateSwingTwistConstraint(
+	XPhysicsSystem* system,
+	const XSwingTwistConstraintSettings& st,
+	const BodyID& body1,
+	const BodyID& body2
+) {
+	SwingTwistConstraintSettings settings;
+	CopyConstraintSettings(settings, st);
+	settings.mSpace = st.space;
+	settings.mPosition1 = st.position1;
+	settings.mTwistAxis1 = st.twistAxis1;
+	settings.mPlaneAxis1 = st.planeAxis1;
+	settings.mPosition2 = st.position2;
+	settings.mTwistAxis2 = st.twistAxis2;
+	settings.mPlaneAxis2 = st.planeAxis2;
+	settings.mSwingType = st.swingType;
+	settings.mNormalHalfConeAngle = st.normalHalfConeAngle;
+	settings.mPlaneHalfConeAngle = st.planeHalfConeAngle;
+	settings.mTwistMinAngle = st.twistMinAngle;
+	settings.mTwistMaxAngle = st.twistMaxAngle;
+	settings.mMaxFrictionTorque = st.maxFrictionTorque;
+	settings.mSwingMotorSettings = st.swingMotorSettings;
+	settings.mTwistMotorSettings = st.twistMotorSettings;
+	return CreateTwoBodyConstraint<SwingTwistConstraint>(system, settings, body1, body2);
+}
+
+struct XSixDOFConstraintSettings {
+	uint64 userData;
+	bool enabled;
+	uint32 constraintPriority;
+	uint32 numVelocityStepsOverride;
+	uint32 numPositionStepsOverride;
+	EConstraintSpace space;
+	Vec3 position1;
+	Vec3 axisX1;
+	Vec3 axisY1;
+	Vec3 position2;
+	Vec3 axisX2;
+	Vec3 axisY2;
+	ESwingType swingType;
+	float maxFriction[SixDOFConstraintSettings::EAxis::Num];
+	float limitMin[SixDOFConstraintSettings::EAxis::Num];
+	float limitMax[SixDOFConstraintSettings::EAxis::Num];
+	SpringSettings limitsSpringSettings[SixDOFConstraintSettings::EAxis::NumTranslation];
+	MotorSettings motorSettings[SixDOFConstraintSettings::EAxis::Num];
+};
+static_assert(sizeof(XSixDOFConstraintSettings) == 416, "XSixDOFConstraintSettings size");
+
+SixDOFConstraint* CreateSixDOFConstraint(
+	XPhysicsSystem* system,
+	const XSixDOFConstraintSettings& st,
+	const BodyID& body1,
+	const BodyID& body2
+) {
+	SixDOFConstraintSettings settings;
+	CopyConstraintSettings(settings, st);
+	settings.mSpace = st.space;
+	settings.mPosition1 = st.position1;
+	settings.mAxisX1 = st.axisX1;
+	settings.mAxisY1 = st.axisY1;
+	settings.mPosition2 = st.position2;
+	settings.mAxisX2 = st.axisX2;
+	settings.mAxisY2 = st.axisY2;
+	settings.mSwingType = st.swingType;
+	for (int axis = 0; axis < SixDOFConstraintSettings::EAxis::Num; ++axis) {
+		settings.mMaxFriction[axis] = st.maxFriction[axis];
+		settings.mLimitMin[axis] = st.limitMin[axis];
+		settings.mLimitMax[axis] = st.limitMax[axis];
+		settings.mMotorSettings[axis] = st.motorSettings[axis];
+	}
+	for (int axis = 0; axis < SixDOFConstraintSettings::EAxis::NumTranslation; ++axis) {
+		settings.mLimitsSpringSettings[axis] = st.limitsSpringSettings[axis];
+	}
+	return CreateTwoBodyConstraint<SixDOFConstraint>(system, settings, body1, body2);
+}
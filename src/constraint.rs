@@ -0,0 +1,1802 @@
+use core::fmt;
+use cxx::{kind, type_id, ExternType};
+use glam::{Quat, Vec3A};
+use static_assertions::const_assert_eq;
+use std::mem;
+use std::pin::Pin;
+use std::ptr::NonNull;
+
+use crate::base::{BodyID, JMut, JMutTarget, JRef, JRefTarget};
+use crate::error::{JoltError, JoltResult};
+use crate::soft_body::SoftBodyContactListener;
+use crate::system::{BodyActivationListener, ContactListener, PhysicsSystem};
+
+#[cxx::bridge()]
+pub(crate) mod ffi {
+    #[repr(u32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum ConstraintType {
+        Constraint,
+        TwoBodyConstraint,
+    }
+
+    #[repr(u32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum ConstraintSubType {
+        Fixed,
+        Point,
+        Hinge,
+        Slider,
+        Distance,
+        Cone,
+        SwingTwist,
+        SixDOF,
+        Path,
+        Vehicle,
+        RackAndPinion,
+        Gear,
+        Pulley,
+
+        User1,
+        User2,
+        User3,
+        User4,
+    }
+
+    #[repr(u32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum ConstraintSpace {
+        LocalToBodyCOM,
+        WorldSpace,
+    }
+
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum SpringMode {
+        FrequencyAndDamping,
+        StiffnessAndDamping,
+    }
+
+    #[repr(u32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum MotorState {
+        Off,
+        Velocity,
+        Position,
+    }
+
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum SwingType {
+        Cone,
+        Pyramid,
+    }
+
+    #[repr(u32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum SixDOFAxis {
+        TranslationX,
+        TranslationY,
+        TranslationZ,
+        RotationX,
+        RotationY,
+        RotationZ,
+    }
+
+    unsafe extern "C++" {
+        include!("rust/cxx.h");
+        include!("jolt-physics-rs/src/ffi.h");
+
+        type Vec3 = crate::base::ffi::Vec3;
+        type Quat = crate::base::ffi::Quat;
+        type BodyID = crate::base::ffi::BodyID;
+        type XPhysicsSystem = crate::system::ffi::XPhysicsSystem;
+
+        type ConstraintType;
+        type ConstraintSubType;
+        type ConstraintSpace;
+        type SpringMode;
+        type MotorState;
+        type SwingType;
+        type SixDOFAxis;
+        type SpringSettings = crate::constraint::SpringSettings;
+        type MotorSettings = crate::constraint::MotorSettings;
+
+        type XFixedConstraintSettings = crate::constraint::FixedConstraintSettings;
+        type XPointConstraintSettings = crate::constraint::PointConstraintSettings;
+        type XHingeConstraintSettings = crate::constraint::HingeConstraintSettings;
+        type XSliderConstraintSettings = crate::constraint::SliderConstraintSettings;
+        type XDistanceConstraintSettings = crate::constraint::DistanceConstraintSettings;
+        type XConeConstraintSettings = crate::constraint::ConeConstraintSettings;
+        type XSwingTwistConstraintSettings = crate::constraint::SwingTwistConstraintSettings;
+        type XSixDOFConstraintSettings = crate::constraint::SixDOFConstraintSettings;
+
+        type Constraint;
+        unsafe fn DropConstraint(constraint: *mut Constraint);
+        unsafe fn CloneConstraint(constraint: *mut Constraint) -> *mut Constraint;
+        unsafe fn CountRefConstraint(constraint: *const Constraint) -> u32;
+
+        unsafe fn CreateFixedConstraint(
+            system: *mut XPhysicsSystem,
+            settings: &XFixedConstraintSettings,
+            body1: &BodyID,
+            body2: &BodyID,
+        ) -> *mut Constraint;
+        unsafe fn CreatePointConstraint(
+            system: *mut XPhysicsSystem,
+            settings: &XPointConstraintSettings,
+            body1: &BodyID,
+            body2: &BodyID,
+        ) -> *mut Constraint;
+        unsafe fn CreateHingeConstraint(
+            system: *mut XPhysicsSystem,
+            settings: &XHingeConstraintSettings,
+            body1: &BodyID,
+            body2: &BodyID,
+        ) -> *mut Constraint;
+        unsafe fn CreateSliderConstraint(
+            system: *mut XPhysicsSystem,
+            settings: &XSliderConstraintSettings,
+            body1: &BodyID,
+            body2: &BodyID,
+        ) -> *mut Constraint;
+        unsafe fn CreateDistanceConstraint(
+            system: *mut XPhysicsSystem,
+            settings: &XDistanceConstraintSettings,
+            body1: &BodyID,
+            body2: &BodyID,
+        ) -> *mut Constraint;
+        unsafe fn CreateConeConstraint(
+            system: *mut XPhysicsSystem,
+            settings: &XConeConstraintSettings,
+            body1: &BodyID,
+            body2: &BodyID,
+        ) -> *mut Constraint;
+
+        fn GetType(self: &Constraint) -> ConstraintType;
+        fn GetSubType(self: &Constraint) -> ConstraintSubType;
+        fn GetConstraintPriority(self: &Constraint) -> u32;
+        fn SetConstraintPriority(self: Pin<&mut Constraint>, priority: u32);
+        fn GetNumVelocityStepsOverride(self: &Constraint) -> u32;
+        fn SetNumVelocityStepsOverride(self: Pin<&mut Constraint>, steps: u32);
+        fn GetNumPositionStepsOverride(self: &Constraint) -> u32;
+        fn SetNumPositionStepsOverride(self: Pin<&mut Constraint>, steps: u32);
+        fn GetEnabled(self: &Constraint) -> bool;
+        fn SetEnabled(self: Pin<&mut Constraint>, enabled: bool);
+        fn GetUserData(self: &Constraint) -> u64;
+        fn SetUserData(self: Pin<&mut Constraint>, user_data: u64);
+        fn IsActive(self: &Constraint) -> bool;
+
+        type SwingTwistConstraint;
+        unsafe fn DropSwingTwistConstraint(constraint: *mut SwingTwistConstraint);
+        unsafe fn CloneSwingTwistConstraint(constraint: *mut SwingTwistConstraint) -> *mut SwingTwistConstraint;
+        unsafe fn CountRefSwingTwistConstraint(constraint: *const SwingTwistConstraint) -> u32;
+        unsafe fn CreateSwingTwistConstraint(
+            system: *mut XPhysicsSystem,
+            settings: &XSwingTwistConstraintSettings,
+            body1: &BodyID,
+            body2: &BodyID,
+        ) -> *mut SwingTwistConstraint;
+
+        fn GetNormalHalfConeAngle(self: &SwingTwistConstraint) -> f32;
+        fn SetNormalHalfConeAngle(self: Pin<&mut SwingTwistConstraint>, angle: f32);
+        fn GetPlaneHalfConeAngle(self: &SwingTwistConstraint) -> f32;
+        fn SetPlaneHalfConeAngle(self: Pin<&mut SwingTwistConstraint>, angle: f32);
+        fn GetTwistMinAngle(self: &SwingTwistConstraint) -> f32;
+        fn SetTwistMinAngle(self: Pin<&mut SwingTwistConstraint>, angle: f32);
+        fn GetTwistMaxAngle(self: &SwingTwistConstraint) -> f32;
+        fn SetTwistMaxAngle(self: Pin<&mut SwingTwistConstraint>, angle: f32);
+        fn GetMaxFrictionTorque(self: &SwingTwistConstraint) -> f32;
+        fn SetMaxFrictionTorque(self: Pin<&mut SwingTwistConstraint>, torque: f32);
+        fn GetSwingMotorSettings(self: &SwingTwistConstraint) -> &MotorSettings;
+        #[rust_name = "GetSwingMotorSettingsMut"]
+        fn GetSwingMotorSettings(self: Pin<&mut SwingTwistConstraint>) -> &mut MotorSettings;
+        fn GetTwistMotorSettings(self: &SwingTwistConstraint) -> &MotorSettings;
+        #[rust_name = "GetTwistMotorSettingsMut"]
+        fn GetTwistMotorSettings(self: Pin<&mut SwingTwistConstraint>) -> &mut MotorSettings;
+        fn GetSwingMotorState(self: &SwingTwistConstraint) -> MotorState;
+        fn SetSwingMotorState(self: Pin<&mut SwingTwistConstraint>, state: MotorState);
+        fn GetTwistMotorState(self: &SwingTwistConstraint) -> MotorState;
+        fn SetTwistMotorState(self: Pin<&mut SwingTwistConstraint>, state: MotorState);
+        fn GetTargetAngularVelocityCS(self: &SwingTwistConstraint) -> Vec3;
+        fn SetTargetAngularVelocityCS(self: Pin<&mut SwingTwistConstraint>, velocity: Vec3);
+        fn GetTargetOrientationCS(self: &SwingTwistConstraint) -> Quat;
+        fn SetTargetOrientationCS(self: Pin<&mut SwingTwistConstraint>, orientation: Quat);
+        fn SetTargetOrientationBS(self: Pin<&mut SwingTwistConstraint>, orientation: Quat);
+        fn GetRotationInConstraintSpace(self: &SwingTwistConstraint) -> Quat;
+
+        type SixDOFConstraint;
+        unsafe fn DropSixDOFConstraint(constraint: *mut SixDOFConstraint);
+        unsafe fn CloneSixDOFConstraint(constraint: *mut SixDOFConstraint) -> *mut SixDOFConstraint;
+        unsafe fn CountRefSixDOFConstraint(constraint: *const SixDOFConstraint) -> u32;
+        unsafe fn CreateSixDOFConstraint(
+            system: *mut XPhysicsSystem,
+            settings: &XSixDOFConstraintSettings,
+            body1: &BodyID,
+            body2: &BodyID,
+        ) -> *mut SixDOFConstraint;
+
+        fn SetTranslationLimits(self: Pin<&mut SixDOFConstraint>, limit_min: Vec3, limit_max: Vec3);
+        fn SetRotationLimits(self: Pin<&mut SixDOFConstraint>, limit_min: Vec3, limit_max: Vec3);
+        fn GetLimitsMin(self: &SixDOFConstraint, axis: SixDOFAxis) -> f32;
+        fn GetLimitsMax(self: &SixDOFConstraint, axis: SixDOFAxis) -> f32;
+        fn IsFixedAxis(self: &SixDOFConstraint, axis: SixDOFAxis) -> bool;
+        fn IsFreeAxis(self: &SixDOFConstraint, axis: SixDOFAxis) -> bool;
+        fn GetLimitsSpringSettings(self: &SixDOFConstraint, axis: SixDOFAxis) -> &SpringSettings;
+        fn SetLimitsSpringSettings(self: Pin<&mut SixDOFConstraint>, axis: SixDOFAxis, settings: &SpringSettings);
+        fn GetMaxFriction(self: &SixDOFConstraint, axis: SixDOFAxis) -> f32;
+        fn SetMaxFriction(self: Pin<&mut SixDOFConstraint>, axis: SixDOFAxis, friction: f32);
+        fn GetMotorSettings(self: &SixDOFConstraint, axis: SixDOFAxis) -> &MotorSettings;
+        #[rust_name = "GetMotorSettingsMut"]
+        fn GetMotorSettings(self: Pin<&mut SixDOFConstraint>, axis: SixDOFAxis) -> &mut MotorSettings;
+        fn GetMotorState(self: &SixDOFConstraint, axis: SixDOFAxis) -> MotorState;
+        fn SetMotorState(self: Pin<&mut SixDOFConstraint>, axis: SixDOFAxis, state: MotorState);
+        fn GetTargetVelocityCS(self: &SixDOFConstraint) -> Vec3;
+        fn SetTargetVelocityCS(self: Pin<&mut SixDOFConstraint>, velocity: Vec3);
+        fn GetTargetAngularVelocityCS(self: &SixDOFConstraint) -> Vec3;
+        fn SetTargetAngularVelocityCS(self: Pin<&mut SixDOFConstraint>, velocity: Vec3);
+        fn GetTargetPositionCS(self: &SixDOFConstraint) -> Vec3;
+        fn SetTargetPositionCS(self: Pin<&mut SixDOFConstraint>, position: Vec3);
+        fn GetTargetOrientationCS(self: &SixDOFConstraint) -> Quat;
+        fn SetTargetOrientationCS(self: Pin<&mut SixDOFConstraint>, orientation: Quat);
+        fn SetTargetOrientationBS(self: Pin<&mut SixDOFConstraint>, orientation: Quat);
+        fn GetRotationInConstraintSpace(self: &SixDOFConstraint) -> Quat;
+    }
+}
+
+pub type ConstraintType = ffi::ConstraintType;
+pub type ConstraintSubType = ffi::ConstraintSubType;
+pub type ConstraintSpace = ffi::ConstraintSpace;
+pub type SpringMode = ffi::SpringMode;
+pub type MotorState = ffi::MotorState;
+pub type SwingType = ffi::SwingType;
+pub type SixDOFAxis = ffi::SixDOFAxis;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SpringSettings {
+    pub mode: SpringMode,
+    pub frequency_or_stiffness: f32,
+    pub damping: f32,
+}
+const_assert_eq!(mem::size_of::<SpringSettings>(), 12);
+
+unsafe impl ExternType for SpringSettings {
+    type Id = type_id!("SpringSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for SpringSettings {
+    fn default() -> SpringSettings {
+        SpringSettings {
+            mode: SpringMode::FrequencyAndDamping,
+            frequency_or_stiffness: 0.0,
+            damping: 0.0,
+        }
+    }
+}
+
+impl SpringSettings {
+    pub fn new_frequency(frequency: f32, damping: f32) -> SpringSettings {
+        SpringSettings {
+            mode: SpringMode::FrequencyAndDamping,
+            frequency_or_stiffness: frequency,
+            damping,
+        }
+    }
+
+    pub fn new_stiffness(stiffness: f32, damping: f32) -> SpringSettings {
+        SpringSettings {
+            mode: SpringMode::StiffnessAndDamping,
+            frequency_or_stiffness: stiffness,
+            damping,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct MotorSettings {
+    pub spring_settings: SpringSettings,
+    pub min_force_limit: f32,
+    pub max_force_limit: f32,
+    pub min_torque_limit: f32,
+    pub max_torque_limit: f32,
+}
+const_assert_eq!(mem::size_of::<MotorSettings>(), 28);
+
+unsafe impl ExternType for MotorSettings {
+    type Id = type_id!("MotorSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for MotorSettings {
+    fn default() -> MotorSettings {
+        MotorSettings {
+            spring_settings: SpringSettings::new_frequency(2.0, 1.0),
+            min_force_limit: -f32::MAX,
+            max_force_limit: f32::MAX,
+            min_torque_limit: -f32::MAX,
+            max_torque_limit: f32::MAX,
+        }
+    }
+}
+
+impl MotorSettings {
+    pub fn new(frequency: f32, damping: f32) -> MotorSettings {
+        MotorSettings {
+            spring_settings: SpringSettings::new_frequency(frequency, damping),
+            ..Default::default()
+        }
+    }
+
+    pub fn new_limits(frequency: f32, damping: f32, force_limit: f32, torque_limit: f32) -> MotorSettings {
+        MotorSettings {
+            spring_settings: SpringSettings::new_frequency(frequency, damping),
+            min_force_limit: -force_limit,
+            max_force_limit: force_limit,
+            min_torque_limit: -torque_limit,
+            max_torque_limit: torque_limit,
+        }
+    }
+
+    #[inline]
+    pub fn set_force_limit(&mut self, limit: f32) {
+        self.min_force_limit = -limit;
+        self.max_force_limit = limit;
+    }
+
+    #[inline]
+    pub fn set_torque_limit(&mut self, limit: f32) {
+        self.min_torque_limit = -limit;
+        self.max_torque_limit = limit;
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct FixedConstraintSettings {
+    pub user_data: u64,
+    pub enabled: bool,
+    pub constraint_priority: u32,
+    pub num_velocity_steps_override: u32,
+    pub num_position_steps_override: u32,
+    pub space: ConstraintSpace,
+    pub auto_detect_point: bool,
+    pub point1: Vec3A,
+    pub axis_x1: Vec3A,
+    pub axis_y1: Vec3A,
+    pub point2: Vec3A,
+    pub axis_x2: Vec3A,
+    pub axis_y2: Vec3A,
+}
+const_assert_eq!(mem::size_of::<FixedConstraintSettings>(), 128);
+
+unsafe impl ExternType for FixedConstraintSettings {
+    type Id = type_id!("XFixedConstraintSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for FixedConstraintSettings {
+    fn default() -> FixedConstraintSettings {
+        FixedConstraintSettings {
+            user_data: 0,
+            enabled: true,
+            constraint_priority: 0,
+            num_velocity_steps_override: 0,
+            num_position_steps_override: 0,
+            space: ConstraintSpace::WorldSpace,
+            auto_detect_point: false,
+            point1: Vec3A::ZERO,
+            axis_x1: Vec3A::X,
+            axis_y1: Vec3A::Y,
+            point2: Vec3A::ZERO,
+            axis_x2: Vec3A::X,
+            axis_y2: Vec3A::Y,
+        }
+    }
+}
+
+impl FixedConstraintSettings {
+    pub fn new_auto_detect() -> FixedConstraintSettings {
+        FixedConstraintSettings {
+            auto_detect_point: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PointConstraintSettings {
+    pub user_data: u64,
+    pub enabled: bool,
+    pub constraint_priority: u32,
+    pub num_velocity_steps_override: u32,
+    pub num_position_steps_override: u32,
+    pub space: ConstraintSpace,
+    pub point1: Vec3A,
+    pub point2: Vec3A,
+}
+const_assert_eq!(mem::size_of::<PointConstraintSettings>(), 64);
+
+unsafe impl ExternType for PointConstraintSettings {
+    type Id = type_id!("XPointConstraintSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for PointConstraintSettings {
+    fn default() -> PointConstraintSettings {
+        PointConstraintSettings {
+            user_data: 0,
+            enabled: true,
+            constraint_priority: 0,
+            num_velocity_steps_override: 0,
+            num_position_steps_override: 0,
+            space: ConstraintSpace::WorldSpace,
+            point1: Vec3A::ZERO,
+            point2: Vec3A::ZERO,
+        }
+    }
+}
+
+impl PointConstraintSettings {
+    pub fn new(point: Vec3A) -> PointConstraintSettings {
+        PointConstraintSettings {
+            point1: point,
+            point2: point,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct HingeConstraintSettings {
+    pub user_data: u64,
+    pub enabled: bool,
+    pub constraint_priority: u32,
+    pub num_velocity_steps_override: u32,
+    pub num_position_steps_override: u32,
+    pub space: ConstraintSpace,
+    pub point1: Vec3A,
+    pub hinge_axis1: Vec3A,
+    pub normal_axis1: Vec3A,
+    pub point2: Vec3A,
+    pub hinge_axis2: Vec3A,
+    pub normal_axis2: Vec3A,
+    pub limits_min: f32,
+    pub limits_max: f32,
+    pub limits_spring_settings: SpringSettings,
+    pub max_friction_torque: f32,
+}
+const_assert_eq!(mem::size_of::<HingeConstraintSettings>(), 160);
+
+unsafe impl ExternType for HingeConstraintSettings {
+    type Id = type_id!("XHingeConstraintSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for HingeConstraintSettings {
+    fn default() -> HingeConstraintSettings {
+        HingeConstraintSettings {
+            user_data: 0,
+            enabled: true,
+            constraint_priority: 0,
+            num_velocity_steps_override: 0,
+            num_position_steps_override: 0,
+            space: ConstraintSpace::WorldSpace,
+            point1: Vec3A::ZERO,
+            hinge_axis1: Vec3A::Y,
+            normal_axis1: Vec3A::X,
+            point2: Vec3A::ZERO,
+            hinge_axis2: Vec3A::Y,
+            normal_axis2: Vec3A::X,
+            limits_min: -std::f32::consts::PI,
+            limits_max: std::f32::consts::PI,
+            limits_spring_settings: SpringSettings::default(),
+            max_friction_torque: 0.0,
+        }
+    }
+}
+
+impl HingeConstraintSettings {
+    pub fn new(point: Vec3A, hinge_axis: Vec3A, normal_axis: Vec3A) -> HingeConstraintSettings {
+        HingeConstraintSettings {
+            point1: point,
+            hinge_axis1: hinge_axis,
+            normal_axis1: normal_axis,
+            point2: point,
+            hinge_axis2: hinge_axis,
+            normal_axis2: normal_axis,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct SliderConstraintSettings {
+    pub user_data: u64,
+    pub enabled: bool,
+    pub constraint_priority: u32,
+    pub num_velocity_steps_override: u32,
+    pub num_position_steps_override: u32,
+    pub space: ConstraintSpace,
+    pub auto_detect_point: bool,
+    pub point1: Vec3A,
+    pub slider_axis1: Vec3A,
+    pub normal_axis1: Vec3A,
+    pub point2: Vec3A,
+    pub slider_axis2: Vec3A,
+    pub normal_axis2: Vec3A,
+    pub limits_min: f32,
+    pub limits_max: f32,
+    pub limits_spring_settings: SpringSettings,
+    pub max_friction_force: f32,
+}
+const_assert_eq!(mem::size_of::<SliderConstraintSettings>(), 160);
+
+unsafe impl ExternType for SliderConstraintSettings {
+    type Id = type_id!("XSliderConstraintSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for SliderConstraintSettings {
+    fn default() -> SliderConstraintSettings {
+        SliderConstraintSettings {
+            user_data: 0,
+            enabled: true,
+            constraint_priority: 0,
+            num_velocity_steps_override: 0,
+            num_position_steps_override: 0,
+            space: ConstraintSpace::WorldSpace,
+            auto_detect_point: false,
+            point1: Vec3A::ZERO,
+            slider_axis1: Vec3A::X,
+            normal_axis1: Vec3A::Y,
+            point2: Vec3A::ZERO,
+            slider_axis2: Vec3A::X,
+            normal_axis2: Vec3A::Y,
+            limits_min: -f32::MAX,
+            limits_max: f32::MAX,
+            limits_spring_settings: SpringSettings::default(),
+            max_friction_force: 0.0,
+        }
+    }
+}
+
+impl SliderConstraintSettings {
+    pub fn new(slider_axis: Vec3A, normal_axis: Vec3A) -> SliderConstraintSettings {
+        SliderConstraintSettings {
+            auto_detect_point: true,
+            slider_axis1: slider_axis,
+            normal_axis1: normal_axis,
+            slider_axis2: slider_axis,
+            normal_axis2: normal_axis,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct DistanceConstraintSettings {
+    pub user_data: u64,
+    pub enabled: bool,
+    pub constraint_priority: u32,
+    pub num_velocity_steps_override: u32,
+    pub num_position_steps_override: u32,
+    pub space: ConstraintSpace,
+    pub point1: Vec3A,
+    pub point2: Vec3A,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub limits_spring_settings: SpringSettings,
+}
+const_assert_eq!(mem::size_of::<DistanceConstraintSettings>(), 96);
+
+unsafe impl ExternType for DistanceConstraintSettings {
+    type Id = type_id!("XDistanceConstraintSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for DistanceConstraintSettings {
+    fn default() -> DistanceConstraintSettings {
+        DistanceConstraintSettings {
+            user_data: 0,
+            enabled: true,
+            constraint_priority: 0,
+            num_velocity_steps_override: 0,
+            num_position_steps_override: 0,
+            space: ConstraintSpace::WorldSpace,
+            point1: Vec3A::ZERO,
+            point2: Vec3A::ZERO,
+            min_distance: -1.0,
+            max_distance: -1.0,
+            limits_spring_settings: SpringSettings::default(),
+        }
+    }
+}
+
+impl DistanceConstraintSettings {
+    pub fn new(point1: Vec3A, point2: Vec3A) -> DistanceConstraintSettings {
+        DistanceConstraintSettings {
+            point1,
+            point2,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct ConeConstraintSettings {
+    pub user_data: u64,
+    pub enabled: bool,
+    pub constraint_priority: u32,
+    pub num_velocity_steps_override: u32,
+    pub num_position_steps_override: u32,
+    pub space: ConstraintSpace,
+    pub point1: Vec3A,
+    pub twist_axis1: Vec3A,
+    pub point2: Vec3A,
+    pub twist_axis2: Vec3A,
+    pub half_cone_angle: f32,
+}
+const_assert_eq!(mem::size_of::<ConeConstraintSettings>(), 112);
+
+unsafe impl ExternType for ConeConstraintSettings {
+    type Id = type_id!("XConeConstraintSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for ConeConstraintSettings {
+    fn default() -> ConeConstraintSettings {
+        ConeConstraintSettings {
+            user_data: 0,
+            enabled: true,
+            constraint_priority: 0,
+            num_velocity_steps_override: 0,
+            num_position_steps_override: 0,
+            space: ConstraintSpace::WorldSpace,
+            point1: Vec3A::ZERO,
+            twist_axis1: Vec3A::X,
+            point2: Vec3A::ZERO,
+            twist_axis2: Vec3A::X,
+            half_cone_angle: 0.0,
+        }
+    }
+}
+
+impl ConeConstraintSettings {
+    pub fn new(point: Vec3A, twist_axis: Vec3A, half_cone_angle: f32) -> ConeConstraintSettings {
+        ConeConstraintSettings {
+            point1: point,
+            twist_axis1: twist_axis,
+            point2: point,
+            twist_axis2: twist_axis,
+            half_cone_angle,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct SwingTwistConstraintSettings {
+    pub user_data: u64,
+    pub enabled: bool,
+    pub constraint_priority: u32,
+    pub num_velocity_steps_override: u32,
+    pub num_position_steps_override: u32,
+    pub space: ConstraintSpace,
+    pub position1: Vec3A,
+    pub twist_axis1: Vec3A,
+    pub plane_axis1: Vec3A,
+    pub position2: Vec3A,
+    pub twist_axis2: Vec3A,
+    pub plane_axis2: Vec3A,
+    pub swing_type: SwingType,
+    pub normal_half_cone_angle: f32,
+    pub plane_half_cone_angle: f32,
+    pub twist_min_angle: f32,
+    pub twist_max_angle: f32,
+    pub max_friction_torque: f32,
+    pub swing_motor_settings: MotorSettings,
+    pub twist_motor_settings: MotorSettings,
+}
+const_assert_eq!(mem::size_of::<SwingTwistConstraintSettings>(), 208);
+
+unsafe impl ExternType for SwingTwistConstraintSettings {
+    type Id = type_id!("XSwingTwistConstraintSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for SwingTwistConstraintSettings {
+    fn default() -> SwingTwistConstraintSettings {
+        SwingTwistConstraintSettings {
+            user_data: 0,
+            enabled: true,
+            constraint_priority: 0,
+            num_velocity_steps_override: 0,
+            num_position_steps_override: 0,
+            space: ConstraintSpace::WorldSpace,
+            position1: Vec3A::ZERO,
+            twist_axis1: Vec3A::X,
+            plane_axis1: Vec3A::Y,
+            position2: Vec3A::ZERO,
+            twist_axis2: Vec3A::X,
+            plane_axis2: Vec3A::Y,
+            swing_type: SwingType::Cone,
+            normal_half_cone_angle: 0.0,
+            plane_half_cone_angle: 0.0,
+            twist_min_angle: 0.0,
+            twist_max_angle: 0.0,
+            max_friction_torque: 0.0,
+            swing_motor_settings: MotorSettings::default(),
+            twist_motor_settings: MotorSettings::default(),
+        }
+    }
+}
+
+impl SwingTwistConstraintSettings {
+    pub fn new(position: Vec3A, twist_axis: Vec3A, plane_axis: Vec3A) -> SwingTwistConstraintSettings {
+        SwingTwistConstraintSettings {
+            position1: position,
+            twist_axis1: twist_axis,
+            plane_axis1: plane_axis,
+            position2: position,
+            twist_axis2: twist_axis,
+            plane_axis2: plane_axis,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct SixDOFConstraintSettings {
+    pub user_data: u64,
+    pub enabled: bool,
+    pub constraint_priority: u32,
+    pub num_velocity_steps_override: u32,
+    pub num_position_steps_override: u32,
+    pub space: ConstraintSpace,
+    pub position1: Vec3A,
+    pub axis_x1: Vec3A,
+    pub axis_y1: Vec3A,
+    pub position2: Vec3A,
+    pub axis_x2: Vec3A,
+    pub axis_y2: Vec3A,
+    pub swing_type: SwingType,
+    pub max_friction: [f32; 6],
+    pub limit_min: [f32; 6],
+    pub limit_max: [f32; 6],
+    pub limits_spring_settings: [SpringSettings; 3],
+    pub motor_settings: [MotorSettings; 6],
+}
+const_assert_eq!(mem::size_of::<SixDOFConstraintSettings>(), 416);
+
+unsafe impl ExternType for SixDOFConstraintSettings {
+    type Id = type_id!("XSixDOFConstraintSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for SixDOFConstraintSettings {
+    fn default() -> SixDOFConstraintSettings {
+        SixDOFConstraintSettings {
+            user_data: 0,
+            enabled: true,
+            constraint_priority: 0,
+            num_velocity_steps_override: 0,
+            num_position_steps_override: 0,
+            space: ConstraintSpace::WorldSpace,
+            position1: Vec3A::ZERO,
+            axis_x1: Vec3A::X,
+            axis_y1: Vec3A::Y,
+            position2: Vec3A::ZERO,
+            axis_x2: Vec3A::X,
+            axis_y2: Vec3A::Y,
+            swing_type: SwingType::Cone,
+            max_friction: [0.0; 6],
+            limit_min: [-f32::MAX; 6],
+            limit_max: [f32::MAX; 6],
+            limits_spring_settings: [SpringSettings::default(); 3],
+            motor_settings: [MotorSettings::default(); 6],
+        }
+    }
+}
+
+impl SixDOFConstraintSettings {
+    pub fn new(position: Vec3A, axis_x: Vec3A, axis_y: Vec3A) -> SixDOFConstraintSettings {
+        SixDOFConstraintSettings {
+            position1: position,
+            axis_x1: axis_x,
+            axis_y1: axis_y,
+            position2: position,
+            axis_x2: axis_x,
+            axis_y2: axis_y,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn make_free_axis(&mut self, axis: SixDOFAxis) {
+        self.limit_min[axis.repr as usize] = -f32::MAX;
+        self.limit_max[axis.repr as usize] = f32::MAX;
+    }
+
+    #[inline]
+    pub fn is_free_axis(&self, axis: SixDOFAxis) -> bool {
+        self.limit_min[axis.repr as usize] == -f32::MAX && self.limit_max[axis.repr as usize] == f32::MAX
+    }
+
+    #[inline]
+    pub fn make_fixed_axis(&mut self, axis: SixDOFAxis) {
+        self.limit_min[axis.repr as usize] = f32::MAX;
+        self.limit_max[axis.repr as usize] = -f32::MAX;
+    }
+
+    #[inline]
+    pub fn is_fixed_axis(&self, axis: SixDOFAxis) -> bool {
+        self.limit_min[axis.repr as usize] >= self.limit_max[axis.repr as usize]
+    }
+
+    #[inline]
+    pub fn set_limited_axis(&mut self, axis: SixDOFAxis, min: f32, max: f32) {
+        self.limit_min[axis.repr as usize] = min;
+        self.limit_max[axis.repr as usize] = max;
+    }
+}
+
+#[inline]
+pub fn create_fixed_constraint<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &FixedConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JRef<Constraint>> {
+    create_fixed_constraint_mut(system, settings, body1, body2).map(|c| c.into())
+}
+
+#[inline]
+pub fn create_fixed_constraint_mut<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &FixedConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JMut<Constraint>> {
+    unsafe {
+        let ptr = ffi::CreateFixedConstraint(
+            system.as_x_ptr(),
+            mem::transmute::<&FixedConstraintSettings, &ffi::XFixedConstraintSettings>(settings),
+            &body1,
+            &body2,
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<Constraint>::new_unchecked(ptr))
+    }
+}
+
+#[inline]
+pub fn create_point_constraint<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &PointConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JRef<Constraint>> {
+    create_point_constraint_mut(system, settings, body1, body2).map(|c| c.into())
+}
+
+#[inline]
+pub fn create_point_constraint_mut<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &PointConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JMut<Constraint>> {
+    unsafe {
+        let ptr = ffi::CreatePointConstraint(
+            system.as_x_ptr(),
+            mem::transmute::<&PointConstraintSettings, &ffi::XPointConstraintSettings>(settings),
+            &body1,
+            &body2,
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<Constraint>::new_unchecked(ptr))
+    }
+}
+
+#[inline]
+pub fn create_hinge_constraint<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &HingeConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JRef<Constraint>> {
+    create_hinge_constraint_mut(system, settings, body1, body2).map(|c| c.into())
+}
+
+#[inline]
+pub fn create_hinge_constraint_mut<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &HingeConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JMut<Constraint>> {
+    unsafe {
+        let ptr = ffi::CreateHingeConstraint(
+            system.as_x_ptr(),
+            mem::transmute::<&HingeConstraintSettings, &ffi::XHingeConstraintSettings>(settings),
+            &body1,
+            &body2,
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<Constraint>::new_unchecked(ptr))
+    }
+}
+
+#[inline]
+pub fn create_slider_constraint<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &SliderConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JRef<Constraint>> {
+    create_slider_constraint_mut(system, settings, body1, body2).map(|c| c.into())
+}
+
+#[inline]
+pub fn create_slider_constraint_mut<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &SliderConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JMut<Constraint>> {
+    unsafe {
+        let ptr = ffi::CreateSliderConstraint(
+            system.as_x_ptr(),
+            mem::transmute::<&SliderConstraintSettings, &ffi::XSliderConstraintSettings>(settings),
+            &body1,
+            &body2,
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<Constraint>::new_unchecked(ptr))
+    }
+}
+
+#[inline]
+pub fn create_distance_constraint<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &DistanceConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JRef<Constraint>> {
+    create_distance_constraint_mut(system, settings, body1, body2).map(|c| c.into())
+}
+
+#[inline]
+pub fn create_distance_constraint_mut<
+    CL: ContactListener,
+    BAL: BodyActivationListener,
+    SBCL: SoftBodyContactListener,
+>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &DistanceConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JMut<Constraint>> {
+    unsafe {
+        let ptr = ffi::CreateDistanceConstraint(
+            system.as_x_ptr(),
+            mem::transmute::<&DistanceConstraintSettings, &ffi::XDistanceConstraintSettings>(settings),
+            &body1,
+            &body2,
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<Constraint>::new_unchecked(ptr))
+    }
+}
+
+#[inline]
+pub fn create_cone_constraint<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &ConeConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JRef<Constraint>> {
+    create_cone_constraint_mut(system, settings, body1, body2).map(|c| c.into())
+}
+
+#[inline]
+pub fn create_cone_constraint_mut<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &ConeConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JMut<Constraint>> {
+    unsafe {
+        let ptr = ffi::CreateConeConstraint(
+            system.as_x_ptr(),
+            mem::transmute::<&ConeConstraintSettings, &ffi::XConeConstraintSettings>(settings),
+            &body1,
+            &body2,
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<Constraint>::new_unchecked(ptr))
+    }
+}
+
+#[inline]
+pub fn create_swing_twist_constraint<
+    CL: ContactListener,
+    BAL: BodyActivationListener,
+    SBCL: SoftBodyContactListener,
+>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &SwingTwistConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JRef<SwingTwistConstraint>> {
+    create_swing_twist_constraint_mut(system, settings, body1, body2).map(|c| c.into())
+}
+
+#[inline]
+pub fn create_swing_twist_constraint_mut<
+    CL: ContactListener,
+    BAL: BodyActivationListener,
+    SBCL: SoftBodyContactListener,
+>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &SwingTwistConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JMut<SwingTwistConstraint>> {
+    unsafe {
+        let ptr = ffi::CreateSwingTwistConstraint(
+            system.as_x_ptr(),
+            mem::transmute::<&SwingTwistConstraintSettings, &ffi::XSwingTwistConstraintSettings>(settings),
+            &body1,
+            &body2,
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<SwingTwistConstraint>::new_unchecked(ptr))
+    }
+}
+
+#[inline]
+pub fn create_six_dof_constraint<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &SixDOFConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JRef<SixDOFConstraint>> {
+    create_six_dof_constraint_mut(system, settings, body1, body2).map(|c| c.into())
+}
+
+#[inline]
+pub fn create_six_dof_constraint_mut<
+    CL: ContactListener,
+    BAL: BodyActivationListener,
+    SBCL: SoftBodyContactListener,
+>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    settings: &SixDOFConstraintSettings,
+    body1: BodyID,
+    body2: BodyID,
+) -> JoltResult<JMut<SixDOFConstraint>> {
+    unsafe {
+        let ptr = ffi::CreateSixDOFConstraint(
+            system.as_x_ptr(),
+            mem::transmute::<&SixDOFConstraintSettings, &ffi::XSixDOFConstraintSettings>(settings),
+            &body1,
+            &body2,
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<SixDOFConstraint>::new_unchecked(ptr))
+    }
+}
+
+/// Keeps the physics system it was created in alive, it refers to the bodies of that system.
+/// Dropping the `PhysicsSystem` removes all constraints from it.
+pub struct Constraint(pub(crate) ffi::Constraint);
+
+const_assert_eq!(mem::size_of::<JRef<Constraint>>(), mem::size_of::<usize>());
+
+impl fmt::Debug for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Constraint")
+            .field("type", &self.get_type())
+            .field("sub_type", &self.get_sub_type())
+            .field("enabled", &self.get_enabled())
+            .field("user_data", &self.get_user_data())
+            .finish()
+    }
+}
+
+unsafe impl JRefTarget for Constraint {
+    type JRaw = NonNull<Constraint>;
+
+    #[inline]
+    fn name() -> &'static str {
+        "Constraint"
+    }
+
+    #[inline]
+    unsafe fn make_ref(raw: &Self::JRaw) -> &Self {
+        unsafe { raw.as_ref() }
+    }
+
+    #[inline]
+    unsafe fn clone_raw(raw: &Self::JRaw) -> Self::JRaw {
+        NonNull::new_unchecked(ffi::CloneConstraint(raw.as_ptr() as *mut _) as *mut _)
+    }
+
+    #[inline]
+    unsafe fn drop_raw(raw: &mut Self::JRaw) {
+        ffi::DropConstraint(raw.as_ptr() as *mut _);
+    }
+
+    #[inline]
+    unsafe fn count_ref(raw: &Self::JRaw) -> u32 {
+        unsafe { ffi::CountRefConstraint(raw.as_ptr() as *const _) }
+    }
+}
+
+unsafe impl JMutTarget for Constraint {
+    #[inline]
+    unsafe fn make_mut(raw: &mut Self::JRaw) -> &mut Self {
+        unsafe { raw.as_mut() }
+    }
+
+    #[inline]
+    unsafe fn steal_raw(raw: &Self::JRaw) -> Self::JRaw {
+        *raw
+    }
+}
+
+impl JMut<Constraint> {
+    #[inline]
+    pub(crate) unsafe fn new_unchecked(raw: *mut ffi::Constraint) -> JMut<Constraint> {
+        JMut(unsafe { NonNull::new_unchecked(raw as *mut _) })
+    }
+}
+
+impl Constraint {
+    #[inline]
+    fn as_ref(&self) -> &ffi::Constraint {
+        &self.0
+    }
+
+    #[inline]
+    fn as_mut(&mut self) -> Pin<&mut ffi::Constraint> {
+        unsafe { Pin::new_unchecked(&mut self.0) }
+    }
+
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *mut ffi::Constraint {
+        &self.0 as *const _ as *mut _
+    }
+
+    #[inline]
+    pub fn get_type(&self) -> ConstraintType {
+        self.as_ref().GetType()
+    }
+
+    #[inline]
+    pub fn get_sub_type(&self) -> ConstraintSubType {
+        self.as_ref().GetSubType()
+    }
+
+    #[inline]
+    pub fn get_constraint_priority(&self) -> u32 {
+        self.as_ref().GetConstraintPriority()
+    }
+
+    #[inline]
+    pub fn set_constraint_priority(&mut self, priority: u32) {
+        self.as_mut().SetConstraintPriority(priority);
+    }
+
+    #[inline]
+    pub fn get_num_velocity_steps_override(&self) -> u32 {
+        self.as_ref().GetNumVelocityStepsOverride()
+    }
+
+    #[inline]
+    pub fn set_num_velocity_steps_override(&mut self, steps: u32) {
+        self.as_mut().SetNumVelocityStepsOverride(steps);
+    }
+
+    #[inline]
+    pub fn get_num_position_steps_override(&self) -> u32 {
+        self.as_ref().GetNumPositionStepsOverride()
+    }
+
+    #[inline]
+    pub fn set_num_position_steps_override(&mut self, steps: u32) {
+        self.as_mut().SetNumPositionStepsOverride(steps);
+    }
+
+    #[inline]
+    pub fn get_enabled(&self) -> bool {
+        self.as_ref().GetEnabled()
+    }
+
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.as_mut().SetEnabled(enabled);
+    }
+
+    #[inline]
+    pub fn get_user_data(&self) -> u64 {
+        self.as_ref().GetUserData()
+    }
+
+    #[inline]
+    pub fn set_user_data(&mut self, user_data: u64) {
+        self.as_mut().SetUserData(user_data);
+    }
+
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.as_ref().IsActive()
+    }
+}
+
+pub struct SwingTwistConstraint(pub(crate) ffi::SwingTwistConstraint);
+
+impl fmt::Debug for SwingTwistConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwingTwistConstraint")
+            .field("enabled", &self.as_constraint().get_enabled())
+            .field("swing_motor_state", &self.get_swing_motor_state())
+            .field("twist_motor_state", &self.get_twist_motor_state())
+            .field("user_data", &self.as_constraint().get_user_data())
+            .finish()
+    }
+}
+
+unsafe impl JRefTarget for SwingTwistConstraint {
+    type JRaw = NonNull<SwingTwistConstraint>;
+
+    #[inline]
+    fn name() -> &'static str {
+        "SwingTwistConstraint"
+    }
+
+    #[inline]
+    unsafe fn make_ref(raw: &Self::JRaw) -> &Self {
+        unsafe { raw.as_ref() }
+    }
+
+    #[inline]
+    unsafe fn clone_raw(raw: &Self::JRaw) -> Self::JRaw {
+        NonNull::new_unchecked(ffi::CloneSwingTwistConstraint(raw.as_ptr() as *mut _) as *mut _)
+    }
+
+    #[inline]
+    unsafe fn drop_raw(raw: &mut Self::JRaw) {
+        ffi::DropSwingTwistConstraint(raw.as_ptr() as *mut _);
+    }
+
+    #[inline]
+    unsafe fn count_ref(raw: &Self::JRaw) -> u32 {
+        unsafe { ffi::CountRefSwingTwistConstraint(raw.as_ptr() as *const _) }
+    }
+}
+
+unsafe impl JMutTarget for SwingTwistConstraint {
+    #[inline]
+    unsafe fn make_mut(raw: &mut Self::JRaw) -> &mut Self {
+        unsafe { raw.as_mut() }
+    }
+
+    #[inline]
+    unsafe fn steal_raw(raw: &Self::JRaw) -> Self::JRaw {
+        *raw
+    }
+}
+
+impl JMut<SwingTwistConstraint> {
+    #[inline]
+    pub(crate) unsafe fn new_unchecked(raw: *mut ffi::SwingTwistConstraint) -> JMut<SwingTwistConstraint> {
+        JMut(unsafe { NonNull::new_unchecked(raw as *mut _) })
+    }
+}
+
+impl From<JMut<SwingTwistConstraint>> for JMut<Constraint> {
+    #[inline]
+    fn from(constraint: JMut<SwingTwistConstraint>) -> JMut<Constraint> {
+        let base = unsafe { JMut::<Constraint>::new_unchecked(constraint.0.as_ptr() as *mut _) };
+        mem::forget(constraint);
+        base
+    }
+}
+
+impl From<JRef<SwingTwistConstraint>> for JRef<Constraint> {
+    #[inline]
+    fn from(constraint: JRef<SwingTwistConstraint>) -> JRef<Constraint> {
+        let base = JRef::<Constraint>(constraint.0.cast());
+        mem::forget(constraint);
+        base
+    }
+}
+
+impl SwingTwistConstraint {
+    #[inline]
+    fn as_ref(&self) -> &ffi::SwingTwistConstraint {
+        &self.0
+    }
+
+    #[inline]
+    fn as_mut(&mut self) -> Pin<&mut ffi::SwingTwistConstraint> {
+        unsafe { Pin::new_unchecked(&mut self.0) }
+    }
+
+    #[inline]
+    pub fn as_constraint(&self) -> &Constraint {
+        unsafe { &*(self as *const _ as *const Constraint) }
+    }
+
+    #[inline]
+    pub fn as_constraint_mut(&mut self) -> &mut Constraint {
+        unsafe { &mut *(self as *mut _ as *mut Constraint) }
+    }
+
+    #[inline]
+    pub fn get_normal_half_cone_angle(&self) -> f32 {
+        self.as_ref().GetNormalHalfConeAngle()
+    }
+
+    #[inline]
+    pub fn set_normal_half_cone_angle(&mut self, angle: f32) {
+        self.as_mut().SetNormalHalfConeAngle(angle);
+    }
+
+    #[inline]
+    pub fn get_plane_half_cone_angle(&self) -> f32 {
+        self.as_ref().GetPlaneHalfConeAngle()
+    }
+
+    #[inline]
+    pub fn set_plane_half_cone_angle(&mut self, angle: f32) {
+        self.as_mut().SetPlaneHalfConeAngle(angle);
+    }
+
+    #[inline]
+    pub fn get_twist_min_angle(&self) -> f32 {
+        self.as_ref().GetTwistMinAngle()
+    }
+
+    #[inline]
+    pub fn set_twist_min_angle(&mut self, angle: f32) {
+        self.as_mut().SetTwistMinAngle(angle);
+    }
+
+    #[inline]
+    pub fn get_twist_max_angle(&self) -> f32 {
+        self.as_ref().GetTwistMaxAngle()
+    }
+
+    #[inline]
+    pub fn set_twist_max_angle(&mut self, angle: f32) {
+        self.as_mut().SetTwistMaxAngle(angle);
+    }
+
+    #[inline]
+    pub fn get_max_friction_torque(&self) -> f32 {
+        self.as_ref().GetMaxFrictionTorque()
+    }
+
+    #[inline]
+    pub fn set_max_friction_torque(&mut self, torque: f32) {
+        self.as_mut().SetMaxFrictionTorque(torque);
+    }
+
+    #[inline]
+    pub fn get_swing_motor_settings(&self) -> &MotorSettings {
+        self.as_ref().GetSwingMotorSettings()
+    }
+
+    #[inline]
+    pub fn get_swing_motor_settings_mut(&mut self) -> &mut MotorSettings {
+        self.as_mut().GetSwingMotorSettingsMut()
+    }
+
+    #[inline]
+    pub fn get_twist_motor_settings(&self) -> &MotorSettings {
+        self.as_ref().GetTwistMotorSettings()
+    }
+
+    #[inline]
+    pub fn get_twist_motor_settings_mut(&mut self) -> &mut MotorSettings {
+        self.as_mut().GetTwistMotorSettingsMut()
+    }
+
+    #[inline]
+    pub fn get_swing_motor_state(&self) -> MotorState {
+        self.as_ref().GetSwingMotorState()
+    }
+
+    #[inline]
+    pub fn set_swing_motor_state(&mut self, state: MotorState) {
+        self.as_mut().SetSwingMotorState(state);
+    }
+
+    #[inline]
+    pub fn get_twist_motor_state(&self) -> MotorState {
+        self.as_ref().GetTwistMotorState()
+    }
+
+    #[inline]
+    pub fn set_twist_motor_state(&mut self, state: MotorState) {
+        self.as_mut().SetTwistMotorState(state);
+    }
+
+    #[inline]
+    pub fn get_target_angular_velocity_cs(&self) -> Vec3A {
+        self.as_ref().GetTargetAngularVelocityCS().into()
+    }
+
+    #[inline]
+    pub fn set_target_angular_velocity_cs(&mut self, velocity: Vec3A) {
+        self.as_mut().SetTargetAngularVelocityCS(velocity.into());
+    }
+
+    #[inline]
+    pub fn get_target_orientation_cs(&self) -> Quat {
+        self.as_ref().GetTargetOrientationCS().into()
+    }
+
+    #[inline]
+    pub fn set_target_orientation_cs(&mut self, orientation: Quat) {
+        self.as_mut().SetTargetOrientationCS(orientation.into());
+    }
+
+    #[inline]
+    pub fn set_target_orientation_bs(&mut self, orientation: Quat) {
+        self.as_mut().SetTargetOrientationBS(orientation.into());
+    }
+
+    #[inline]
+    pub fn get_rotation_in_constraint_space(&self) -> Quat {
+        self.as_ref().GetRotationInConstraintSpace().into()
+    }
+}
+
+pub struct SixDOFConstraint(pub(crate) ffi::SixDOFConstraint);
+
+impl fmt::Debug for SixDOFConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SixDOFConstraint")
+            .field("enabled", &self.as_constraint().get_enabled())
+            .field("user_data", &self.as_constraint().get_user_data())
+            .finish()
+    }
+}
+
+unsafe impl JRefTarget for SixDOFConstraint {
+    type JRaw = NonNull<SixDOFConstraint>;
+
+    #[inline]
+    fn name() -> &'static str {
+        "SixDOFConstraint"
+    }
+
+    #[inline]
+    unsafe fn make_ref(raw: &Self::JRaw) -> &Self {
+        unsafe { raw.as_ref() }
+    }
+
+    #[inline]
+    unsafe fn clone_raw(raw: &Self::JRaw) -> Self::JRaw {
+        NonNull::new_unchecked(ffi::CloneSixDOFConstraint(raw.as_ptr() as *mut _) as *mut _)
+    }
+
+    #[inline]
+    unsafe fn drop_raw(raw: &mut Self::JRaw) {
+        ffi::DropSixDOFConstraint(raw.as_ptr() as *mut _);
+    }
+
+    #[inline]
+    unsafe fn count_ref(raw: &Self::JRaw) -> u32 {
+        unsafe { ffi::CountRefSixDOFConstraint(raw.as_ptr() as *const _) }
+    }
+}
+
+unsafe impl JMutTarget for SixDOFConstraint {
+    #[inline]
+    unsafe fn make_mut(raw: &mut Self::JRaw) -> &mut Self {
+        unsafe { raw.as_mut() }
+    }
+
+    #[inline]
+    unsafe fn steal_raw(raw: &Self::JRaw) -> Self::JRaw {
+        *raw
+    }
+}
+
+impl JMut<SixDOFConstraint> {
+    #[inline]
+    pub(crate) unsafe fn new_unchecked(raw: *mut ffi::SixDOFConstraint) -> JMut<SixDOFConstraint> {
+        JMut(unsafe { NonNull::new_unchecked(raw as *mut _) })
+    }
+}
+
+impl From<JMut<SixDOFConstraint>> for JMut<Constraint> {
+    #[inline]
+    fn from(constraint: JMut<SixDOFConstraint>) -> JMut<Constraint> {
+        let base = unsafe { JMut::<Constraint>::new_unchecked(constraint.0.as_ptr() as *mut _) };
+        mem::forget(constraint);
+        base
+    }
+}
+
+impl From<JRef<SixDOFConstraint>> for JRef<Constraint> {
+    #[inline]
+    fn from(constraint: JRef<SixDOFConstraint>) -> JRef<Constraint> {
+        let base = JRef::<Constraint>(constraint.0.cast());
+        mem::forget(constraint);
+        base
+    }
+}
+
+impl SixDOFConstraint {
+    #[inline]
+    fn as_ref(&self) -> &ffi::SixDOFConstraint {
+        &self.0
+    }
+
+    #[inline]
+    fn as_mut(&mut self) -> Pin<&mut ffi::SixDOFConstraint> {
+        unsafe { Pin::new_unchecked(&mut self.0) }
+    }
+
+    #[inline]
+    pub fn as_constraint(&self) -> &Constraint {
+        unsafe { &*(self as *const _ as *const Constraint) }
+    }
+
+    #[inline]
+    pub fn as_constraint_mut(&mut self) -> &mut Constraint {
+        unsafe { &mut *(self as *mut _ as *mut Constraint) }
+    }
+
+    #[inline]
+    pub fn set_translation_limits(&mut self, limit_min: Vec3A, limit_max: Vec3A) {
+        self.as_mut().SetTranslationLimits(limit_min.into(), limit_max.into());
+    }
+
+    #[inline]
+    pub fn set_rotation_limits(&mut self, limit_min: Vec3A, limit_max: Vec3A) {
+        self.as_mut().SetRotationLimits(limit_min.into(), limit_max.into());
+    }
+
+    #[inline]
+    pub fn get_limits_min(&self, axis: SixDOFAxis) -> f32 {
+        self.as_ref().GetLimitsMin(axis)
+    }
+
+    #[inline]
+    pub fn get_limits_max(&self, axis: SixDOFAxis) -> f32 {
+        self.as_ref().GetLimitsMax(axis)
+    }
+
+    #[inline]
+    pub fn is_fixed_axis(&self, axis: SixDOFAxis) -> bool {
+        self.as_ref().IsFixedAxis(axis)
+    }
+
+    #[inline]
+    pub fn is_free_axis(&self, axis: SixDOFAxis) -> bool {
+        self.as_ref().IsFreeAxis(axis)
+    }
+
+    /// Only translation axes have limit springs.
+    #[inline]
+    pub fn get_limits_spring_settings(&self, axis: SixDOFAxis) -> &SpringSettings {
+        assert!(axis.repr <= SixDOFAxis::TranslationZ.repr, "not a translation axis");
+        self.as_ref().GetLimitsSpringSettings(axis)
+    }
+
+    /// Only translation axes have limit springs.
+    #[inline]
+    pub fn set_limits_spring_settings(&mut self, axis: SixDOFAxis, settings: &SpringSettings) {
+        assert!(axis.repr <= SixDOFAxis::TranslationZ.repr, "not a translation axis");
+        self.as_mut().SetLimitsSpringSettings(axis, settings);
+    }
+
+    #[inline]
+    pub fn get_max_friction(&self, axis: SixDOFAxis) -> f32 {
+        self.as_ref().GetMaxFriction(axis)
+    }
+
+    #[inline]
+    pub fn set_max_friction(&mut self, axis: SixDOFAxis, friction: f32) {
+        self.as_mut().SetMaxFriction(axis, friction);
+    }
+
+    #[inline]
+    pub fn get_motor_settings(&self, axis: SixDOFAxis) -> &MotorSettings {
+        self.as_ref().GetMotorSettings(axis)
+    }
+
+    #[inline]
+    pub fn get_motor_settings_mut(&mut self, axis: SixDOFAxis) -> &mut MotorSettings {
+        self.as_mut().GetMotorSettingsMut(axis)
+    }
+
+    #[inline]
+    pub fn get_motor_state(&self, axis: SixDOFAxis) -> MotorState {
+        self.as_ref().GetMotorState(axis)
+    }
+
+    #[inline]
+    pub fn set_motor_state(&mut self, axis: SixDOFAxis, state: MotorState) {
+        self.as_mut().SetMotorState(axis, state);
+    }
+
+    #[inline]
+    pub fn get_target_velocity_cs(&self) -> Vec3A {
+        self.as_ref().GetTargetVelocityCS().into()
+    }
+
+    #[inline]
+    pub fn set_target_velocity_cs(&mut self, velocity: Vec3A) {
+        self.as_mut().SetTargetVelocityCS(velocity.into());
+    }
+
+    #[inline]
+    pub fn get_target_angular_velocity_cs(&self) -> Vec3A {
+        self.as_ref().GetTargetAngularVelocityCS().into()
+    }
+
+    #[inline]
+    pub fn set_target_angular_velocity_cs(&mut self, velocity: Vec3A) {
+        self.as_mut().SetTargetAngularVelocityCS(velocity.into());
+    }
+
+    #[inline]
+    pub fn get_target_position_cs(&self) -> Vec3A {
+        self.as_ref().GetTargetPositionCS().into()
+    }
+
+    #[inline]
+    pub fn set_target_position_cs(&mut self, position: Vec3A) {
+        self.as_mut().SetTargetPositionCS(position.into());
+    }
+
+    #[inline]
+    pub fn get_target_orientation_cs(&self) -> Quat {
+        self.as_ref().GetTargetOrientationCS().into()
+    }
+
+    #[inline]
+    pub fn set_target_orientation_cs(&mut self, orientation: Quat) {
+        self.as_mut().SetTargetOrientationCS(orientation.into());
+    }
+
+    #[inline]
+    pub fn set_target_orientation_bs(&mut self, orientation: Quat) {
+        self.as_mut().SetTargetOrientationBS(orientation.into());
+    }
+
+    #[inline]
+    pub fn get_rotation_in_constraint_space(&self) -> Quat {
+        self.as_ref().GetRotationInConstraintSpace().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::MotionType;
+    use crate::body::BodyCreationSettings;
+    use crate::shape::{create_box_shape, BoxShapeSettings};
+    use crate::system::PhysicsSystemConfig;
+    use crate::test_callback::create_test_system;
+
+    fn create_anchor_and_box(system: &mut PhysicsSystem, box_position: Vec3A) -> (BodyID, BodyID) {
+        let anchor = create_box_shape(&BoxShapeSettings::new(0.1, 0.1, 0.1)).unwrap();
+        let cube = create_box_shape(&BoxShapeSettings::new(0.25, 0.25, 0.25)).unwrap();
+        let body_itf = system.body_itf();
+        let anchor_id = body_itf
+            .create_add_body(
+                &BodyCreationSettings::new_static(anchor, 0, Vec3A::ZERO, Quat::IDENTITY),
+                false,
+            )
+            .unwrap();
+        let settings = BodyCreationSettings::new(cube, 0, MotionType::Dynamic, box_position, Quat::IDENTITY);
+        let box_id = body_itf.create_add_body(&settings, true).unwrap();
+        (anchor_id, box_id)
+    }
+
+    #[test]
+    fn test_hinge_constraint() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let (anchor_id, box_id) = create_anchor_and_box(&mut system, Vec3A::new(1.0, 0.0, 0.0));
+        let settings = HingeConstraintSettings::new(Vec3A::ZERO, Vec3A::Z, Vec3A::X);
+        let hinge = create_hinge_constraint(&mut system, &settings, anchor_id, box_id).unwrap();
+        assert_eq!(hinge.get_sub_type(), ConstraintSubType::Hinge);
+
+        assert!(!system.remove_constraint(&hinge));
+        assert!(system.add_constraint(&hinge));
+        assert!(!system.add_constraint(&hinge));
+        for _ in 0..60 {
+            system.update(1.0 / 60.0, 1).unwrap();
+        }
+        let position = system.body_itf().get_position(box_id);
+        assert!(position.y < -0.1);
+        assert!((position.length() - 1.0).abs() < 0.05);
+
+        assert!(system.remove_constraint(&hinge));
+        assert!(!system.remove_constraint(&hinge));
+        for _ in 0..60 {
+            system.update(1.0 / 60.0, 1).unwrap();
+        }
+        assert!(system.body_itf().get_position(box_id).length() > 2.0);
+    }
+
+    #[test]
+    fn test_constraint_outlives_system() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let (anchor_id, box_id) = create_anchor_and_box(&mut system, Vec3A::new(1.0, 0.0, 0.0));
+        let count = system.count_ref();
+        let settings = HingeConstraintSettings::new(Vec3A::ZERO, Vec3A::Z, Vec3A::X);
+        let hinge = create_hinge_constraint(&mut system, &settings, anchor_id, box_id).unwrap();
+        assert_eq!(system.count_ref(), count + 1);
+        assert!(system.add_constraint(&hinge));
+        system.update(1.0 / 60.0, 1).unwrap();
+
+        drop(system);
+        assert_eq!(hinge.count_ref(), 1);
+        assert!(hinge.get_enabled());
+        assert!(hinge.is_active());
+    }
+
+    #[test]
+    fn test_fixed_constraint() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let (anchor_id, box_id) = create_anchor_and_box(&mut system, Vec3A::new(0.0, 1.0, 0.0));
+        let fixed = create_fixed_constraint(
+            &mut system,
+            &FixedConstraintSettings::new_auto_detect(),
+            anchor_id,
+            box_id,
+        )
+        .unwrap();
+        assert_eq!(fixed.get_sub_type(), ConstraintSubType::Fixed);
+
+        assert!(system.add_constraint(&fixed));
+        for _ in 0..60 {
+            system.update(1.0 / 60.0, 1).unwrap();
+        }
+        let position = system.body_itf().get_position(box_id);
+        assert!((position - Vec3A::new(0.0, 1.0, 0.0)).length() < 0.01);
+
+        let mut other: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        create_anchor_and_box(&mut other, Vec3A::new(0.0, 1.0, 0.0));
+        assert!(!other.add_constraint(&fixed));
+        assert!(!other.remove_constraint(&fixed));
+
+        assert!(system.remove_constraint(&fixed));
+    }
+}
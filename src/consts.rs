@@ -2,3 +2,10 @@ pub const DEFAULT_CONVEX_RADIUS: f32 = 0.01;
 pub const MIN_CONVEX_RADIUS: f32 = 0.0;
 pub const MAX_CONVEX_RADIUS: f32 = 0.05;
 pub const DEFAULT_ERROR_CONVEX_RADIUS: f32 = 0.05;
+
+pub const MAX_PHYSICS_JOBS: u32 = 2048;
+pub const MAX_PHYSICS_BARRIERS: u32 = 8;
+
+pub const UPDATE_ERROR_MANIFOLD_CACHE_FULL: u32 = 1 << 0;
+pub const UPDATE_ERROR_BODY_PAIR_CACHE_FULL: u32 = 1 << 1;
+pub const UPDATE_ERROR_CONTACT_CONSTRAINTS_FULL: u32 = 1 << 2;
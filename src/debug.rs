@@ -3,10 +3,12 @@ use glam::Vec3A;
 use static_assertions::{assert_cfg, const_assert_eq};
 use std::{
     mem,
+    sync::atomic::Ordering,
     sync::{Arc, Mutex},
 };
 
 use crate::base::JVec3;
+use crate::system::DEBUG_RENDERER_ALIVE;
 
 assert_cfg!(windows, "Debug rendering is only supported on Windows");
 
@@ -223,11 +225,17 @@ pub trait DebugApp {
     fn get_camera_pivot(&mut self, heading: f32, pitch: f32) -> Vec3A;
 }
 
+/// Panics if a `DebugFrameRecorder` is alive, both use the global Jolt debug renderer.
 pub fn run_debug_application(mut dbg_app: Arc<Mutex<dyn DebugApp>>) {
+    assert!(
+        !DEBUG_RENDERER_ALIVE.swap(true, Ordering::SeqCst),
+        "Jolt debug renderer already in use"
+    );
     let x_physics_system = dbg_app.lock().unwrap().cpp_physics_system() as *mut ffi::XPhysicsSystem;
     let rs_dbg_app = Box::new(RustDebugApp {
         dbg_app,
         x_physics_system,
     });
     ffi::RunDebugApplication(rs_dbg_app);
+    DEBUG_RENDERER_ALIVE.store(false, Ordering::SeqCst);
 }
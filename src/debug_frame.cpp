@@ -0,0 +1,78 @@
+#include "jolt-physics-rs/src/ffi.h"
+#include "jolt-physics-rs/src/debug_frame.rs.h"
+
+struct XDebugDrawSettings {
+	Vec3 cameraPosition;
+	DebugShapeColor shapeColor;
+	bool drawShape;
+	bool drawShapeWireframe;
+	bool drawBoundingBox;
+	bool drawCenterOfMassTransform;
+	bool drawWorldTransform;
+	bool drawVelocity;
+	bool drawMassAndInertia;
+	bool drawSleepStats;
+	bool drawConstraints;
+	bool drawConstraintLimits;
+	bool drawConstraintReferenceFrame;
+	bool drawCharacters;
+};
+static_assert(sizeof(XDebugDrawSettings) == 32, "XDebugDrawSettings size");
+
+class XDebugRenderer: public DebugRendererSimple {
+public:
+	DebugFrame* _frame = nullptr;
+
+	void DrawLine(RVec3Arg from, RVec3Arg to, ColorArg color) override {
+		this->_frame->AddLine(Vec3(from), Vec3(to), color);
+	}
+
+	void DrawTriangle(RVec3Arg v1, RVec3Arg v2, RVec3Arg v3, ColorArg color, ECastShadow castShadow) override {
+		this->_frame->AddTriangle(Vec3(v1), Vec3(v2), Vec3(v3), color, castShadow == ECastShadow::On);
+	}
+
+	void DrawText3D(RVec3Arg position, const string_view& str, ColorArg color, float height) override {
+		this->_frame->AddText(Vec3(position), rust::Str(str.data(), str.size()), color, height);
+	}
+};
+
+XDebugRenderer* CreateDebugRenderer() {
+	return new XDebugRenderer();
+}
+
+void DropDebugRenderer(XDebugRenderer* renderer) {
+	delete renderer;
+}
+
+void DrawPhysicsSystem(XDebugRenderer* renderer, XPhysicsSystem* system, const XDebugDrawSettings& settings, DebugFrame& frame) {
+	renderer->_frame = &frame;
+	renderer->SetCameraPos(RVec3(settings.cameraPosition));
+
+	BodyManager::DrawSettings drawSettings;
+	drawSettings.mDrawShape = settings.drawShape;
+	drawSettings.mDrawShapeWireframe = settings.drawShapeWireframe;
+	drawSettings.mDrawShapeColor = (BodyManager::EShapeColor)settings.shapeColor;
+	drawSettings.mDrawBoundingBox = settings.drawBoundingBox;
+	drawSettings.mDrawCenterOfMassTransform = settings.drawCenterOfMassTransform;
+	drawSettings.mDrawWorldTransform = settings.drawWorldTransform;
+	drawSettings.mDrawVelocity = settings.drawVelocity;
+	drawSettings.mDrawMassAndInertia = settings.drawMassAndInertia;
+	drawSettings.mDrawSleepStats = settings.drawSleepStats;
+	system->PhySys().DrawBodies(drawSettings, renderer);
+
+	if (settings.drawConstraints) {
+		system->PhySys().DrawConstraints(renderer);
+	}
+	if (settings.drawConstraintLimits) {
+		system->PhySys().DrawConstraintLimits(renderer);
+	}
+	if (settings.drawConstraintReferenceFrame) {
+		system->PhySys().DrawConstraintReferenceFrame(renderer);
+	}
+	if (settings.drawCharacters) {
+		system->DebugRender(renderer);
+	}
+
+	renderer->NextFrame();
+	renderer->_frame = nullptr;
+}
@@ -0,0 +1,253 @@
+use cxx::{kind, type_id, ExternType};
+use glam::Vec3A;
+use static_assertions::const_assert_eq;
+use std::mem;
+use std::sync::atomic::Ordering;
+
+use crate::base::{Color, JVec3};
+use crate::soft_body::SoftBodyContactListener;
+use crate::system::{BodyActivationListener, ContactListener, PhysicsSystem, DEBUG_RENDERER_ALIVE};
+
+#[cxx::bridge()]
+pub(crate) mod ffi {
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum DebugShapeColor {
+        InstanceColor,
+        ShapeTypeColor,
+        MotionTypeColor,
+        SleepColor,
+        IslandColor,
+        MaterialColor,
+    }
+
+    extern "Rust" {
+        type DebugFrame;
+
+        #[cxx_name = "AddLine"]
+        fn add_line(self: &mut DebugFrame, from: Vec3, to: Vec3, color: Color);
+        #[cxx_name = "AddTriangle"]
+        fn add_triangle(self: &mut DebugFrame, v1: Vec3, v2: Vec3, v3: Vec3, color: Color, cast_shadow: bool);
+        #[cxx_name = "AddText"]
+        fn add_text(self: &mut DebugFrame, position: Vec3, text: &str, color: Color, height: f32);
+    }
+
+    unsafe extern "C++" {
+        include!("rust/cxx.h");
+        include!("jolt-physics-rs/src/ffi.h");
+
+        type DebugShapeColor;
+
+        type Vec3 = crate::base::ffi::Vec3;
+        type Color = crate::base::ffi::Color;
+        type XPhysicsSystem = crate::system::ffi::XPhysicsSystem;
+        type XDebugDrawSettings = crate::debug_frame::DebugDrawSettings;
+
+        type XDebugRenderer;
+        fn CreateDebugRenderer() -> *mut XDebugRenderer;
+        unsafe fn DropDebugRenderer(renderer: *mut XDebugRenderer);
+        unsafe fn DrawPhysicsSystem(
+            renderer: *mut XDebugRenderer,
+            system: *mut XPhysicsSystem,
+            settings: &XDebugDrawSettings,
+            frame: &mut DebugFrame,
+        );
+    }
+}
+
+pub type DebugShapeColor = ffi::DebugShapeColor;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct DebugDrawSettings {
+    /// Used to select the level of detail of shapes, see `DebugRenderer::SetCameraPos`.
+    pub camera_position: Vec3A,
+    pub shape_color: DebugShapeColor,
+    pub draw_shape: bool,
+    pub draw_shape_wireframe: bool,
+    pub draw_bounding_box: bool,
+    pub draw_center_of_mass_transform: bool,
+    pub draw_world_transform: bool,
+    pub draw_velocity: bool,
+    pub draw_mass_and_inertia: bool,
+    pub draw_sleep_stats: bool,
+    pub draw_constraints: bool,
+    pub draw_constraint_limits: bool,
+    pub draw_constraint_reference_frame: bool,
+    pub draw_characters: bool,
+}
+const_assert_eq!(mem::size_of::<DebugDrawSettings>(), 32);
+
+unsafe impl ExternType for DebugDrawSettings {
+    type Id = type_id!("XDebugDrawSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for DebugDrawSettings {
+    fn default() -> Self {
+        DebugDrawSettings {
+            camera_position: Vec3A::ZERO,
+            shape_color: DebugShapeColor::MotionTypeColor,
+            draw_shape: true,
+            draw_shape_wireframe: false,
+            draw_bounding_box: false,
+            draw_center_of_mass_transform: false,
+            draw_world_transform: false,
+            draw_velocity: false,
+            draw_mass_and_inertia: false,
+            draw_sleep_stats: false,
+            draw_constraints: true,
+            draw_constraint_limits: false,
+            draw_constraint_reference_frame: false,
+            draw_characters: true,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugLine {
+    pub from: Vec3A,
+    pub to: Vec3A,
+    pub color: Color,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugTriangle {
+    pub v1: Vec3A,
+    pub v2: Vec3A,
+    pub v3: Vec3A,
+    pub color: Color,
+    pub cast_shadow: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugText {
+    pub position: Vec3A,
+    pub text: String,
+    pub color: Color,
+    pub height: f32,
+}
+
+/// Geometry emitted by the Jolt debug renderer during one draw call.
+#[derive(Debug, Clone, Default)]
+pub struct DebugFrame {
+    pub lines: Vec<DebugLine>,
+    pub triangles: Vec<DebugTriangle>,
+    pub texts: Vec<DebugText>,
+}
+
+impl DebugFrame {
+    pub fn new() -> DebugFrame {
+        DebugFrame::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.triangles.clear();
+        self.texts.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty() && self.triangles.is_empty() && self.texts.is_empty()
+    }
+
+    fn add_line(&mut self, from: JVec3, to: JVec3, color: Color) {
+        self.lines.push(DebugLine {
+            from: from.into(),
+            to: to.into(),
+            color,
+        });
+    }
+
+    fn add_triangle(&mut self, v1: JVec3, v2: JVec3, v3: JVec3, color: Color, cast_shadow: bool) {
+        self.triangles.push(DebugTriangle {
+            v1: v1.into(),
+            v2: v2.into(),
+            v3: v3.into(),
+            color,
+            cast_shadow,
+        });
+    }
+
+    fn add_text(&mut self, position: JVec3, text: &str, color: Color, height: f32) {
+        self.texts.push(DebugText {
+            position: position.into(),
+            text: text.to_string(),
+            color,
+            height,
+        });
+    }
+}
+
+/// Headless Jolt `DebugRenderer` that records into a `DebugFrame` instead of drawing.
+///
+/// Jolt keeps the debug renderer as a singleton, so at most one recorder is alive at a time.
+pub struct DebugFrameRecorder(*mut ffi::XDebugRenderer);
+
+impl Drop for DebugFrameRecorder {
+    fn drop(&mut self) {
+        unsafe { ffi::DropDebugRenderer(self.0) };
+        DEBUG_RENDERER_ALIVE.store(false, Ordering::SeqCst);
+    }
+}
+
+impl DebugFrameRecorder {
+    /// None while another recorder or the debug application holds the Jolt debug renderer.
+    pub fn new() -> Option<DebugFrameRecorder> {
+        if DEBUG_RENDERER_ALIVE.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        Some(DebugFrameRecorder(ffi::CreateDebugRenderer()))
+    }
+
+    /// Clears `frame` and records the bodies, constraints and characters of `system` into it.
+    pub fn draw<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+        &mut self,
+        system: &PhysicsSystem<CL, BAL, SBCL>,
+        settings: &DebugDrawSettings,
+        frame: &mut DebugFrame,
+    ) {
+        frame.clear();
+        unsafe { ffi::DrawPhysicsSystem(self.0, system.as_x_ptr(), settings, frame) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Quat;
+
+    use super::*;
+    use crate::body::{BodyCreationSettings, MotionType};
+    use crate::shape::{create_box_shape, BoxShapeSettings};
+    use crate::system::PhysicsSystemConfig;
+    use crate::test_callback::create_test_system;
+
+    #[test]
+    fn test_debug_frame_recorder() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let shape = create_box_shape(&BoxShapeSettings::new(0.5, 0.5, 0.5)).unwrap();
+        let settings = BodyCreationSettings::new(shape, 0, MotionType::Dynamic, Vec3A::ZERO, Quat::IDENTITY);
+        system.body_itf().create_add_body(&settings, true).unwrap();
+
+        let mut recorder = DebugFrameRecorder::new().unwrap();
+        assert!(DebugFrameRecorder::new().is_none());
+
+        let mut frame = DebugFrame::new();
+        recorder.draw(&system, &DebugDrawSettings::default(), &mut frame);
+        assert!(!frame.triangles.is_empty());
+
+        let settings = DebugDrawSettings {
+            draw_shape: false,
+            draw_constraints: false,
+            draw_characters: false,
+            ..Default::default()
+        };
+        recorder.draw(&system, &settings, &mut frame);
+        assert!(frame.is_empty());
+
+        drop(recorder);
+        assert!(DebugFrameRecorder::new().is_some());
+    }
+}
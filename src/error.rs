@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::consts::*;
+
 #[derive(Error, Debug)]
 pub enum JoltError {
     #[error("Create shape")]
@@ -8,9 +10,42 @@ pub enum JoltError {
     TooLessSubShape,
     #[error("Create body")]
     CreateBody,
+    #[error("Create constraint")]
+    CreateConstraint,
+    #[error("Create soft body")]
+    CreateSoftBody,
+    #[error("Restore state")]
+    RestoreState,
+    #[error("Restore shape")]
+    RestoreShape,
+    #[error("Describe shape")]
+    DescribeShape,
+    #[error("Load scene")]
+    LoadScene,
 
+    /// Raise `PhysicsSystemConfig::max_contact_constraints`.
+    #[error("Engine update: manifold cache full")]
+    ManifoldCacheFull,
+    /// Raise `PhysicsSystemConfig::max_body_pairs`.
+    #[error("Engine update: body pair cache full")]
+    BodyPairCacheFull,
+    /// Raise `PhysicsSystemConfig::max_contact_constraints`.
+    #[error("Engine update: contact constraints full")]
+    ContactConstraintsFull,
+    /// Several `UPDATE_ERROR_*` bits at once.
     #[error("Engine update ({0})")]
     EngineUpdate(u32),
 }
 
+impl JoltError {
+    pub(crate) fn from_update_error(bits: u32) -> JoltError {
+        match bits {
+            UPDATE_ERROR_MANIFOLD_CACHE_FULL => JoltError::ManifoldCacheFull,
+            UPDATE_ERROR_BODY_PAIR_CACHE_FULL => JoltError::BodyPairCacheFull,
+            UPDATE_ERROR_CONTACT_CONSTRAINTS_FULL => JoltError::ContactConstraintsFull,
+            bits => JoltError::EngineUpdate(bits),
+        }
+    }
+}
+
 pub type JoltResult<T> = Result<T, JoltError>;
@@ -5,6 +5,7 @@
 #endif
 #include <stdarg.h>
 #include <thread>
+#include <memory>
 #include <iostream>
 #include <unordered_set>
 
@@ -15,11 +16,24 @@
 #include <Jolt/Core/Factory.h>
 #include <Jolt/Core/TempAllocator.h>
 #include <Jolt/Core/JobSystemSingleThreaded.h>
+#include <Jolt/Core/JobSystemThreadPool.h>
 #include <Jolt/Core/FPException.h>
+#include <Jolt/Core/StateRecorderImpl.h>
+#include <Jolt/Core/HashCombine.h>
 #include <Jolt/Physics/PhysicsSettings.h>
 #include <Jolt/Physics/PhysicsSystem.h>
+#include <Jolt/Physics/PhysicsScene.h>
 #include <Jolt/Physics/Collision/BroadPhase/BroadPhaseLayer.h>
 #include <Jolt/Physics/Collision/CollideShape.h>
+#include <Jolt/Physics/Collision/RayCast.h>
+#include <Jolt/Physics/Collision/CastResult.h>
+#include <Jolt/Physics/Collision/ShapeCast.h>
+#include <Jolt/Physics/Collision/CollidePointResult.h>
+#include <Jolt/Physics/Collision/AABoxCast.h>
+#include <Jolt/Geometry/OrientedBox.h>
+#include <Jolt/Geometry/Indexify.h>
+#include <Jolt/Physics/Collision/CollisionCollectorImpl.h>
+#include <Jolt/Physics/Collision/PhysicsMaterialSimple.h>
 #include <Jolt/Physics/Collision/Shape/SphereShape.h>
 #include <Jolt/Physics/Collision/Shape/BoxShape.h>
 #include <Jolt/Physics/Collision/Shape/CapsuleShape.h>
@@ -41,8 +55,30 @@
 #include <Jolt/Physics/Body/BodyActivationListener.h>
 #include <Jolt/Physics/Character/Character.h>
 #include <Jolt/Physics/Character/CharacterVirtual.h>
+#include <Jolt/Physics/Constraints/FixedConstraint.h>
+#include <Jolt/Physics/Constraints/PointConstraint.h>
+#include <Jolt/Physics/Constraints/HingeConstraint.h>
+#include <Jolt/Physics/Constraints/SliderConstraint.h>
+#include <Jolt/Physics/Constraints/DistanceConstraint.h>
+#include <Jolt/Physics/Constraints/ConeConstraint.h>
+#include <Jolt/Physics/Constraints/SwingTwistConstraint.h>
+#include <Jolt/Physics/Constraints/SixDOFConstraint.h>
+#include <Jolt/Physics/Vehicle/VehicleConstraint.h>
+#include <Jolt/Physics/Vehicle/VehicleCollisionTester.h>
+#include <Jolt/Physics/Vehicle/WheeledVehicleController.h>
+#include <Jolt/Physics/Vehicle/TrackedVehicleController.h>
+#include <Jolt/Physics/Vehicle/MotorcycleController.h>
+#include <Jolt/Physics/SoftBody/SoftBodySharedSettings.h>
+#include <Jolt/Physics/SoftBody/SoftBodyCreationSettings.h>
+#include <Jolt/Physics/SoftBody/SoftBodyMotionProperties.h>
+#include <Jolt/Physics/SoftBody/SoftBodyContactListener.h>
+#include <Jolt/Physics/SoftBody/SoftBodyManifold.h>
 
 #ifdef JPH_DEBUG_RENDERER
+#include <Jolt/Renderer/DebugRendererSimple.h>
+#endif
+
+#ifdef JOLT_RS_DEBUG_APP
 #include <TestFramework/TestFramework.h>
 #include <Application/EntryPoint.h>
 #include <Application/Application.h>
@@ -134,6 +170,19 @@ uint32 RefCountRef(const T* rs_ptr) {
 
 class XPhysicsSystem;
 
+inline void StateRecorderToVec(const StateRecorderImpl& recorder, rust::Vec<uint8>& data) {
+	string str = recorder.GetData();
+	data.clear();
+	data.reserve(str.size());
+	for (char ch : str) {
+		data.push_back((uint8)ch);
+	}
+}
+
+inline void StateRecorderFromSlice(StateRecorderImpl& recorder, rust::Slice<const uint8> data) {
+	recorder.WriteBytes(data.data(), data.size());
+}
+
 #include "jolt-physics-rs/src/base.rs.h"
 
 //
@@ -144,6 +193,22 @@ inline void DropPhysicsMaterial(PhysicsMaterial* ptr) { DropRef<PhysicsMaterial>
 inline PhysicsMaterial* ClonePhysicsMaterial(PhysicsMaterial* ptr) { return CloneRef<PhysicsMaterial>(ptr); }
 inline uint32 CountRefPhysicsMaterial(const PhysicsMaterial* ptr) { return RefCountRef<PhysicsMaterial>(ptr); }
 
+class XPhysicsMaterial: public PhysicsMaterialSimple {
+	JPH_DECLARE_RTTI_VIRTUAL(JPH_EXPORT, XPhysicsMaterial)
+private:
+	uint8* _payload = nullptr;
+
+public:
+	XPhysicsMaterial() = default;
+	XPhysicsMaterial(const string_view& name, ColorArg color, uint8* payload)
+		: PhysicsMaterialSimple(name, color), _payload(payload) {}
+	~XPhysicsMaterial() override;
+	const uint8* GetPayload() const { return this->_payload; }
+};
+
+PhysicsMaterial* CreatePhysicsMaterial(rust::Str name, Color color, uint8* payload);
+const uint8* GetPhysicsMaterialPayload(const PhysicsMaterial& material);
+
 inline void DropShape(Shape* ptr) { DropRef<Shape>(ptr); }
 inline Shape* CloneShape(Shape* ptr) { return CloneRef<Shape>(ptr); }
 inline uint32 CountRefShape(const Shape* ptr) { return RefCountRef<Shape>(ptr); }
@@ -194,8 +259,45 @@ StaticCompoundShape* CreateStaticCompoundShape(const XStaticCompoundShapeSetting
 struct XMutableCompoundShapeSettings;
 MutableCompoundShape* CreateMutableCompoundShape(const XMutableCompoundShapeSettings& settings);
 typedef CompoundShape::SubShape XCompoundSubShape;
+
+void SaveShapeBinary(const Shape& shape, rust::Vec<uint8>& data);
+Shape* RestoreShapeBinary(rust::Slice<const uint8> data);
+bool CastRayOnShape(const Shape& shape, Vec3 origin, Vec3 direction, float& fraction, SubShapeID& subShapeId);
+void CollidePointOnShape(const Shape& shape, Vec3 point, rust::Vec<SubShapeID>& hits);
+Vec3 GetShapeSupport(const Shape& shape, Vec3 direction, Vec3 scale);
+uint32 CountShapeMaterials(const Shape& shape);
+PhysicsMaterial* CloneShapeMaterial(const Shape& shape, uint32 index);
+bool GetSphereShapeSettings(const Shape& shape, XSphereShapeSettings& settings);
+bool GetBoxShapeSettings(const Shape& shape, XBoxShapeSettings& settings);
+bool GetCapsuleShapeSettings(const Shape& shape, XCapsuleShapeSettings& settings);
+bool GetTaperedCapsuleShapeSettings(const Shape& shape, XTaperedCapsuleShapeSettings& settings);
+bool GetCylinderShapeSettings(const Shape& shape, XCylinderShapeSettings& settings);
+bool GetTaperedCylinderShapeSettings(const Shape& shape, XTaperedCylinderShapeSettings& settings);
+bool GetConvexHullShapeSettings(const Shape& shape, XConvexHullShapeSettings& settings, rust::Vec<Vec3>& points);
+bool GetTriangleShapeSettings(const Shape& shape, XTriangleShapeSettings& settings);
+bool GetPlaneShapeSettings(const Shape& shape, XPlaneShapeSettings& settings);
+bool GetMeshShapeTriangles(const Shape& shape, rust::Vec<Float3>& vertices, rust::Vec<IndexedTriangle>& triangles);
+bool GetHeightFieldShapeSettings(const Shape& shape, XHeightFieldShapeSettings& settings, rust::Vec<float>& heights, rust::Vec<uint8>& materialIndices);
+bool GetEmptyShapeSettings(const Shape& shape, XEmptyShapeSettings& settings);
+bool GetScaledShapeSettings(const Shape& shape, XScaledShapeSettings& settings);
+bool GetRotatedTranslatedShapeSettings(const Shape& shape, XRotatedTranslatedShapeSettings& settings);
+bool GetOffsetCenterOfMassShapeSettings(const Shape& shape, XOffsetCenterOfMassShapeSettings& settings);
+
+inline const Shape* GetDecoratedInnerShape(const Shape& shape) {
+	if (shape.GetType() != EShapeType::Decorated) {
+		return nullptr;
+	}
+	return static_cast<const DecoratedShape&>(shape).GetInnerShape();
+}
 static_assert(sizeof(XCompoundSubShape) == 40, "XCompoundSubShape size");
 
+//
+// body
+//
+
+void SaveBodyState(const Body& body, rust::Vec<uint8>& data);
+bool RestoreBodyState(Body& body, rust::Slice<const uint8> data);
+
 //
 // system
 //
@@ -208,6 +310,8 @@ public:
 void GlobalInitialize();
 void GlobalFinalize();
 
+typedef EStateRecorderState StateRecorderState;
+
 using XBodyStats = BodyManager::BodyStats;
 static_assert(sizeof(XBodyStats) == 36, "XBodyStats size");
 
@@ -219,19 +323,25 @@ public:
 	BodyID CreateAddBody(const BodyCreationSettings& settings, EActivation activation);
 };
 
+struct XPhysicsSystemConfig;
+class XCharacterVirtual;
+
 class XPhysicsSystem: public RefTarget<XPhysicsSystem> {
 private:
 	TempAllocatorImpl _allocator;
-	JobSystemSingleThreaded _jobSys;
+	unique_ptr<JobSystem> _jobSys;
 	PhysicsSystem _phySys;
 	rust::Fn<void (XPhysicsSystem&)> _rustCleanUp;
 	const BroadPhaseLayerInterface* _bpli;
 	const ObjectVsBroadPhaseLayerFilter* _obplf;
 	const ObjectLayerPairFilter* _olpf;
 	RENDERER_ONLY(unordered_set<XDebugRenderable*> _renderables;)
+	Array<XCharacterVirtual*> _characters;
+	unordered_set<Constraint*> _constraints;
 
 public:
 	XPhysicsSystem(
+		const XPhysicsSystemConfig& config,
 		rust::Fn<void (XPhysicsSystem&)> rustCleanUp,
 		const BroadPhaseLayerInterface* bpli,
 		const ObjectVsBroadPhaseLayerFilter* obplf,
@@ -239,7 +349,8 @@ public:
 	);
 	~XPhysicsSystem();
 	PhysicsSystem& PhySys() { return this->_phySys; }
-	JobSystemSingleThreaded& JobSys() { return this->_jobSys; }
+	const PhysicsSystem& PhySys() const { return this->_phySys; }
+	JobSystem& JobSys() { return *this->_jobSys; }
 	TempAllocatorImpl& Allocator() { return this->_allocator; }
 	BodyInterface& BodyItf(bool lock) { return lock ? this->_phySys.GetBodyInterface() : this->_phySys.GetBodyInterfaceNoLock(); }
 
@@ -252,12 +363,21 @@ public:
 	uint32 Update(float delta, uint32 step);
 	void GetBodies(rust::Vec<BodyID>& bodies) const;
 	void GetActiveBodies(EBodyType bodyType, rust::Vec<BodyID>& bodies) const;
+	void SaveState(EStateRecorderState state, const StateRecorderFilter* filter, rust::Vec<uint8>& data) const;
+	bool RestoreState(rust::Slice<const uint8> data);
+	uint64 StateHash() const;
+	bool AddConstraint(Constraint* constraint);
+	bool RemoveConstraint(Constraint* constraint);
+	void RemoveAllConstraints();
+	void AddCharacterVirtual(XCharacterVirtual* character) { _characters.push_back(character); }
+	void RemoveCharacterVirtual(XCharacterVirtual* character);
 	RENDERER_ONLY(void AddRenderable(XDebugRenderable* renderable) { _renderables.insert(renderable); })
 	RENDERER_ONLY(void RemoveRenderable(XDebugRenderable* renderable) { _renderables.erase(renderable); })
 	RENDERER_ONLY(void DebugRender(DebugRenderer* debugRenderer);)
 };
 
 XPhysicsSystem* CreatePhysicSystem(
+	const XPhysicsSystemConfig& config,
 	rust::Fn<void (XPhysicsSystem&)> rustCleanUp,
 	const BroadPhaseLayerInterface* bpli,
 	const ObjectVsBroadPhaseLayerFilter* obplf,
@@ -266,6 +386,12 @@ XPhysicsSystem* CreatePhysicSystem(
 inline void DropXPhysicsSystem(XPhysicsSystem* ptr) { DropRef<XPhysicsSystem>(ptr); }
 inline XPhysicsSystem* CloneXPhysicsSystem(XPhysicsSystem* ptr) { return CloneRef<XPhysicsSystem>(ptr); }
 inline uint32 CountRefXPhysicsSystem(const XPhysicsSystem* ptr) { return RefCountRef<XPhysicsSystem>(ptr); }
+inline bool AddSystemConstraint(XPhysicsSystem* system, Constraint* constraint) { return system->AddConstraint(constraint); }
+inline bool RemoveSystemConstraint(XPhysicsSystem* system, Constraint* constraint) { return system->RemoveConstraint(constraint); }
+inline void RemoveAllSystemConstraints(XPhysicsSystem* system) { system->RemoveAllConstraints(); }
+inline XBodyInterface* GetPhysicsSystemBodyInterface(PhysicsSystem& system) { return reinterpret_cast<XBodyInterface*>(&system.GetBodyInterfaceNoLock()); }
+inline void SetPhysicsSystemCombineFriction(PhysicsSystem& system, const uint8* func) { system.SetCombineFriction(reinterpret_cast<ContactConstraintManager::CombineFunction>(func)); }
+inline void SetPhysicsSystemCombineRestitution(PhysicsSystem& system, const uint8* func) { system.SetCombineRestitution(reinterpret_cast<ContactConstraintManager::CombineFunction>(func)); }
 
 //
 // character
@@ -327,6 +453,8 @@ public:
 	void RefreshContacts(ObjectLayer chara_layer);
 	void UpdateGroundVelocity() { CharacterVirtual::UpdateGroundVelocity(); }
 	bool SetShape(ObjectLayer chara_layer, const Shape* shape, float maxPenetrationDepth);
+	void SaveState(rust::Vec<uint8>& data) const;
+	bool RestoreState(rust::Slice<const uint8> data);
 	// void CheckCollision(
 	// 	ObjectLayer chara_layer,
 	// 	RsVec3 position,
@@ -351,6 +479,248 @@ inline void DropXCharacterVirtual(XCharacterVirtual* ptr) { DropRef<XCharacterVi
 inline XCharacterVirtual* CloneXCharacterVirtual(XCharacterVirtual* ptr) { return CloneRef<XCharacterVirtual>(ptr); }
 inline uint32 CountRefXCharacterVirtual(const XCharacterVirtual* ptr) { return RefCountRef<XCharacterVirtual>(ptr); }
 
+//
+// constraint
+//
+
+typedef EConstraintType ConstraintType;
+typedef EConstraintSubType ConstraintSubType;
+typedef EConstraintSpace ConstraintSpace;
+typedef ESpringMode SpringMode;
+typedef EMotorState MotorState;
+typedef ESwingType SwingType;
+typedef SixDOFConstraintSettings::EAxis SixDOFAxis;
+
+// Constraints point at bodies of the system, keep it alive as long as the constraint
+template <class C>
+class XConstraint: public C {
+private:
+	Ref<XPhysicsSystem> _system;
+
+public:
+	template <class S>
+	XConstraint(Ref<XPhysicsSystem> system, Body& body1, Body& body2, const S& settings)
+		: C(body1, body2, settings), _system(system) {}
+};
+
+inline void DropConstraint(Constraint* ptr) { DropRef<Constraint>(ptr); }
+inline Constraint* CloneConstraint(Constraint* ptr) { return CloneRef<Constraint>(ptr); }
+inline uint32 CountRefConstraint(const Constraint* ptr) { return RefCountRef<Constraint>(ptr); }
+
+struct XFixedConstraintSettings;
+Constraint* CreateFixedConstraint(XPhysicsSystem* system, const XFixedConstraintSettings& settings, const BodyID& body1, const BodyID& body2);
+struct XPointConstraintSettings;
+Constraint* CreatePointConstraint(XPhysicsSystem* system, const XPointConstraintSettings& settings, const BodyID& body1, const BodyID& body2);
+struct XHingeConstraintSettings;
+Constraint* CreateHingeConstraint(XPhysicsSystem* system, const XHingeConstraintSettings& settings, const BodyID& body1, const BodyID& body2);
+struct XSliderConstraintSettings;
+Constraint* CreateSliderConstraint(XPhysicsSystem* system, const XSliderConstraintSettings& settings, const BodyID& body1, const BodyID& body2);
+struct XDistanceConstraintSettings;
+Constraint* CreateDistanceConstraint(XPhysicsSystem* system, const XDistanceConstraintSettings& settings, const BodyID& body1, const BodyID& body2);
+struct XConeConstraintSettings;
+Constraint* CreateConeConstraint(XPhysicsSystem* system, const XConeConstraintSettings& settings, const BodyID& body1, const BodyID& body2);
+
+inline void DropSwingTwistConstraint(SwingTwistConstraint* ptr) { DropRef<SwingTwistConstraint>(ptr); }
+inline SwingTwistConstraint* CloneSwingTwistConstraint(SwingTwistConstraint* ptr) { return CloneRef<SwingTwistConstraint>(ptr); }
+inline uint32 CountRefSwingTwistConstraint(const SwingTwistConstraint* ptr) { return RefCountRef<SwingTwistConstraint>(ptr); }
+
+inline void DropSixDOFConstraint(SixDOFConstraint* ptr) { DropRef<SixDOFConstraint>(ptr); }
+inline SixDOFConstraint* CloneSixDOFConstraint(SixDOFConstraint* ptr) { return CloneRef<SixDOFConstraint>(ptr); }
+inline uint32 CountRefSixDOFConstraint(const SixDOFConstraint* ptr) { return RefCountRef<SixDOFConstraint>(ptr); }
+
+struct XSwingTwistConstraintSettings;
+SwingTwistConstraint* CreateSwingTwistConstraint(XPhysicsSystem* system, const XSwingTwistConstraintSettings& settings, const BodyID& body1, const BodyID& body2);
+struct XSixDOFConstraintSettings;
+SixDOFConstraint* CreateSixDOFConstraint(XPhysicsSystem* system, const XSixDOFConstraintSettings& settings, const BodyID& body1, const BodyID& body2);
+
+//
+// query
+//
+
+typedef EActiveEdgeMode ActiveEdgeMode;
+typedef ECollectFacesMode CollectFacesMode;
+
+struct XQueryFilter;
+struct XRayCastHit;
+bool CastRay(
+	XPhysicsSystem* system,
+	Vec3 origin,
+	Vec3 direction,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	XRayCastHit& hit
+);
+void CastRayAll(
+	XPhysicsSystem* system,
+	Vec3 origin,
+	Vec3 direction,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	rust::Vec<XRayCastHit>& hits
+);
+struct XShapeCastSettings;
+void CastShape(
+	XPhysicsSystem* system,
+	const Shape* shape,
+	Mat44 transform,
+	Vec3 scale,
+	Vec3 direction,
+	const XShapeCastSettings& settings,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	rust::Vec<ShapeCastResult>& hits
+);
+struct XCollideShapeSettings;
+void CollideShape(
+	XPhysicsSystem* system,
+	const Shape* shape,
+	Mat44 transform,
+	Vec3 scale,
+	const XCollideShapeSettings& settings,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	rust::Vec<CollideShapeResult>& hits
+);
+void CollidePoint(
+	XPhysicsSystem* system,
+	Vec3 point,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	rust::Vec<CollidePointResult>& hits
+);
+void BroadPhaseCollideAABox(XPhysicsSystem* system, const AABox& box, const XQueryFilter& filter, rust::Vec<BodyID>& bodies);
+void BroadPhaseCollideSphere(XPhysicsSystem* system, Vec3 center, float radius, const XQueryFilter& filter, rust::Vec<BodyID>& bodies);
+void BroadPhaseCollideOrientedBox(XPhysicsSystem* system, Mat44 orientation, Vec3 halfExtents, const XQueryFilter& filter, rust::Vec<BodyID>& bodies);
+void BroadPhaseCastAABox(XPhysicsSystem* system, const AABox& box, Vec3 direction, const XQueryFilter& filter, rust::Vec<BodyID>& bodies);
+
+//
+// soft body
+//
+
+inline void DropSoftBodySharedSettings(SoftBodySharedSettings* ptr) { DropRef<SoftBodySharedSettings>(ptr); }
+inline SoftBodySharedSettings* CloneSoftBodySharedSettings(SoftBodySharedSettings* ptr) { return CloneRef<SoftBodySharedSettings>(ptr); }
+inline uint32 CountRefSoftBodySharedSettings(const SoftBodySharedSettings* ptr) { return RefCountRef<SoftBodySharedSettings>(ptr); }
+
+struct XSoftBodySharedSettings;
+SoftBodySharedSettings* CreateSoftBodySharedSettings(const XSoftBodySharedSettings& settings);
+inline uint32 GetSoftBodyNumVertices(const SoftBodySharedSettings& settings) { return (uint32)settings.mVertices.size(); }
+inline uint32 GetSoftBodyNumFaces(const SoftBodySharedSettings& settings) { return (uint32)settings.mFaces.size(); }
+inline uint32 GetSoftBodyNumEdges(const SoftBodySharedSettings& settings) { return (uint32)settings.mEdgeConstraints.size(); }
+inline uint32 GetSoftBodyNumVolumes(const SoftBodySharedSettings& settings) { return (uint32)settings.mVolumeConstraints.size(); }
+
+struct XSoftBodyCreationSettings;
+BodyID CreateSoftBody(XBodyInterface& bodyItf, const XSoftBodyCreationSettings& settings);
+BodyID CreateAddSoftBody(XBodyInterface& bodyItf, const XSoftBodyCreationSettings& settings, EActivation activation);
+bool GetSoftBodyVertices(const Body& body, bool worldSpace, rust::Vec<Vec3>& vertices);
+bool GetSoftBodyVerticesByID(const XPhysicsSystem& system, const BodyID& bodyId, bool worldSpace, rust::Vec<Vec3>& vertices);
+
+static_assert(sizeof(SoftBodyContactSettings) == 16, "SoftBodyContactSettings size");
+inline uint32 GetSoftBodyManifoldNumVertices(const SoftBodyManifold& manifold) { return (uint32)manifold.GetVertices().size(); }
+inline Vec3 GetSoftBodyManifoldVertexPosition(const SoftBodyManifold& manifold, uint32 index) { return manifold.GetVertices()[index].mPosition; }
+inline bool SoftBodyManifoldHasContact(const SoftBodyManifold& manifold, uint32 index) { return manifold.HasContact(manifold.GetVertices()[index]); }
+inline Vec3 GetSoftBodyManifoldLocalContactPoint(const SoftBodyManifold& manifold, uint32 index) { return manifold.GetLocalContactPoint(manifold.GetVertices()[index]); }
+inline Vec3 GetSoftBodyManifoldContactNormal(const SoftBodyManifold& manifold, uint32 index) { return manifold.GetContactNormal(manifold.GetVertices()[index]); }
+inline BodyID GetSoftBodyManifoldContactBodyID(const SoftBodyManifold& manifold, uint32 index) { return manifold.GetContactBodyID(manifold.GetVertices()[index]); }
+
+//
+// vehicle
+//
+
+enum class VehicleControllerType: uint8 {
+	Wheeled,
+	Tracked,
+	Motorcycle,
+};
+
+enum class VehicleCollisionTesterType: uint8 {
+	Ray,
+	CastSphere,
+	CastCylinder,
+};
+typedef ETransmissionMode TransmissionMode;
+
+struct XWheelState;
+
+class XVehicleConstraint: public VehicleConstraint {
+private:
+	Ref<XPhysicsSystem> _system;
+	VehicleControllerType _controllerType;
+
+public:
+	XVehicleConstraint(
+		Ref<XPhysicsSystem> system,
+		Body& body,
+		const VehicleConstraintSettings& settings,
+		VehicleControllerType controllerType
+	): VehicleConstraint(body, settings), _system(system), _controllerType(controllerType) {}
+	VehicleControllerType GetControllerType() const { return _controllerType; }
+	uint32 GetNumWheels() const { return (uint32)GetWheels().size(); }
+	void GetWheelState(uint32 index, XWheelState& state) const;
+	Mat44 GetWheelLocalTransform(uint32 index, Vec3 right, Vec3 up) const { return VehicleConstraint::GetWheelLocalTransform(index, right, up); }
+	Mat44 GetWheelWorldTransform(uint32 index, Vec3 right, Vec3 up) const { return VehicleConstraint::GetWheelWorldTransform(index, right, up); }
+	const WheeledVehicleController* GetWheeledController() const;
+	WheeledVehicleController* GetWheeledController();
+	const TrackedVehicleController* GetTrackedController() const;
+	TrackedVehicleController* GetTrackedController();
+	const MotorcycleController* GetMotorcycleController() const;
+	MotorcycleController* GetMotorcycleController();
+};
+
+inline void DropXVehicleConstraint(XVehicleConstraint* ptr) { DropRef<XVehicleConstraint>(ptr); }
+inline XVehicleConstraint* CloneXVehicleConstraint(XVehicleConstraint* ptr) { return CloneRef<XVehicleConstraint>(ptr); }
+inline uint32 CountRefXVehicleConstraint(const XVehicleConstraint* ptr) { return RefCountRef<XVehicleConstraint>(ptr); }
+
+struct XVehicleConstraintSettings;
+struct XWheelSettingsWV;
+struct XWheeledVehicleControllerSettings;
+struct XWheelSettingsTV;
+struct XTrackedVehicleControllerSettings;
+struct XMotorcycleControllerSettings;
+XVehicleConstraint* CreateWheeledVehicleConstraint(
+	XPhysicsSystem* system,
+	const BodyID& body,
+	const XVehicleConstraintSettings& settings,
+	const XWheelSettingsWV* wheels,
+	uint32 numWheels,
+	const XWheeledVehicleControllerSettings& controller
+);
+XVehicleConstraint* CreateTrackedVehicleConstraint(
+	XPhysicsSystem* system,
+	const BodyID& body,
+	const XVehicleConstraintSettings& settings,
+	const XWheelSettingsTV* wheels,
+	uint32 numWheels,
+	const XTrackedVehicleControllerSettings& controller
+);
+XVehicleConstraint* CreateMotorcycleConstraint(
+	XPhysicsSystem* system,
+	const BodyID& body,
+	const XVehicleConstraintSettings& settings,
+	const XWheelSettingsWV* wheels,
+	uint32 numWheels,
+	const XMotorcycleControllerSettings& controller
+);
+bool AddVehicleConstraint(XPhysicsSystem* system, XVehicleConstraint* constraint);
+bool RemoveVehicleConstraint(XPhysicsSystem* system, XVehicleConstraint* constraint);
+
+inline float GetWheeledVehicleEngineRPM(const WheeledVehicleController& controller) { return controller.GetEngine().GetCurrentRPM(); }
+inline int32 GetWheeledVehicleCurrentGear(const WheeledVehicleController& controller) { return controller.GetTransmission().GetCurrentGear(); }
+inline float GetWheeledVehicleClutchFriction(const WheeledVehicleController& controller) { return controller.GetTransmission().GetClutchFriction(); }
+inline void SetWheeledVehicleTransmission(WheeledVehicleController& controller, int32 gear, float clutch) { controller.GetTransmission().Set(gear, clutch); }
+inline float GetTrackedVehicleEngineRPM(const TrackedVehicleController& controller) { return controller.GetEngine().GetCurrentRPM(); }
+inline int32 GetTrackedVehicleCurrentGear(const TrackedVehicleController& controller) { return controller.GetTransmission().GetCurrentGear(); }
+inline float GetTrackedVehicleClutchFriction(const TrackedVehicleController& controller) { return controller.GetTransmission().GetClutchFriction(); }
+inline void SetTrackedVehicleTransmission(TrackedVehicleController& controller, int32 gear, float clutch) { controller.GetTransmission().Set(gear, clutch); }
+inline float GetTrackedVehicleTrackAngularVelocity(const TrackedVehicleController& controller, uint32 side) { return controller.GetTracks()[side].mAngularVelocity; }
+
+//
+// scene
+//
+
+void SavePhysicsScene(XPhysicsSystem* system, rust::Vec<uint8>& data);
+bool LoadPhysicsScene(XPhysicsSystem* system, rust::Slice<const uint8> data);
+bool GetSceneBodySettings(XPhysicsSystem* system, const BodyID& bodyId, BodyCreationSettings& settings);
+
 //
 // Unit tests
 //
@@ -366,12 +736,38 @@ const char* TestCharacterContactListener(
 	XCharacterVirtual* chara1,
 	XCharacterVirtual* chara2
 );
+const char* TestBodyFilter(const BodyFilter* filter, XPhysicsSystem* system);
+const char* TestSoftBodyContactListener(SoftBodyContactListener* listener, XPhysicsSystem* system);
+const char* TestStateRecorderFilter(const StateRecorderFilter* filter, XPhysicsSystem* system);
+const char* TestPhysicsStepListener(PhysicsStepListener* listener, XPhysicsSystem* system);
 
 //
-// Debug
+// debug frame
 //
 
 #if defined(JPH_DEBUG_RENDERER)
+enum class DebugShapeColor: uint8 {
+	InstanceColor,
+	ShapeTypeColor,
+	MotionTypeColor,
+	SleepColor,
+	IslandColor,
+	MaterialColor,
+};
+
+struct XDebugDrawSettings;
+struct DebugFrame;
+class XDebugRenderer;
+XDebugRenderer* CreateDebugRenderer();
+void DropDebugRenderer(XDebugRenderer* renderer);
+void DrawPhysicsSystem(XDebugRenderer* renderer, XPhysicsSystem* system, const XDebugDrawSettings& settings, DebugFrame& frame);
+#endif
+
+//
+// Debug
+//
+
+#if defined(JOLT_RS_DEBUG_APP)
 typedef EKey DebugKey;
 struct RustDebugApp;
 void RunDebugApplication(rust::Box<RustDebugApp> rs_app);
@@ -5,20 +5,36 @@
 pub mod base;
 pub mod body;
 pub mod character;
+pub mod constraint;
 pub mod consts;
+#[cfg(feature = "debug-frame")]
+pub mod debug_frame;
 pub mod error;
+pub mod query;
+pub mod scene;
+#[cfg(any(feature = "serde", feature = "rkyv"))]
+pub mod serialize;
 pub mod shape;
+pub mod soft_body;
 pub mod system;
+pub mod vehicle;
 pub mod vtable;
 
 pub use base::*;
 pub use body::*;
 pub use character::*;
+pub use constraint::*;
 pub use consts::*;
+#[cfg(feature = "debug-frame")]
+pub use debug_frame::*;
 pub use error::*;
 pub use jolt_macros::vdata;
+pub use query::*;
+pub use scene::*;
 pub use shape::*;
+pub use soft_body::*;
 pub use system::*;
+pub use vehicle::*;
 pub use vtable::*;
 
 #[cfg(all(windows, feature = "debug-renderer"))]
@@ -0,0 +1,336 @@
+#include "jolt-physics-rs/src/ffi.h"
+#include "jolt-physics-rs/src/query.rs.h"
+
+struct XQueryFilter {
+	ObjectLayer objectLayer;
+	uint8 broadPhaseLayer;
+	bool useObjectLayer;
+	bool useBroadPhaseLayer;
+};
+static_assert(sizeof(XQueryFilter) == 8, "XQueryFilter size");
+
+struct XRayCastHit {
+	Vec3 position;
+	Vec3 normal;
+	BodyID bodyID;
+	SubShapeID subShapeID;
+	float fraction;
+};
+static_assert(sizeof(XRayCastHit) == 48, "XRayCastHit size");
+
+struct XShapeCastSettings {
+	EBackFaceMode backFaceModeTriangles;
+	EBackFaceMode backFaceModeConvex;
+	EActiveEdgeMode activeEdgeMode;
+	ECollectFacesMode collectFacesMode;
+	bool useShrunkenShapeAndConvexRadius;
+	bool returnDeepestPoint;
+	bool collectAll;
+	float collisionTolerance;
+	float penetrationTolerance;
+	Vec3 activeEdgeMovementDirection;
+};
+static_assert(sizeof(XShapeCastSettings) == 32, "XShapeCastSettings size");
+static_assert(sizeof(ShapeCastResult) == 1136, "ShapeCastResult size");
+
+struct XCollideShapeSettings {
+	EActiveEdgeMode activeEdgeMode;
+	ECollectFacesMode collectFacesMode;
+	EBackFaceMode backFaceMode;
+	float collisionTolerance;
+	float penetrationTolerance;
+	float maxSeparationDistance;
+	Vec3 activeEdgeMovementDirection;
+};
+static_assert(sizeof(XCollideShapeSettings) == 32, "XCollideShapeSettings size");
+static_assert(sizeof(CollidePointResult) == 8, "CollidePointResult size");
+
+class XBroadPhaseLayerFilter: public BroadPhaseLayerFilter {
+private:
+	const ObjectVsBroadPhaseLayerFilter* _obplf;
+	XQueryFilter _filter;
+
+public:
+	XBroadPhaseLayerFilter(XPhysicsSystem* system, const XQueryFilter& filter):
+		_obplf(system->GetObjectVsBroadPhaseLayerFilter()),
+		_filter(filter) {}
+
+	bool ShouldCollide(BroadPhaseLayer layer) const override {
+		if (this->_filter.useBroadPhaseLayer && layer != BroadPhaseLayer(this->_filter.broadPhaseLayer)) {
+			return false;
+		}
+		if (this->_filter.useObjectLayer && !this->_obplf->ShouldCollide(this->_filter.objectLayer, layer)) {
+			return false;
+		}
+		return true;
+	}
+};
+
+class XObjectLayerFilter: public ObjectLayerFilter {
+private:
+	const ObjectLayerPairFilter* _olpf;
+	XQueryFilter _filter;
+
+public:
+	XObjectLayerFilter(XPhysicsSystem* system, const XQueryFilter& filter):
+		_olpf(system->GetObjectLayerPairFilter()),
+		_filter(filter) {}
+
+	bool ShouldCollide(ObjectLayer layer) const override {
+		return !this->_filter.useObjectLayer || this->_olpf->ShouldCollide(this->_filter.objectLayer, layer);
+	}
+};
+
+static const BodyFilter DEFAULT_BODY_FILTER {};
+
+//
+// ray cast
+//
+
+static XRayCastHit MakeRayCastHit(XPhysicsSystem* system, const RRayCast& ray, const RayCastResult& result) {
+	XRayCastHit hit;
+	hit.position = ray.GetPointOnRay(result.mFraction);
+	hit.bodyID = result.mBodyID;
+	hit.subShapeID = result.mSubShapeID2;
+	hit.fraction = result.mFraction;
+	BodyLockRead lock(system->PhySys().GetBodyLockInterface(), result.mBodyID);
+	if (lock.Succeeded()) {
+		hit.normal = lock.GetBody().GetWorldSpaceSurfaceNormal(result.mSubShapeID2, hit.position);
+	} else {
+		hit.normal = Vec3::sZero();
+	}
+	return hit;
+}
+
+bool CastRay(
+	XPhysicsSystem* system,
+	Vec3 origin,
+	Vec3 direction,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	XRayCastHit& hit
+) {
+	RRayCast ray(origin, direction);
+	RayCastResult result;
+	bool found = system->PhySys().GetNarrowPhaseQuery().CastRay(
+		ray,
+		result,
+		XBroadPhaseLayerFilter(system, filter),
+		XObjectLayerFilter(system, filter),
+		bodyFilter != nullptr ? *bodyFilter : DEFAULT_BODY_FILTER
+	);
+	if (found) {
+		hit = MakeRayCastHit(system, ray, result);
+	}
+	return found;
+}
+
+void CastRayAll(
+	XPhysicsSystem* system,
+	Vec3 origin,
+	Vec3 direction,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	rust::Vec<XRayCastHit>& hits
+) {
+	RRayCast ray(origin, direction);
+	AllHitCollisionCollector<CastRayCollector> collector;
+	system->PhySys().GetNarrowPhaseQuery().CastRay(
+		ray,
+		RayCastSettings(),
+		collector,
+		XBroadPhaseLayerFilter(system, filter),
+		XObjectLayerFilter(system, filter),
+		bodyFilter != nullptr ? *bodyFilter : DEFAULT_BODY_FILTER
+	);
+	collector.Sort();
+
+	hits.clear();
+	hits.reserve(collector.mHits.size());
+	for (const RayCastResult& result : collector.mHits) {
+		hits.push_back(MakeRayCastHit(system, ray, result));
+	}
+}
+
+//
+// shape cast
+//
+
+void CastShape(
+	XPhysicsSystem* system,
+	const Shape* shape,
+	Mat44 transform,
+	Vec3 scale,
+	Vec3 direction,
+	const XShapeCastSettings& settings,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	rust::Vec<ShapeCastResult>& hits
+) {
+	RShapeCast shapeCast = RShapeCast::sFromWorldTransform(shape, scale, transform, direction);
+	ShapeCastSettings castSettings;
+	castSettings.mBackFaceModeTriangles = settings.backFaceModeTriangles;
+	castSettings.mBackFaceModeConvex = settings.backFaceModeConvex;
+	castSettings.mActiveEdgeMode = settings.activeEdgeMode;
+	castSettings.mCollectFacesMode = settings.collectFacesMode;
+	castSettings.mUseShrunkenShapeAndConvexRadius = settings.useShrunkenShapeAndConvexRadius;
+	castSettings.mReturnDeepestPoint = settings.returnDeepestPoint;
+	castSettings.mCollisionTolerance = settings.collisionTolerance;
+	castSettings.mPenetrationTolerance = settings.penetrationTolerance;
+	castSettings.mActiveEdgeMovementDirection = settings.activeEdgeMovementDirection;
+
+	const NarrowPhaseQuery& query = system->PhySys().GetNarrowPhaseQuery();
+	XBroadPhaseLayerFilter bplFilter(system, filter);
+	XObjectLayerFilter olFilter(system, filter);
+	const BodyFilter& bFilter = bodyFilter != nullptr ? *bodyFilter : DEFAULT_BODY_FILTER;
+
+	hits.clear();
+	if (settings.collectAll) {
+		AllHitCollisionCollector<CastShapeCollector> collector;
+		query.CastShape(shapeCast, castSettings, RVec3::sZero(), collector, bplFilter, olFilter, bFilter);
+		collector.Sort();
+		hits.reserve(collector.mHits.size());
+		for (const ShapeCastResult& hit : collector.mHits) {
+			hits.push_back(hit);
+		}
+	} else {
+		ClosestHitCollisionCollector<CastShapeCollector> collector;
+		query.CastShape(shapeCast, castSettings, RVec3::sZero(), collector, bplFilter, olFilter, bFilter);
+		if (collector.HadHit()) {
+			hits.push_back(collector.mHit);
+		}
+	}
+}
+
+//
+// collide shape
+//
+
+void CollideShape(
+	XPhysicsSystem* system,
+	const Shape* shape,
+	Mat44 transform,
+	Vec3 scale,
+	const XCollideShapeSettings& settings,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	rust::Vec<CollideShapeResult>& hits
+) {
+	CollideShapeSettings collideSettings;
+	collideSettings.mActiveEdgeMode = settings.activeEdgeMode;
+	collideSettings.mCollectFacesMode = settings.collectFacesMode;
+	collideSettings.mBackFaceMode = settings.backFaceMode;
+	collideSettings.mCollisionTolerance = settings.collisionTolerance;
+	collideSettings.mPenetrationTolerance = settings.penetrationTolerance;
+	collideSettings.mMaxSeparationDistance = settings.maxSeparationDistance;
+	collideSettings.mActiveEdgeMovementDirection = settings.activeEdgeMovementDirection;
+
+	AllHitCollisionCollector<CollideShapeCollector> collector;
+	system->PhySys().GetNarrowPhaseQuery().CollideShape(
+		shape,
+		scale,
+		transform,
+		collideSettings,
+		RVec3::sZero(),
+		collector,
+		XBroadPhaseLayerFilter(system, filter),
+		XObjectLayerFilter(system, filter),
+		bodyFilter != nullptr ? *bodyFilter : DEFAULT_BODY_FILTER
+	);
+	collector.Sort();
+
+	hits.clear();
+	hits.reserve(collector.mHits.size());
+	for (const CollideShapeResult& hit : collector.mHits) {
+		hits.push_back(hit);
+	}
+}
+
+void CollidePoint(
+	XPhysicsSystem* system,
+	Vec3 point,
+	const XQueryFilter& filter,
+	const BodyFilter* bodyFilter,
+	rust::Vec<CollidePointResult>& hits
+) {
+	AllHitCollisionCollector<CollidePointCollector> collector;
+	system->PhySys().GetNarrowPhaseQuery().CollidePoint(
+		point,
+		collector,
+		XBroadPhaseLayerFilter(system, filter),
+		XObjectLayerFilter(system, filter),
+		bodyFilter != nullptr ? *bodyFilter : DEFAULT_BODY_FILTER
+	);
+
+	hits.clear();
+	hits.reserve(collector.mHits.size());
+	for (const CollidePointResult& hit : collector.mHits) {
+		hits.push_back(hit);
+	}
+}
+
+//
+// broad phase
+//
+
+void BroadPhaseCollideAABox(XPhysicsSystem* system, const AABox& box, const XQueryFilter& filter, rust::Vec<BodyID>& bodies) {
+	AllHitCollisionCollector<CollideShapeBodyCollector> collector;
+	system->PhySys().GetBroadPhaseQuery().CollideAABox(
+		box,
+		collector,
+		XBroadPhaseLayerFilter(system, filter),
+		XObjectLayerFilter(system, filter)
+	);
+	bodies.clear();
+	bodies.reserve(collector.mHits.size());
+	for (const BodyID& body : collector.mHits) {
+		bodies.push_back(body);
+	}
+}
+
+void BroadPhaseCollideSphere(XPhysicsSystem* system, Vec3 center, float radius, const XQueryFilter& filter, rust::Vec<BodyID>& bodies) {
+	AllHitCollisionCollector<CollideShapeBodyCollector> collector;
+	system->PhySys().GetBroadPhaseQuery().CollideSphere(
+		center,
+		radius,
+		collector,
+		XBroadPhaseLayerFilter(system, filter),
+		XObjectLayerFilter(system, filter)
+	);
+	bodies.clear();
+	bodies.reserve(collector.mHits.size());
+	for (const BodyID& body : collector.mHits) {
+		bodies.push_back(body);
+	}
+}
+
+void BroadPhaseCollideOrientedBox(XPhysicsSystem* system, Mat44 orientation, Vec3 halfExtents, const XQueryFilter& filter, rust::Vec<BodyID>& bodies) {
+	AllHitCollisionCollector<CollideShapeBodyCollector> collector;
+	system->PhySys().GetBroadPhaseQuery().CollideOrientedBox(
+		OrientedBox(orientation, halfExtents),
+		collector,
+		XBroadPhaseLayerFilter(system, filter),
+		XObjectLayerFilter(system, filter)
+	);
+	bodies.clear();
+	bodies.reserve(collector.mHits.size());
+	for (const BodyID& body : collector.mHits) {
+		bodies.push_back(body);
+	}
+}
+
+void BroadPhaseCastAABox(XPhysicsSystem* system, const AABox& box, Vec3 direction, const XQueryFilter& filter, rust::Vec<BodyID>& bodies) {
+	AllHitCollisionCollector<CastShapeBodyCollector> collector;
+	system->PhySys().GetBroadPhaseQuery().CastAABox(
+		{ box, direction },
+		collector,
+		XBroadPhaseLayerFilter(system, filter),
+		XObjectLayerFilter(system, filter)
+	);
+	collector.Sort();
+	bodies.clear();
+	bodies.reserve(collector.mHits.size());
+	for (const BroadPhaseCastResult& hit : collector.mHits) {
+		bodies.push_back(hit.mBodyID);
+	}
+}
@@ -0,0 +1,723 @@
+use cxx::{kind, type_id, ExternType};
+use glam::{Mat4, Vec3A};
+use jolt_macros::vtable;
+use static_assertions::const_assert_eq;
+use std::marker::PhantomData;
+use std::{fmt, mem, ptr};
+
+use crate::base::{AABox, BodyID, BroadPhaseLayer, ObjectLayer, SubShapeID};
+use crate::body::Body;
+use crate::character::BackFaceMode;
+use crate::shape::Shape;
+use crate::soft_body::SoftBodyContactListener;
+use crate::system::{BodyActivationListener, CollideShapeResult, ContactListener, PhysicsSystem};
+use crate::vtable::VPair;
+
+#[cxx::bridge()]
+pub(crate) mod ffi {
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum ActiveEdgeMode {
+        CollideOnlyWithActive,
+        CollideWithAll,
+    }
+
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum CollectFacesMode {
+        CollectFaces,
+        NoFaces,
+    }
+
+    impl Vec<XRayCastHit> {}
+    impl Vec<ShapeCastResult> {}
+    impl Vec<CollideShapeResult> {}
+    impl Vec<CollidePointResult> {}
+
+    unsafe extern "C++" {
+        include!("rust/cxx.h");
+        include!("jolt-physics-rs/src/ffi.h");
+
+        type ActiveEdgeMode;
+        type CollectFacesMode;
+        type BackFaceMode = crate::character::ffi::BackFaceMode;
+
+        type Vec3 = crate::base::ffi::Vec3;
+        type Mat44 = crate::base::ffi::Mat44;
+        type AABox = crate::base::ffi::AABox;
+        type BodyID = crate::base::ffi::BodyID;
+        type Shape = crate::shape::ffi::Shape;
+        type XPhysicsSystem = crate::system::ffi::XPhysicsSystem;
+
+        type XQueryFilter = crate::query::QueryFilter;
+        type XRayCastHit = crate::query::RayCastHit;
+        type XShapeCastSettings = crate::query::ShapeCastSettings;
+        type ShapeCastResult = crate::query::ShapeCastResult;
+        type XCollideShapeSettings = crate::query::CollideShapeSettings;
+        type CollideShapeResult = crate::system::CollideShapeResult;
+        type CollidePointResult = crate::query::CollidePointResult;
+        type BodyFilter;
+
+        unsafe fn CastRay(
+            system: *mut XPhysicsSystem,
+            origin: Vec3,
+            direction: Vec3,
+            filter: &XQueryFilter,
+            body_filter: *const BodyFilter,
+            hit: &mut XRayCastHit,
+        ) -> bool;
+        unsafe fn CastRayAll(
+            system: *mut XPhysicsSystem,
+            origin: Vec3,
+            direction: Vec3,
+            filter: &XQueryFilter,
+            body_filter: *const BodyFilter,
+            hits: &mut Vec<XRayCastHit>,
+        );
+        unsafe fn CastShape(
+            system: *mut XPhysicsSystem,
+            shape: *const Shape,
+            transform: Mat44,
+            scale: Vec3,
+            direction: Vec3,
+            settings: &XShapeCastSettings,
+            filter: &XQueryFilter,
+            body_filter: *const BodyFilter,
+            hits: &mut Vec<ShapeCastResult>,
+        );
+        unsafe fn CollideShape(
+            system: *mut XPhysicsSystem,
+            shape: *const Shape,
+            transform: Mat44,
+            scale: Vec3,
+            settings: &XCollideShapeSettings,
+            filter: &XQueryFilter,
+            body_filter: *const BodyFilter,
+            hits: &mut Vec<CollideShapeResult>,
+        );
+        unsafe fn CollidePoint(
+            system: *mut XPhysicsSystem,
+            point: Vec3,
+            filter: &XQueryFilter,
+            body_filter: *const BodyFilter,
+            hits: &mut Vec<CollidePointResult>,
+        );
+
+        unsafe fn BroadPhaseCollideAABox(
+            system: *mut XPhysicsSystem,
+            aabox: &AABox,
+            filter: &XQueryFilter,
+            bodies: &mut Vec<BodyID>,
+        );
+        unsafe fn BroadPhaseCollideSphere(
+            system: *mut XPhysicsSystem,
+            center: Vec3,
+            radius: f32,
+            filter: &XQueryFilter,
+            bodies: &mut Vec<BodyID>,
+        );
+        unsafe fn BroadPhaseCollideOrientedBox(
+            system: *mut XPhysicsSystem,
+            orientation: Mat44,
+            half_extents: Vec3,
+            filter: &XQueryFilter,
+            bodies: &mut Vec<BodyID>,
+        );
+        unsafe fn BroadPhaseCastAABox(
+            system: *mut XPhysicsSystem,
+            aabox: &AABox,
+            direction: Vec3,
+            filter: &XQueryFilter,
+            bodies: &mut Vec<BodyID>,
+        );
+    }
+}
+
+pub type ActiveEdgeMode = ffi::ActiveEdgeMode;
+pub type CollectFacesMode = ffi::CollectFacesMode;
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct QueryFilter {
+    /// Layer of the query, tested against bodies with the system's object layer filters.
+    pub object_layer: ObjectLayer,
+    pub broad_phase_layer: BroadPhaseLayer,
+    pub use_object_layer: bool,
+    pub use_broad_phase_layer: bool,
+}
+const_assert_eq!(mem::size_of::<QueryFilter>(), 8);
+
+unsafe impl ExternType for QueryFilter {
+    type Id = type_id!("XQueryFilter");
+    type Kind = kind::Trivial;
+}
+
+impl QueryFilter {
+    #[inline]
+    pub fn new_object_layer(object_layer: ObjectLayer) -> QueryFilter {
+        QueryFilter {
+            object_layer,
+            use_object_layer: true,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn new_broad_phase_layer(broad_phase_layer: BroadPhaseLayer) -> QueryFilter {
+        QueryFilter {
+            broad_phase_layer,
+            use_broad_phase_layer: true,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn with_object_layer(mut self, object_layer: ObjectLayer) -> QueryFilter {
+        self.object_layer = object_layer;
+        self.use_object_layer = true;
+        self
+    }
+
+    #[inline]
+    pub fn with_broad_phase_layer(mut self, broad_phase_layer: BroadPhaseLayer) -> QueryFilter {
+        self.broad_phase_layer = broad_phase_layer;
+        self.use_broad_phase_layer = true;
+        self
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RayCastHit {
+    pub position: Vec3A,
+    pub normal: Vec3A,
+    pub body_id: BodyID,
+    pub sub_shape_id: SubShapeID,
+    pub fraction: f32,
+}
+const_assert_eq!(mem::size_of::<RayCastHit>(), 48);
+
+unsafe impl ExternType for RayCastHit {
+    type Id = type_id!("XRayCastHit");
+    type Kind = kind::Trivial;
+}
+
+impl Default for RayCastHit {
+    fn default() -> RayCastHit {
+        RayCastHit {
+            position: Vec3A::ZERO,
+            normal: Vec3A::ZERO,
+            body_id: BodyID::INVALID,
+            sub_shape_id: SubShapeID::EMPTY,
+            fraction: 1.0 + f32::EPSILON,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeCastSettings {
+    pub back_face_mode_triangles: BackFaceMode,
+    pub back_face_mode_convex: BackFaceMode,
+    pub active_edge_mode: ActiveEdgeMode,
+    pub collect_faces_mode: CollectFacesMode,
+    pub use_shrunken_shape_and_convex_radius: bool,
+    pub return_deepest_point: bool,
+    /// Collect every hit sorted by fraction instead of only the closest one.
+    pub collect_all: bool,
+    pub collision_tolerance: f32,
+    pub penetration_tolerance: f32,
+    pub active_edge_movement_direction: Vec3A,
+}
+const_assert_eq!(mem::size_of::<ShapeCastSettings>(), 32);
+
+unsafe impl ExternType for ShapeCastSettings {
+    type Id = type_id!("XShapeCastSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for ShapeCastSettings {
+    fn default() -> ShapeCastSettings {
+        ShapeCastSettings {
+            back_face_mode_triangles: BackFaceMode::IgnoreBackFaces,
+            back_face_mode_convex: BackFaceMode::IgnoreBackFaces,
+            active_edge_mode: ActiveEdgeMode::CollideOnlyWithActive,
+            collect_faces_mode: CollectFacesMode::NoFaces,
+            use_shrunken_shape_and_convex_radius: false,
+            return_deepest_point: false,
+            collect_all: false,
+            collision_tolerance: 1.0e-4,
+            penetration_tolerance: 1.0e-4,
+            active_edge_movement_direction: Vec3A::ZERO,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct ShapeCastResult {
+    pub collide: CollideShapeResult,
+    /// Time of impact, as a fraction of the cast direction.
+    pub fraction: f32,
+    pub is_back_face_hit: bool,
+}
+const_assert_eq!(mem::size_of::<ShapeCastResult>(), 1136);
+
+unsafe impl ExternType for ShapeCastResult {
+    type Id = type_id!("ShapeCastResult");
+    type Kind = kind::Trivial;
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CollideShapeSettings {
+    pub active_edge_mode: ActiveEdgeMode,
+    pub collect_faces_mode: CollectFacesMode,
+    pub back_face_mode: BackFaceMode,
+    pub collision_tolerance: f32,
+    pub penetration_tolerance: f32,
+    /// Bodies within this distance of the shape are reported as well, with a negative penetration depth.
+    pub max_separation_distance: f32,
+    pub active_edge_movement_direction: Vec3A,
+}
+const_assert_eq!(mem::size_of::<CollideShapeSettings>(), 32);
+
+unsafe impl ExternType for CollideShapeSettings {
+    type Id = type_id!("XCollideShapeSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for CollideShapeSettings {
+    fn default() -> CollideShapeSettings {
+        CollideShapeSettings {
+            active_edge_mode: ActiveEdgeMode::CollideOnlyWithActive,
+            collect_faces_mode: CollectFacesMode::NoFaces,
+            back_face_mode: BackFaceMode::IgnoreBackFaces,
+            collision_tolerance: 1.0e-4,
+            penetration_tolerance: 1.0e-4,
+            max_separation_distance: 0.0,
+            active_edge_movement_direction: Vec3A::ZERO,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CollidePointResult {
+    pub body_id: BodyID,
+    pub sub_shape_id: SubShapeID,
+}
+const_assert_eq!(mem::size_of::<CollidePointResult>(), 8);
+
+unsafe impl ExternType for CollidePointResult {
+    type Id = type_id!("CollidePointResult");
+    type Kind = kind::Trivial;
+}
+
+#[inline]
+fn body_filter_ptr<BF: BodyFilter>(body_filter: &VPair<BF, BodyFilterVTable>) -> *const ffi::BodyFilter {
+    body_filter as *const _ as *const ffi::BodyFilter
+}
+
+impl<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener> PhysicsSystem<CL, BAL, SBCL> {
+    /// Casts a ray from `origin` to `origin + direction` and returns the closest hit.
+    #[inline]
+    pub fn cast_ray(&self, origin: Vec3A, direction: Vec3A, filter: &QueryFilter) -> Option<RayCastHit> {
+        unsafe { self.cast_ray_impl(origin, direction, filter, ptr::null()) }
+    }
+
+    /// Same as `cast_ray`, bodies rejected by `body_filter` are skipped.
+    #[inline]
+    pub fn cast_ray_with_body_filter<BF: BodyFilter>(
+        &self,
+        origin: Vec3A,
+        direction: Vec3A,
+        filter: &QueryFilter,
+        body_filter: &VPair<BF, BodyFilterVTable>,
+    ) -> Option<RayCastHit> {
+        unsafe { self.cast_ray_impl(origin, direction, filter, body_filter_ptr(body_filter)) }
+    }
+
+    unsafe fn cast_ray_impl(
+        &self,
+        origin: Vec3A,
+        direction: Vec3A,
+        filter: &QueryFilter,
+        body_filter: *const ffi::BodyFilter,
+    ) -> Option<RayCastHit> {
+        let mut hit = RayCastHit::default();
+        let found = ffi::CastRay(
+            self.as_x_ptr(),
+            origin.into(),
+            direction.into(),
+            filter,
+            body_filter,
+            &mut hit,
+        );
+        match found {
+            true => Some(hit),
+            false => None,
+        }
+    }
+
+    /// Casts a ray from `origin` to `origin + direction` and returns all hits, sorted by fraction.
+    #[inline]
+    pub fn cast_ray_all(&self, origin: Vec3A, direction: Vec3A, filter: &QueryFilter) -> Vec<RayCastHit> {
+        unsafe { self.cast_ray_all_impl(origin, direction, filter, ptr::null()) }
+    }
+
+    /// Same as `cast_ray_all`, bodies rejected by `body_filter` are skipped.
+    #[inline]
+    pub fn cast_ray_all_with_body_filter<BF: BodyFilter>(
+        &self,
+        origin: Vec3A,
+        direction: Vec3A,
+        filter: &QueryFilter,
+        body_filter: &VPair<BF, BodyFilterVTable>,
+    ) -> Vec<RayCastHit> {
+        unsafe { self.cast_ray_all_impl(origin, direction, filter, body_filter_ptr(body_filter)) }
+    }
+
+    unsafe fn cast_ray_all_impl(
+        &self,
+        origin: Vec3A,
+        direction: Vec3A,
+        filter: &QueryFilter,
+        body_filter: *const ffi::BodyFilter,
+    ) -> Vec<RayCastHit> {
+        let mut hits = Vec::new();
+        ffi::CastRayAll(
+            self.as_x_ptr(),
+            origin.into(),
+            direction.into(),
+            filter,
+            body_filter,
+            &mut hits,
+        );
+        hits
+    }
+
+    /// Sweeps `shape` from `transform` along `direction`, the hits are in world space.
+    #[inline]
+    pub fn cast_shape(
+        &self,
+        shape: &Shape,
+        transform: Mat4,
+        scale: Vec3A,
+        direction: Vec3A,
+        settings: &ShapeCastSettings,
+        filter: &QueryFilter,
+    ) -> Vec<ShapeCastResult> {
+        unsafe { self.cast_shape_impl(shape, transform, scale, direction, settings, filter, ptr::null()) }
+    }
+
+    /// Same as `cast_shape`, bodies rejected by `body_filter` are skipped.
+    #[inline]
+    pub fn cast_shape_with_body_filter<BF: BodyFilter>(
+        &self,
+        shape: &Shape,
+        transform: Mat4,
+        scale: Vec3A,
+        direction: Vec3A,
+        settings: &ShapeCastSettings,
+        filter: &QueryFilter,
+        body_filter: &VPair<BF, BodyFilterVTable>,
+    ) -> Vec<ShapeCastResult> {
+        let body_filter = body_filter_ptr(body_filter);
+        unsafe { self.cast_shape_impl(shape, transform, scale, direction, settings, filter, body_filter) }
+    }
+
+    unsafe fn cast_shape_impl(
+        &self,
+        shape: &Shape,
+        transform: Mat4,
+        scale: Vec3A,
+        direction: Vec3A,
+        settings: &ShapeCastSettings,
+        filter: &QueryFilter,
+        body_filter: *const ffi::BodyFilter,
+    ) -> Vec<ShapeCastResult> {
+        let mut hits = Vec::new();
+        ffi::CastShape(
+            self.as_x_ptr(),
+            &shape.0,
+            transform.into(),
+            scale.into(),
+            direction.into(),
+            settings,
+            filter,
+            body_filter,
+            &mut hits,
+        );
+        hits
+    }
+
+    /// Returns every body overlapping `shape` placed at `transform`, sorted by penetration depth.
+    #[inline]
+    pub fn collide_shape(
+        &self,
+        shape: &Shape,
+        transform: Mat4,
+        scale: Vec3A,
+        settings: &CollideShapeSettings,
+        filter: &QueryFilter,
+    ) -> Vec<CollideShapeResult> {
+        unsafe { self.collide_shape_impl(shape, transform, scale, settings, filter, ptr::null()) }
+    }
+
+    /// Same as `collide_shape`, bodies rejected by `body_filter` are skipped.
+    #[inline]
+    pub fn collide_shape_with_body_filter<BF: BodyFilter>(
+        &self,
+        shape: &Shape,
+        transform: Mat4,
+        scale: Vec3A,
+        settings: &CollideShapeSettings,
+        filter: &QueryFilter,
+        body_filter: &VPair<BF, BodyFilterVTable>,
+    ) -> Vec<CollideShapeResult> {
+        let body_filter = body_filter_ptr(body_filter);
+        unsafe { self.collide_shape_impl(shape, transform, scale, settings, filter, body_filter) }
+    }
+
+    unsafe fn collide_shape_impl(
+        &self,
+        shape: &Shape,
+        transform: Mat4,
+        scale: Vec3A,
+        settings: &CollideShapeSettings,
+        filter: &QueryFilter,
+        body_filter: *const ffi::BodyFilter,
+    ) -> Vec<CollideShapeResult> {
+        let mut hits = Vec::new();
+        ffi::CollideShape(
+            self.as_x_ptr(),
+            &shape.0,
+            transform.into(),
+            scale.into(),
+            settings,
+            filter,
+            body_filter,
+            &mut hits,
+        );
+        hits
+    }
+
+    /// Returns every body containing `point`.
+    #[inline]
+    pub fn collide_point(&self, point: Vec3A, filter: &QueryFilter) -> Vec<CollidePointResult> {
+        unsafe { self.collide_point_impl(point, filter, ptr::null()) }
+    }
+
+    /// Same as `collide_point`, bodies rejected by `body_filter` are skipped.
+    #[inline]
+    pub fn collide_point_with_body_filter<BF: BodyFilter>(
+        &self,
+        point: Vec3A,
+        filter: &QueryFilter,
+        body_filter: &VPair<BF, BodyFilterVTable>,
+    ) -> Vec<CollidePointResult> {
+        unsafe { self.collide_point_impl(point, filter, body_filter_ptr(body_filter)) }
+    }
+
+    unsafe fn collide_point_impl(
+        &self,
+        point: Vec3A,
+        filter: &QueryFilter,
+        body_filter: *const ffi::BodyFilter,
+    ) -> Vec<CollidePointResult> {
+        let mut hits = Vec::new();
+        ffi::CollidePoint(self.as_x_ptr(), point.into(), filter, body_filter, &mut hits);
+        hits
+    }
+
+    #[inline]
+    pub fn broad_phase_query(&self) -> BroadPhaseQuery<'_> {
+        BroadPhaseQuery {
+            system: self.as_x_ptr(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+//
+// BroadPhaseQuery
+//
+
+pub struct BroadPhaseQuery<'a> {
+    system: *mut ffi::XPhysicsSystem,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl fmt::Debug for BroadPhaseQuery<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BroadPhaseQuery").finish()
+    }
+}
+
+impl BroadPhaseQuery<'_> {
+    #[inline]
+    pub fn collide_aabox(&self, aabox: &AABox, filter: &QueryFilter) -> Vec<BodyID> {
+        let mut bodies = Vec::new();
+        unsafe { ffi::BroadPhaseCollideAABox(self.system, aabox, filter, &mut bodies) };
+        bodies
+    }
+
+    #[inline]
+    pub fn collide_sphere(&self, center: Vec3A, radius: f32, filter: &QueryFilter) -> Vec<BodyID> {
+        let mut bodies = Vec::new();
+        unsafe { ffi::BroadPhaseCollideSphere(self.system, center.into(), radius, filter, &mut bodies) };
+        bodies
+    }
+
+    #[inline]
+    pub fn collide_oriented_box(&self, orientation: Mat4, half_extents: Vec3A, filter: &QueryFilter) -> Vec<BodyID> {
+        let mut bodies = Vec::new();
+        unsafe {
+            ffi::BroadPhaseCollideOrientedBox(
+                self.system,
+                orientation.into(),
+                half_extents.into(),
+                filter,
+                &mut bodies,
+            )
+        };
+        bodies
+    }
+
+    /// Sweeps `aabox` along `direction`, the bodies are sorted by time of impact.
+    #[inline]
+    pub fn cast_aabox(&self, aabox: &AABox, direction: Vec3A, filter: &QueryFilter) -> Vec<BodyID> {
+        let mut bodies = Vec::new();
+        unsafe { ffi::BroadPhaseCastAABox(self.system, aabox, direction.into(), filter, &mut bodies) };
+        bodies
+    }
+}
+
+#[vtable(allow_empty)]
+#[repr(C)]
+pub struct BodyFilterVTable {
+    pub drop: extern "C" fn(*mut u8),
+    pub should_collide: extern "C" fn(*const u8, body_id: &BodyID) -> bool,
+    pub should_collide_locked: extern "C" fn(*const u8, body: &Body) -> bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Quat;
+    use jolt_macros::vdata;
+
+    use super::*;
+    use crate::base::MotionType;
+    use crate::body::BodyCreationSettings;
+    use crate::shape::{create_box_shape, BoxShapeSettings};
+    use crate::system::PhysicsSystemConfig;
+    use crate::test_callback::create_test_system;
+
+    use crate as jolt_physics_rs;
+
+    /// A floor with its top at y = 0 and two unit cubes resting on it at x = 0 and x = 3.
+    fn create_world() -> (PhysicsSystem, BodyID, BodyID, BodyID) {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let floor = create_box_shape(&BoxShapeSettings::new(10.0, 1.0, 10.0)).unwrap();
+        let cube = create_box_shape(&BoxShapeSettings::new(0.5, 0.5, 0.5)).unwrap();
+        let body_itf = system.body_itf();
+        let floor = body_itf
+            .create_add_body(
+                &BodyCreationSettings::new_static(floor, 0, Vec3A::NEG_Y, Quat::IDENTITY),
+                false,
+            )
+            .unwrap();
+        let mut create_cube = |x: f32| {
+            let settings = BodyCreationSettings::new(
+                cube.clone(),
+                0,
+                MotionType::Dynamic,
+                Vec3A::new(x, 1.0, 0.0),
+                Quat::IDENTITY,
+            );
+            body_itf.create_add_body(&settings, false).unwrap()
+        };
+        let cube1 = create_cube(0.0);
+        let cube2 = create_cube(3.0);
+        system.optimize_broad_phase();
+        (system, floor, cube1, cube2)
+    }
+
+    #[vdata(BodyFilterVTable)]
+    struct IgnoreBodyFilter {
+        ignored: BodyID,
+    }
+
+    impl BodyFilter for IgnoreBodyFilter {
+        fn should_collide(&self, body_id: &BodyID) -> bool {
+            *body_id != self.ignored
+        }
+
+        fn should_collide_locked(&self, body: &Body) -> bool {
+            body.get_id() != self.ignored
+        }
+    }
+
+    #[test]
+    fn test_world_cast_ray() {
+        let (system, floor, cube1, cube2) = create_world();
+        let filter = QueryFilter::default();
+        let origin = Vec3A::new(0.0, 5.0, 0.0);
+        let direction = Vec3A::NEG_Y * 10.0;
+
+        let hit = system.cast_ray(origin, direction, &filter).unwrap();
+        assert_eq!(hit.body_id, cube1);
+        assert!((hit.fraction - 0.35).abs() < 1e-4);
+        assert!(hit.position.abs_diff_eq(Vec3A::new(0.0, 1.5, 0.0), 1e-3));
+        assert!(hit.normal.abs_diff_eq(Vec3A::Y, 1e-3));
+
+        let ignore = IgnoreBodyFilter::new_vbox(IgnoreBodyFilter { ignored: cube1 });
+        let hit = system
+            .cast_ray_with_body_filter(origin, direction, &filter, &ignore)
+            .unwrap();
+        assert_eq!(hit.body_id, floor);
+        assert!((hit.fraction - 0.5).abs() < 1e-4);
+
+        let hits = system.cast_ray_all(origin, direction, &filter);
+        assert_eq!(hits.iter().map(|hit| hit.body_id).collect::<Vec<_>>(), [cube1, floor]);
+        let hits = system.cast_ray_all_with_body_filter(origin, direction, &filter, &ignore);
+        assert_eq!(hits.iter().map(|hit| hit.body_id).collect::<Vec<_>>(), [floor]);
+
+        assert!(system.cast_ray(origin, Vec3A::Y, &filter).is_none());
+
+        let hits = system.collide_point(Vec3A::new(3.0, 1.0, 0.0), &filter);
+        assert_eq!(hits.iter().map(|hit| hit.body_id).collect::<Vec<_>>(), [cube2]);
+        let ignore = IgnoreBodyFilter::new_vbox(IgnoreBodyFilter { ignored: cube2 });
+        assert!(system
+            .collide_point_with_body_filter(Vec3A::new(3.0, 1.0, 0.0), &filter, &ignore)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_broad_phase_query() {
+        let (system, floor, cube1, cube2) = create_world();
+        let filter = QueryFilter::default();
+        let query = system.broad_phase_query();
+
+        let aabox = AABox::new(Vec3A::new(2.6, 0.6, -0.4), Vec3A::new(3.4, 1.4, 0.4));
+        assert_eq!(query.collide_aabox(&aabox, &filter), [cube2]);
+
+        let mut bodies = query.collide_sphere(Vec3A::new(0.0, 0.25, 0.0), 0.5, &filter);
+        bodies.sort_by_key(|body| body.0);
+        let mut expected = [floor, cube1];
+        expected.sort_by_key(|body| body.0);
+        assert_eq!(bodies, expected);
+
+        let orientation = Mat4::from_rotation_translation(Quat::from_rotation_y(0.3), Vec3A::new(3.0, 1.0, 0.0).into());
+        assert_eq!(
+            query.collide_oriented_box(orientation, Vec3A::splat(0.3), &filter),
+            [cube2]
+        );
+
+        // Sorted by time of impact
+        let aabox = AABox::new(Vec3A::new(-5.4, 0.6, -0.4), Vec3A::new(-4.6, 1.4, 0.4));
+        assert_eq!(query.cast_aabox(&aabox, Vec3A::X * 10.0, &filter), [cube1, cube2]);
+        assert!(query.cast_aabox(&aabox, Vec3A::NEG_X * 10.0, &filter).is_empty());
+    }
+}
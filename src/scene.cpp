@@ -0,0 +1,97 @@
+#include "jolt-physics-rs/src/ffi.h"
+#include "jolt-physics-rs/src/scene.rs.h"
+
+void SavePhysicsScene(XPhysicsSystem* system, rust::Vec<uint8>& data) {
+	Ref<PhysicsScene> scene = new PhysicsScene();
+	scene->FromPhysicsSystem(&system->PhySys());
+	StateRecorderImpl recorder;
+	scene->SaveBinaryState(recorder, true, true);
+	StateRecorderToVec(recorder, data);
+}
+
+static bool GetSceneBodyID(const BodyIDVector& bodyIds, uint32 index, BodyID& bodyId) {
+	if (index == PhysicsScene::cFixedToWorld) {
+		bodyId = BodyID();
+		return true;
+	}
+	if (index >= bodyIds.size()) {
+		return false;
+	}
+	bodyId = bodyIds[index];
+	return true;
+}
+
+bool LoadPhysicsScene(XPhysicsSystem* system, rust::Slice<const uint8> data) {
+	StateRecorderImpl recorder;
+	StateRecorderFromSlice(recorder, data);
+	PhysicsScene::PhysicsSceneResult result = PhysicsScene::sRestoreFromBinaryState(recorder);
+	if (result.HasError()) {
+		return false;
+	}
+	const PhysicsScene& scene = *result.Get();
+	BodyInterface& bi = system->PhySys().GetBodyInterface();
+
+	// Unlike PhysicsScene::CreateBodies, create everything before adding anything, so a failure can be rolled back
+	size_t numBodies = scene.GetBodies().size() + scene.GetSoftBodies().size();
+	BodyIDVector bodyIds;
+	bodyIds.reserve(numBodies);
+	for (const BodyCreationSettings& settings : scene.GetBodies()) {
+		const Body* body = bi.CreateBody(settings);
+		if (body == nullptr) {
+			break;
+		}
+		bodyIds.push_back(body->GetID());
+	}
+	if (bodyIds.size() == scene.GetBodies().size()) {
+		for (const SoftBodyCreationSettings& settings : scene.GetSoftBodies()) {
+			const Body* body = bi.CreateSoftBody(settings);
+			if (body == nullptr) {
+				break;
+			}
+			bodyIds.push_back(body->GetID());
+		}
+	}
+
+	bool succeeded = bodyIds.size() == numBodies;
+	Array<Ref<Constraint>> constraints;
+	for (const PhysicsScene::ConnectedConstraint& cc : scene.GetConstraints()) {
+		if (!succeeded) {
+			break;
+		}
+		BodyID bodyId1;
+		BodyID bodyId2;
+		if (!GetSceneBodyID(bodyIds, cc.mBody1, bodyId1) || !GetSceneBodyID(bodyIds, cc.mBody2, bodyId2) || bodyId1 == bodyId2) {
+			succeeded = false;
+			break;
+		}
+		Constraint* constraint = bi.CreateConstraint(cc.mSettings, bodyId1, bodyId2);
+		if (constraint == nullptr) {
+			succeeded = false;
+			break;
+		}
+		constraints.push_back(constraint);
+	}
+
+	if (!succeeded) {
+		constraints.clear();
+		bi.DestroyBodies(bodyIds.data(), (int)bodyIds.size());
+		return false;
+	}
+	BodyInterface::AddState state = bi.AddBodiesPrepare(bodyIds.data(), (int)bodyIds.size());
+	bi.AddBodiesFinalize(bodyIds.data(), (int)bodyIds.size(), state, EActivation::Activate);
+	for (Constraint* constraint : constraints) {
+		system->PhySys().AddConstraint(constraint);
+	}
+	return true;
+}
+
+bool GetSceneBodySettings(XPhysicsSystem* system, const BodyID& bodyId, BodyCreationSettings& settings) {
+	BodyLockRead lock(system->PhySys().GetBodyLockInterface(), bodyId);
+	if (!lock.Succeeded() || lock.GetBody().IsSoftBody()) {
+		return false;
+	}
+	settings = lock.GetBody().GetBodyCreationSettings();
+	// The group filter is not owned by the Rust side
+	settings.mCollisionGroup.SetGroupFilter(nullptr);
+	return true;
+}
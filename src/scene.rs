@@ -0,0 +1,237 @@
+use std::pin::Pin;
+
+use crate::base::BodyID;
+use crate::body::BodyCreationSettings;
+use crate::error::{JoltError, JoltResult};
+use crate::soft_body::SoftBodyContactListener;
+use crate::system::{BodyActivationListener, ContactListener, PhysicsSystem};
+
+#[cxx::bridge()]
+pub(crate) mod ffi {
+    unsafe extern "C++" {
+        include!("rust/cxx.h");
+        include!("jolt-physics-rs/src/ffi.h");
+
+        type BodyID = crate::base::ffi::BodyID;
+        type BodyCreationSettings = crate::system::ffi::BodyCreationSettings;
+        type XPhysicsSystem = crate::system::ffi::XPhysicsSystem;
+
+        unsafe fn SavePhysicsScene(system: *mut XPhysicsSystem, data: &mut Vec<u8>);
+        unsafe fn LoadPhysicsScene(system: *mut XPhysicsSystem, data: &[u8]) -> bool;
+        unsafe fn GetSceneBodySettings(
+            system: *mut XPhysicsSystem,
+            body_id: &BodyID,
+            settings: Pin<&mut BodyCreationSettings>,
+        ) -> bool;
+    }
+}
+
+/// Writes all rigid bodies and constraints of `system` as a Jolt `PhysicsScene` binary stream.
+pub fn save_physics_scene<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &PhysicsSystem<CL, BAL, SBCL>,
+    data: &mut Vec<u8>,
+) {
+    unsafe { ffi::SavePhysicsScene(system.as_x_ptr(), data) };
+}
+
+/// Creates the bodies and constraints of a `PhysicsScene` binary stream in `system`, body ids are not preserved.
+/// Nothing is added to `system` on error.
+pub fn load_physics_scene<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    data: &[u8],
+) -> JoltResult<()> {
+    match unsafe { ffi::LoadPhysicsScene(system.as_x_ptr(), data) } {
+        true => Ok(()),
+        false => Err(JoltError::LoadScene),
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SceneBody {
+    pub body_id: BodyID,
+    pub active: bool,
+    pub settings: BodyCreationSettings,
+}
+
+/// Rigid bodies of a physics system in a serde/rkyv friendly form, constraints are not included.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicsSceneDesc {
+    pub bodies: Vec<SceneBody>,
+}
+
+impl PhysicsSceneDesc {
+    /// Captures the creation settings and current state (transform, velocity, user data) of every rigid body.
+    pub fn from_physics_system<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+        system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    ) -> PhysicsSceneDesc {
+        let mut bodies = Vec::new();
+        for body_id in system.get_bodies() {
+            let mut settings = BodyCreationSettings::default();
+            let found = unsafe {
+                let settings = Pin::new_unchecked(&mut *(&mut settings as *mut _ as *mut ffi::BodyCreationSettings));
+                ffi::GetSceneBodySettings(system.as_x_ptr(), &body_id, settings)
+            };
+            if found {
+                let active = system.body_itf().is_active(body_id);
+                bodies.push(SceneBody {
+                    body_id,
+                    active,
+                    settings,
+                });
+            }
+        }
+        PhysicsSceneDesc { bodies }
+    }
+
+    /// Creates and adds the bodies to `system` with their original body ids.
+    /// All bodies are created before any is added, on error the created ones are destroyed again.
+    pub fn create_bodies<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+        &self,
+        system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    ) -> JoltResult<()> {
+        let body_itf = system.body_itf();
+        let mut body_ids = Vec::with_capacity(self.bodies.len());
+        for body in &self.bodies {
+            match body_itf.create_body_with_id(body.body_id, &body.settings) {
+                Ok(body_id) => body_ids.push(body_id),
+                Err(err) => {
+                    for body_id in body_ids {
+                        body_itf.destroy_body(body_id);
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        for (body, body_id) in self.bodies.iter().zip(body_ids) {
+            body_itf.add_body(body_id, body.active);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Quat, Vec3A};
+
+    use super::*;
+    use crate::base::MotionType;
+    use crate::constraint::{create_fixed_constraint, FixedConstraintSettings};
+    use crate::shape::{create_box_shape, BoxShapeSettings};
+    use crate::system::PhysicsSystemConfig;
+    use crate::test_callback::create_test_system;
+
+    /// A floor and a stack of boxes, the user data of a box is its height.
+    fn create_scene(system: &mut PhysicsSystem) -> Vec<BodyID> {
+        let floor = create_box_shape(&BoxShapeSettings::new(10.0, 1.0, 10.0)).unwrap();
+        let cube = create_box_shape(&BoxShapeSettings::new(0.5, 0.5, 0.5)).unwrap();
+        let body_itf = system.body_itf();
+        let mut body_ids = vec![body_itf
+            .create_add_body(
+                &BodyCreationSettings::new_static(floor, 0, Vec3A::new(0.0, -1.0, 0.0), Quat::IDENTITY),
+                false,
+            )
+            .unwrap()];
+        for height in 1..4 {
+            let position = Vec3A::new(0.0, height as f32 * 1.5, 0.0);
+            let settings = BodyCreationSettings {
+                user_data: height,
+                ..BodyCreationSettings::new(cube.clone(), 0, MotionType::Dynamic, position, Quat::IDENTITY)
+            };
+            body_ids.push(body_itf.create_add_body(&settings, true).unwrap());
+        }
+        body_ids
+    }
+
+    #[test]
+    fn test_binary_scene_round_trip() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let body_ids = create_scene(&mut system);
+        let fixed = create_fixed_constraint(
+            &mut system,
+            &FixedConstraintSettings::new_auto_detect(),
+            body_ids[2],
+            body_ids[3],
+        )
+        .unwrap();
+        assert!(system.add_constraint(&fixed));
+
+        let mut data = Vec::new();
+        save_physics_scene(&system, &mut data);
+
+        let mut loaded: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        load_physics_scene(&mut loaded, &data).unwrap();
+        assert_eq!(loaded.get_num_bodies(), 4);
+        for body_id in loaded.get_bodies() {
+            let body_itf = loaded.body_itf();
+            assert!(body_itf.is_added(body_id));
+            let height = body_itf.get_user_data(body_id);
+            if height > 0 {
+                assert_eq!(
+                    body_itf.get_position(body_id),
+                    Vec3A::new(0.0, height as f32 * 1.5, 0.0)
+                );
+            }
+        }
+        loaded.update(1.0 / 60.0, 1).unwrap();
+
+        // Nothing is left behind when the scene doesn't fit.
+        let mut small: PhysicsSystem = create_test_system(&PhysicsSystemConfig {
+            max_bodies: 2,
+            ..Default::default()
+        });
+        assert!(load_physics_scene(&mut small, &data).is_err());
+        assert_eq!(small.get_num_bodies(), 0);
+        assert!(load_physics_scene(&mut small, &data[..data.len() / 2]).is_err());
+        assert_eq!(small.get_num_bodies(), 0);
+    }
+
+    #[test]
+    fn test_scene_desc_rollback() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        create_scene(&mut system);
+        let desc = PhysicsSceneDesc::from_physics_system(&mut system);
+        assert_eq!(desc.bodies.len(), 4);
+
+        // The last body id is taken, the bodies created before are destroyed again.
+        let mut taken: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let last = desc.bodies.last().unwrap();
+        taken
+            .body_itf()
+            .create_body_with_id(last.body_id, &last.settings)
+            .unwrap();
+        assert!(desc.create_bodies(&mut taken).is_err());
+        assert_eq!(taken.get_num_bodies(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_scene_round_trip() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        create_scene(&mut system);
+        system.update(1.0 / 60.0, 1).unwrap();
+        let desc = PhysicsSceneDesc::from_physics_system(&mut system);
+
+        let json = serde_json::to_string(&desc).unwrap();
+        let restored: PhysicsSceneDesc = serde_json::from_str(&json).unwrap();
+        let mut loaded: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        restored.create_bodies(&mut loaded).unwrap();
+        assert_eq!(loaded.get_num_bodies(), 4);
+        for body in &desc.bodies {
+            let body_itf = loaded.body_itf();
+            assert_eq!(body_itf.is_active(body.body_id), body.active);
+            assert_eq!(body_itf.get_user_data(body.body_id), body.settings.user_data);
+            assert_eq!(
+                body_itf.get_position(body.body_id),
+                system.body_itf().get_position(body.body_id)
+            );
+            assert_eq!(
+                body_itf.get_linear_velocity(body.body_id),
+                system.body_itf().get_linear_velocity(body.body_id)
+            );
+        }
+    }
+}
@@ -0,0 +1,470 @@
+//! Adapters for the `serde` and `rkyv` features.
+//!
+//! Shapes are written as their `ShapeDesc` tree, see `ShapeDesc::from_shape`, materials as their debug name and color.
+//! Payloads of materials created by `create_physics_material` are not serialized.
+//! Materials are shared on deserialization, equal descriptions give the same material while it is alive.
+//! Settings borrowing slices (convex hull, mesh, height field and compound shapes) only implement `serde::Serialize`,
+//! their owned `*Desc` counterparts (`ConvexHullShapeDesc`, `CompoundShapeDesc`, ...) are the serialization form.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::base::{AllowedDOFs, Color, JRef, MotionQuality, MotionType, OverrideMassProperties};
+use crate::character::BackFaceMode;
+use crate::error::JoltResult;
+use crate::shape::{PhysicsMaterial, Shape, ShapeDesc};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaterialDesc {
+    pub name: String,
+    pub color: Color,
+}
+
+impl MaterialDesc {
+    #[inline]
+    fn from_material(material: &PhysicsMaterial) -> MaterialDesc {
+        MaterialDesc {
+            name: material.get_debug_name().to_string(),
+            color: material.get_debug_color(),
+        }
+    }
+
+    /// Reuses the material of an equal description that is still referenced outside the cache.
+    fn to_material(&self) -> JRef<PhysicsMaterial> {
+        MATERIALS.with_borrow_mut(|materials| {
+            if let Some(material) = materials.get(self) {
+                return material.clone();
+            }
+            materials.retain(|_, material| material.count_ref() > 1);
+            let material = PhysicsMaterial::new_simple(&self.name, self.color);
+            materials.insert(self.clone(), material.clone());
+            material
+        })
+    }
+}
+
+thread_local! {
+    static MATERIALS: RefCell<HashMap<MaterialDesc, JRef<PhysicsMaterial>>> = RefCell::new(HashMap::new());
+}
+
+#[inline]
+fn shape_to_desc(shape: &Option<JRef<Shape>>) -> JoltResult<Option<ShapeDesc>> {
+    shape.as_ref().map(|shape| ShapeDesc::from_shape(shape)).transpose()
+}
+
+#[inline]
+fn material_to_desc(material: &Option<JRef<PhysicsMaterial>>) -> Option<MaterialDesc> {
+    material.as_ref().map(|material| MaterialDesc::from_material(material))
+}
+
+macro_rules! serde_named_enum {
+    ($type:ident { $($variant:ident),* $(,)? }) => {
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $type {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $(
+                    if *self == $type::$variant {
+                        return serializer.serialize_str(stringify!($variant));
+                    }
+                )*
+                Err(serde::ser::Error::custom(format_args!("invalid {} {}", stringify!($type), self.repr)))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+                match name.as_str() {
+                    $(stringify!($variant) => Ok($type::$variant),)*
+                    _ => Err(serde::de::Error::unknown_variant(&name, &[$(stringify!($variant)),*])),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! rkyv_u8_enum {
+    ($type:ident, $archived:ident) => {
+        #[doc = concat!("Archived `", stringify!($type), "`.")]
+        #[cfg(feature = "rkyv")]
+        #[repr(transparent)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $archived(pub u8);
+
+        #[cfg(feature = "rkyv")]
+        unsafe impl rkyv::Portable for $archived {}
+
+        // Every u8 is a valid repr of the shared enums.
+        #[cfg(feature = "rkyv")]
+        unsafe impl<C: rkyv::rancor::Fallible + ?Sized> rkyv::bytecheck::CheckBytes<C> for $archived {
+            #[inline]
+            unsafe fn check_bytes(_: *const Self, _: &mut C) -> Result<(), C::Error> {
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "rkyv")]
+        impl rkyv::Archive for $type {
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            fn resolve(&self, _: (), out: rkyv::Place<Self::Archived>) {
+                unsafe { out.write_unchecked($archived(self.repr)) };
+            }
+        }
+
+        #[cfg(feature = "rkyv")]
+        impl<S: rkyv::rancor::Fallible + ?Sized> rkyv::Serialize<S> for $type {
+            #[inline]
+            fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "rkyv")]
+        impl<D: rkyv::rancor::Fallible + ?Sized> rkyv::Deserialize<$type, D> for $archived {
+            #[inline]
+            fn deserialize(&self, _: &mut D) -> Result<$type, D::Error> {
+                Ok($type { repr: self.0 })
+            }
+        }
+    };
+}
+
+serde_named_enum!(MotionType {
+    Static,
+    Kinematic,
+    Dynamic
+});
+serde_named_enum!(MotionQuality { Discrete, LinearCast });
+serde_named_enum!(OverrideMassProperties {
+    CalculateMassAndInertia,
+    CalculateInertia,
+    MassAndInertiaProvided,
+});
+serde_named_enum!(BackFaceMode {
+    IgnoreBackFaces,
+    CollideWithBackFaces,
+});
+
+// AllowedDOFs is a bit mask, combinations have no name.
+#[cfg(feature = "serde")]
+impl serde::Serialize for AllowedDOFs {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.repr)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AllowedDOFs {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <u8 as serde::Deserialize>::deserialize(deserializer).map(|repr| AllowedDOFs { repr })
+    }
+}
+
+rkyv_u8_enum!(MotionType, ArchivedMotionType);
+rkyv_u8_enum!(MotionQuality, ArchivedMotionQuality);
+rkyv_u8_enum!(OverrideMassProperties, ArchivedOverrideMassProperties);
+rkyv_u8_enum!(BackFaceMode, ArchivedBackFaceMode);
+rkyv_u8_enum!(AllowedDOFs, ArchivedAllowedDOFs);
+
+//
+// serde
+//
+
+#[cfg(feature = "serde")]
+pub mod serde_shape {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    pub fn serialize<S: Serializer>(shape: &Option<JRef<Shape>>, serializer: S) -> Result<S::Ok, S::Error> {
+        shape_to_desc(shape)
+            .map_err(<S::Error as serde::ser::Error>::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<JRef<Shape>>, D::Error> {
+        match Option::<ShapeDesc>::deserialize(deserializer)? {
+            Some(desc) => desc.build().map(Some).map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub mod serde_material {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        material: &Option<JRef<PhysicsMaterial>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        material_to_desc(material).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<JRef<PhysicsMaterial>>, D::Error> {
+        Ok(Option::<MaterialDesc>::deserialize(deserializer)?.map(|desc| desc.to_material()))
+    }
+}
+
+#[cfg(feature = "serde")]
+pub mod serde_materials {
+    use serde::ser::SerializeSeq;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::*;
+
+    pub fn serialize<S: Serializer>(materials: &[JRef<PhysicsMaterial>], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(materials.len()))?;
+        for material in materials {
+            seq.serialize_element(&MaterialDesc::from_material(material))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<JRef<PhysicsMaterial>>, D::Error> {
+        let descs = Vec::<MaterialDesc>::deserialize(deserializer)?;
+        Ok(descs.iter().map(|desc| desc.to_material()).collect())
+    }
+}
+
+//
+// rkyv
+//
+
+#[cfg(feature = "rkyv")]
+mod rkyv_with {
+    use rkyv::rancor::{Fallible, Source};
+    use rkyv::with::{ArchiveWith, DeserializeWith, SerializeWith};
+    use rkyv::{Archive, Archived, Deserialize, Place, Resolver, Serialize};
+
+    use super::*;
+
+    pub struct ShapeDescWith;
+
+    /// Keeps the tree described by `serialize_with` for `resolve_with`.
+    pub struct ShapeDescResolver {
+        desc: Option<ShapeDesc>,
+        resolver: Resolver<Option<ShapeDesc>>,
+    }
+
+    impl ArchiveWith<Option<JRef<Shape>>> for ShapeDescWith {
+        type Archived = Archived<Option<ShapeDesc>>;
+        type Resolver = ShapeDescResolver;
+
+        fn resolve_with(_: &Option<JRef<Shape>>, resolver: Self::Resolver, out: Place<Self::Archived>) {
+            resolver.desc.resolve(resolver.resolver, out);
+        }
+    }
+
+    impl<S: Fallible + ?Sized> SerializeWith<Option<JRef<Shape>>, S> for ShapeDescWith
+    where
+        Option<ShapeDesc>: Serialize<S>,
+        S::Error: Source,
+    {
+        fn serialize_with(field: &Option<JRef<Shape>>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            let desc = shape_to_desc(field).map_err(S::Error::new)?;
+            let resolver = desc.serialize(serializer)?;
+            Ok(ShapeDescResolver { desc, resolver })
+        }
+    }
+
+    impl<D: Fallible + ?Sized> DeserializeWith<Archived<Option<ShapeDesc>>, Option<JRef<Shape>>, D> for ShapeDescWith
+    where
+        Archived<ShapeDesc>: rkyv::Deserialize<ShapeDesc, D>,
+        D::Error: Source,
+    {
+        fn deserialize_with(
+            field: &Archived<Option<ShapeDesc>>,
+            deserializer: &mut D,
+        ) -> Result<Option<JRef<Shape>>, D::Error> {
+            match field.as_ref() {
+                Some(desc) => {
+                    let desc: ShapeDesc = desc.deserialize(deserializer)?;
+                    desc.build().map(Some).map_err(D::Error::new)
+                }
+                None => Ok(None),
+            }
+        }
+    }
+
+    pub struct MaterialDescWith;
+
+    impl ArchiveWith<Option<JRef<PhysicsMaterial>>> for MaterialDescWith {
+        type Archived = Archived<Option<MaterialDesc>>;
+        type Resolver = Resolver<Option<MaterialDesc>>;
+
+        fn resolve_with(field: &Option<JRef<PhysicsMaterial>>, resolver: Self::Resolver, out: Place<Self::Archived>) {
+            material_to_desc(field).resolve(resolver, out);
+        }
+    }
+
+    impl<S: Fallible + ?Sized> SerializeWith<Option<JRef<PhysicsMaterial>>, S> for MaterialDescWith
+    where
+        Option<MaterialDesc>: Serialize<S>,
+    {
+        fn serialize_with(
+            field: &Option<JRef<PhysicsMaterial>>,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            material_to_desc(field).serialize(serializer)
+        }
+    }
+
+    impl<D: Fallible + ?Sized> DeserializeWith<Archived<Option<MaterialDesc>>, Option<JRef<PhysicsMaterial>>, D>
+        for MaterialDescWith
+    where
+        Archived<MaterialDesc>: rkyv::Deserialize<MaterialDesc, D>,
+    {
+        fn deserialize_with(
+            field: &Archived<Option<MaterialDesc>>,
+            deserializer: &mut D,
+        ) -> Result<Option<JRef<PhysicsMaterial>>, D::Error> {
+            match field.as_ref() {
+                Some(desc) => Ok(Some(desc.deserialize(deserializer)?.to_material())),
+                None => Ok(None),
+            }
+        }
+    }
+
+    impl ArchiveWith<JRef<PhysicsMaterial>> for MaterialDescWith {
+        type Archived = Archived<MaterialDesc>;
+        type Resolver = Resolver<MaterialDesc>;
+
+        fn resolve_with(field: &JRef<PhysicsMaterial>, resolver: Self::Resolver, out: Place<Self::Archived>) {
+            MaterialDesc::from_material(field).resolve(resolver, out);
+        }
+    }
+
+    impl<S: Fallible + ?Sized> SerializeWith<JRef<PhysicsMaterial>, S> for MaterialDescWith
+    where
+        MaterialDesc: Serialize<S>,
+    {
+        fn serialize_with(field: &JRef<PhysicsMaterial>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            MaterialDesc::from_material(field).serialize(serializer)
+        }
+    }
+
+    impl<D: Fallible + ?Sized> DeserializeWith<Archived<MaterialDesc>, JRef<PhysicsMaterial>, D> for MaterialDescWith
+    where
+        Archived<MaterialDesc>: rkyv::Deserialize<MaterialDesc, D>,
+    {
+        fn deserialize_with(
+            field: &Archived<MaterialDesc>,
+            deserializer: &mut D,
+        ) -> Result<JRef<PhysicsMaterial>, D::Error> {
+            Ok(field.deserialize(deserializer)?.to_material())
+        }
+    }
+
+    /// Skips a raw pointer field, which is null after deserialization.
+    pub struct SkipPtr;
+
+    impl<T> ArchiveWith<*const T> for SkipPtr {
+        type Archived = ();
+        type Resolver = ();
+
+        fn resolve_with(_: &*const T, _: Self::Resolver, _: Place<Self::Archived>) {}
+    }
+
+    impl<T, S: Fallible + ?Sized> SerializeWith<*const T, S> for SkipPtr {
+        fn serialize_with(_: &*const T, _: &mut S) -> Result<(), S::Error> {
+            Ok(())
+        }
+    }
+
+    impl<T, D: Fallible + ?Sized> DeserializeWith<(), *const T, D> for SkipPtr {
+        fn deserialize_with(_: &(), _: &mut D) -> Result<*const T, D::Error> {
+            Ok(std::ptr::null())
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+pub use rkyv_with::*;
+
+#[cfg(test)]
+mod tests {
+    use glam::{Quat, Vec3A};
+    use std::ptr;
+
+    use super::*;
+    use crate::base::ShapeSubType;
+    use crate::body::BodyCreationSettings;
+    use crate::shape::{
+        create_box_shape, create_physics_material, create_static_compound_shape, BoxShapeSettings,
+        StaticCompoundShapeSettings, SubShapeSettings,
+    };
+    use crate::system::global_initialize;
+
+    /// A compound of two boxes sharing one shape and one material.
+    fn create_settings() -> BodyCreationSettings {
+        global_initialize();
+        let material = create_physics_material("ice", Color::BLUE, 0.05f32);
+        let cube = create_box_shape(&BoxShapeSettings {
+            material: Some(material),
+            ..BoxShapeSettings::new(0.5, 0.5, 0.5)
+        })
+        .unwrap();
+        let compound = create_static_compound_shape(&StaticCompoundShapeSettings::new(&[
+            SubShapeSettings::new(cube.clone(), Vec3A::ZERO, Quat::IDENTITY),
+            SubShapeSettings::new(cube, Vec3A::new(2.0, 0.0, 0.0), Quat::IDENTITY),
+        ]))
+        .unwrap();
+        BodyCreationSettings::new(
+            compound.into(),
+            1,
+            MotionType::Dynamic,
+            Vec3A::new(1.0, 2.0, 3.0),
+            Quat::IDENTITY,
+        )
+    }
+
+    fn check_restored(restored: &BodyCreationSettings) {
+        assert_eq!(restored.position, Vec3A::new(1.0, 2.0, 3.0));
+        assert_eq!(restored.object_layer, 1);
+        assert_eq!(restored.motion_type, MotionType::Dynamic);
+
+        let shape = restored.shape.as_ref().unwrap();
+        assert_eq!(shape.get_sub_type(), ShapeSubType::StaticCompound);
+        let id0 = shape.encode_sub_shape_id(&[0]).unwrap();
+        let id1 = shape.encode_sub_shape_id(&[1]).unwrap();
+        let material = shape.get_material(id0).unwrap();
+        assert_eq!(material.get_debug_name(), "ice");
+        assert_eq!(material.get_debug_color(), Color::BLUE);
+        assert!(material.get_payload::<f32>().is_none());
+
+        // Both children get the same material, so the leaf is built once.
+        assert!(ptr::eq(material, shape.get_material(id1).unwrap()));
+        assert!(ptr::eq(
+            shape.get_leaf_shape(id0).unwrap().0,
+            shape.get_leaf_shape(id1).unwrap().0
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let settings = create_settings();
+        let json = serde_json::to_string(&settings).unwrap();
+        let restored: BodyCreationSettings = serde_json::from_str(&json).unwrap();
+        check_restored(&restored);
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_rkyv_round_trip() {
+        let settings = create_settings();
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&settings).unwrap();
+        let restored = rkyv::from_bytes::<BodyCreationSettings, rkyv::rancor::Error>(&bytes).unwrap();
+        check_restored(&restored);
+    }
+}
@@ -1,6 +1,28 @@
 #include "jolt-physics-rs/src/ffi.h"
 #include "jolt-physics-rs/src/shape.rs.h"
 
+JPH_IMPLEMENT_RTTI_VIRTUAL(XPhysicsMaterial) {
+	JPH_ADD_BASE_CLASS(XPhysicsMaterial, PhysicsMaterialSimple)
+}
+
+XPhysicsMaterial::~XPhysicsMaterial() {
+	if (this->_payload != nullptr) {
+		DropPhysicsMaterialPayload(this->_payload);
+	}
+}
+
+PhysicsMaterial* CreatePhysicsMaterial(rust::Str name, Color color, uint8* payload) {
+	PhysicsMaterial* material = new XPhysicsMaterial(string_view(name.data(), name.size()), color, payload);
+	return LeakRefT<PhysicsMaterial>(RefConst<PhysicsMaterial>(material));
+}
+
+const uint8* GetPhysicsMaterialPayload(const PhysicsMaterial& material) {
+	if (material.GetRTTI() != JPH_RTTI(XPhysicsMaterial)) {
+		return nullptr;
+	}
+	return static_cast<const XPhysicsMaterial&>(material).GetPayload();
+}
+
 struct XSphereShapeSettings {
 	uint64 userData;
 	RefConst<PhysicsMaterial> material;
@@ -430,3 +452,339 @@ MutableCompoundShape* CreateMutableCompoundShape(const XMutableCompoundShapeSett
 	}
 	return (MutableCompoundShape*)LeakRefT<Shape>(result.Get());
 }
+
+bool CastRayOnShape(const Shape& shape, Vec3 origin, Vec3 direction, float& fraction, SubShapeID& subShapeId) {
+	RayCast ray(origin, direction);
+	RayCastResult result;
+	if (!shape.CastRay(ray, SubShapeIDCreator(), result)) {
+		return false;
+	}
+	fraction = result.mFraction;
+	subShapeId = result.mSubShapeID2;
+	return true;
+}
+
+void CollidePointOnShape(const Shape& shape, Vec3 point, rust::Vec<SubShapeID>& hits) {
+	AllHitCollisionCollector<CollidePointCollector> collector;
+	shape.CollidePoint(point, SubShapeIDCreator(), collector);
+	for (const CollidePointResult& hit : collector.mHits) {
+		hits.push_back(hit.mSubShapeID2);
+	}
+}
+
+Vec3 GetShapeSupport(const Shape& shape, Vec3 direction, Vec3 scale) {
+	const ConvexShape& convex = static_cast<const ConvexShape&>(shape);
+	ConvexShape::SupportBuffer buffer;
+	const ConvexShape::Support* support = convex.GetSupportFunction(ConvexShape::ESupportMode::IncludeConvexRadius, buffer, scale);
+	return support->GetSupport(direction);
+}
+
+uint32 CountShapeMaterials(const Shape& shape) {
+	PhysicsMaterialList materials;
+	shape.SaveMaterialState(materials);
+	return (uint32)materials.size();
+}
+
+PhysicsMaterial* CloneShapeMaterial(const Shape& shape, uint32 index) {
+	PhysicsMaterialList materials;
+	shape.SaveMaterialState(materials);
+	if (index >= materials.size() || materials[index] == nullptr) {
+		return nullptr;
+	}
+	return LeakRefT<PhysicsMaterial>(materials[index]);
+}
+
+static void GetConvexShapeSettings(const ConvexShape& shape, uint64& userData, RefConst<PhysicsMaterial>& material, float& density) {
+	PhysicsMaterialList materials;
+	shape.SaveMaterialState(materials);
+	userData = shape.GetUserData();
+	material = materials.empty() ? nullptr : materials[0];
+	density = shape.GetDensity();
+}
+
+// Cylinders don't expose their convex radius, the support function without convex radius does
+static float GetSupportConvexRadius(const ConvexShape& shape) {
+	ConvexShape::SupportBuffer buffer;
+	return shape.GetSupportFunction(ConvexShape::ESupportMode::ExcludeConvexRadius, buffer, Vec3::sOne())->GetConvexRadius();
+}
+
+// Material indices refer to the materials saved by the shape
+static void GetShapeTriangles(const Shape& shape, TriangleList& triangles) {
+	PhysicsMaterialList materials;
+	shape.SaveMaterialState(materials);
+	Shape::GetTrianglesContext context;
+	shape.GetTrianglesStart(context, AABox::sBiggest(), shape.GetCenterOfMass(), Quat::sIdentity(), Vec3::sOne());
+	Float3 vertices[3 * Shape::cGetTrianglesMinTrianglesRequested];
+	const PhysicsMaterial* triangleMaterials[Shape::cGetTrianglesMinTrianglesRequested];
+	for (;;) {
+		int count = shape.GetTrianglesNext(context, Shape::cGetTrianglesMinTrianglesRequested, vertices, triangleMaterials);
+		if (count == 0) {
+			break;
+		}
+		for (int idx = 0; idx < count; ++idx) {
+			uint32 materialIndex = 0;
+			for (uint32 m = 0; m < materials.size(); ++m) {
+				if (materials[m].GetPtr() == triangleMaterials[idx]) {
+					materialIndex = m;
+					break;
+				}
+			}
+			triangles.push_back(Triangle(vertices[3 * idx], vertices[3 * idx + 1], vertices[3 * idx + 2], materialIndex));
+		}
+	}
+}
+
+bool GetSphereShapeSettings(const Shape& shape, XSphereShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::Sphere) {
+		return false;
+	}
+	const SphereShape& sphere = static_cast<const SphereShape&>(shape);
+	GetConvexShapeSettings(sphere, st.userData, st.material, st.density);
+	st.radius = sphere.GetRadius();
+	return true;
+}
+
+bool GetBoxShapeSettings(const Shape& shape, XBoxShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::Box) {
+		return false;
+	}
+	const BoxShape& box = static_cast<const BoxShape&>(shape);
+	GetConvexShapeSettings(box, st.userData, st.material, st.density);
+	Vec3 halfExtent = box.GetHalfExtent();
+	st.halfX = halfExtent.GetX();
+	st.halfY = halfExtent.GetY();
+	st.halfZ = halfExtent.GetZ();
+	st.convexRadius = box.GetConvexRadius();
+	return true;
+}
+
+bool GetCapsuleShapeSettings(const Shape& shape, XCapsuleShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::Capsule) {
+		return false;
+	}
+	const CapsuleShape& capsule = static_cast<const CapsuleShape&>(shape);
+	GetConvexShapeSettings(capsule, st.userData, st.material, st.density);
+	st.halfHeight = capsule.GetHalfHeightOfCylinder();
+	st.radius = capsule.GetRadius();
+	return true;
+}
+
+bool GetTaperedCapsuleShapeSettings(const Shape& shape, XTaperedCapsuleShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::TaperedCapsule) {
+		return false;
+	}
+	const TaperedCapsuleShape& capsule = static_cast<const TaperedCapsuleShape&>(shape);
+	GetConvexShapeSettings(capsule, st.userData, st.material, st.density);
+	st.halfHeight = capsule.GetHalfHeight();
+	st.topRadius = capsule.GetTopRadius();
+	st.bottomRadius = capsule.GetBottomRadius();
+	return true;
+}
+
+bool GetCylinderShapeSettings(const Shape& shape, XCylinderShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::Cylinder) {
+		return false;
+	}
+	const CylinderShape& cylinder = static_cast<const CylinderShape&>(shape);
+	GetConvexShapeSettings(cylinder, st.userData, st.material, st.density);
+	st.halfHeight = cylinder.GetHalfHeight();
+	st.radius = cylinder.GetRadius();
+	st.convexRadius = GetSupportConvexRadius(cylinder);
+	return true;
+}
+
+bool GetTaperedCylinderShapeSettings(const Shape& shape, XTaperedCylinderShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::TaperedCylinder) {
+		return false;
+	}
+	const TaperedCylinderShape& cylinder = static_cast<const TaperedCylinderShape&>(shape);
+	GetConvexShapeSettings(cylinder, st.userData, st.material, st.density);
+	st.halfHeight = cylinder.GetHalfHeight();
+	st.topRadius = cylinder.GetTopRadius();
+	st.bottomRadius = cylinder.GetBottomRadius();
+	st.convexRadius = GetSupportConvexRadius(cylinder);
+	return true;
+}
+
+bool GetConvexHullShapeSettings(const Shape& shape, XConvexHullShapeSettings& st, rust::Vec<Vec3>& points) {
+	if (shape.GetSubType() != EShapeSubType::ConvexHull) {
+		return false;
+	}
+	const ConvexHullShape& hull = static_cast<const ConvexHullShape&>(shape);
+	GetConvexShapeSettings(hull, st.userData, st.material, st.density);
+	st.maxConvexRadius = hull.GetConvexRadius();
+	points.reserve(hull.GetNumPoints());
+	for (uint idx = 0; idx < hull.GetNumPoints(); ++idx) {
+		points.push_back(hull.GetPoint(idx) + hull.GetCenterOfMass());
+	}
+	return true;
+}
+
+bool GetTriangleShapeSettings(const Shape& shape, XTriangleShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::Triangle) {
+		return false;
+	}
+	const TriangleShape& triangle = static_cast<const TriangleShape&>(shape);
+	GetConvexShapeSettings(triangle, st.userData, st.material, st.density);
+	st.convexRadius = triangle.GetConvexRadius();
+	st.v1 = triangle.GetVertex1();
+	st.v2 = triangle.GetVertex2();
+	st.v3 = triangle.GetVertex3();
+	return true;
+}
+
+bool GetPlaneShapeSettings(const Shape& shape, XPlaneShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::Plane) {
+		return false;
+	}
+	const PlaneShape& plane = static_cast<const PlaneShape&>(shape);
+	PhysicsMaterialList materials;
+	plane.SaveMaterialState(materials);
+	st.userData = plane.GetUserData();
+	st.material = materials.empty() ? nullptr : materials[0];
+	st.plane = plane.GetPlane();
+	st.halfExtent = plane.GetHalfExtent();
+	return true;
+}
+
+bool GetMeshShapeTriangles(const Shape& shape, rust::Vec<Float3>& vertices, rust::Vec<IndexedTriangle>& triangles) {
+	if (shape.GetSubType() != EShapeSubType::Mesh) {
+		return false;
+	}
+	TriangleList triangleList;
+	GetShapeTriangles(shape, triangleList);
+	// Welds the vertices again, active edges are calculated from shared vertices
+	VertexList vertexList;
+	IndexedTriangleList indexedList;
+	Indexify(triangleList, vertexList, indexedList);
+	vertices.reserve(vertexList.size());
+	for (const Float3& vertex : vertexList) {
+		vertices.push_back(vertex);
+	}
+	triangles.reserve(indexedList.size());
+	for (const IndexedTriangle& triangle : indexedList) {
+		triangles.push_back(triangle);
+	}
+	return true;
+}
+
+bool GetHeightFieldShapeSettings(const Shape& shape, XHeightFieldShapeSettings& st, rust::Vec<float>& heights, rust::Vec<uint8>& materialIndices) {
+	if (shape.GetSubType() != EShapeSubType::HeightField) {
+		return false;
+	}
+	const HeightFieldShape& heightField = static_cast<const HeightFieldShape&>(shape);
+	uint sampleCount = heightField.GetSampleCount();
+	st.userData = heightField.GetUserData();
+	st.sampleCount = sampleCount;
+	st.blockSize = heightField.GetBlockSize();
+
+	// Heights are read in local space, so only the horizontal offset and scale are kept
+	Vec3 origin = heightField.GetPosition(0, 0);
+	Vec3 step = heightField.GetPosition(1, 1) - origin;
+	st.offset = Vec3(origin.GetX(), 0.0f, origin.GetZ());
+	st.scale = Vec3(step.GetX(), 1.0f, step.GetZ());
+
+	Array<float> samples(sampleCount * sampleCount);
+	heightField.GetHeights(0, 0, sampleCount, sampleCount, samples.data(), sampleCount);
+	heights.reserve(samples.size());
+	for (float height : samples) {
+		heights.push_back(height);
+	}
+
+	if (CountShapeMaterials(heightField) > 0) {
+		uint materialCount = sampleCount - 1;
+		Array<uint8> indices(materialCount * materialCount);
+		heightField.GetMaterials(0, 0, materialCount, materialCount, indices.data(), materialCount);
+		materialIndices.reserve(indices.size());
+		for (uint8 index : indices) {
+			materialIndices.push_back(index);
+		}
+	}
+	return true;
+}
+
+bool GetEmptyShapeSettings(const Shape& shape, XEmptyShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::Empty) {
+		return false;
+	}
+	st.userData = shape.GetUserData();
+	st.centerOfMass = shape.GetCenterOfMass();
+	return true;
+}
+
+bool GetScaledShapeSettings(const Shape& shape, XScaledShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::Scaled) {
+		return false;
+	}
+	const ScaledShape& scaled = static_cast<const ScaledShape&>(shape);
+	st.userData = scaled.GetUserData();
+	st.innerShape = scaled.GetInnerShape();
+	st.scale = scaled.GetScale();
+	return true;
+}
+
+bool GetRotatedTranslatedShapeSettings(const Shape& shape, XRotatedTranslatedShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::RotatedTranslated) {
+		return false;
+	}
+	const RotatedTranslatedShape& rotated = static_cast<const RotatedTranslatedShape&>(shape);
+	st.userData = rotated.GetUserData();
+	st.innerShape = rotated.GetInnerShape();
+	st.position = rotated.GetPosition();
+	st.rotation = rotated.GetRotation();
+	return true;
+}
+
+bool GetOffsetCenterOfMassShapeSettings(const Shape& shape, XOffsetCenterOfMassShapeSettings& st) {
+	if (shape.GetSubType() != EShapeSubType::OffsetCenterOfMass) {
+		return false;
+	}
+	const OffsetCenterOfMassShape& offset = static_cast<const OffsetCenterOfMassShape&>(shape);
+	st.userData = offset.GetUserData();
+	st.innerShape = offset.GetInnerShape();
+	st.offset = offset.GetOffset();
+	return true;
+}
+
+static constexpr uint32 SHAPE_BINARY_MAGIC = 0x5048534A; // "JSHP"
+static constexpr uint32 SHAPE_BINARY_VERSION = 1;
+
+void SaveShapeBinary(const Shape& shape, rust::Vec<uint8>& data) {
+	StateRecorderImpl recorder;
+	recorder.Write(SHAPE_BINARY_MAGIC);
+	recorder.Write(SHAPE_BINARY_VERSION);
+	Shape::ShapeToIDMap shapeMap;
+	Shape::MaterialToIDMap materialMap;
+	shape.SaveWithChildren(recorder, shapeMap, materialMap);
+	StateRecorderToVec(recorder, data);
+}
+
+Shape* RestoreShapeBinary(rust::Slice<const uint8> data) {
+	StateRecorderImpl recorder;
+	StateRecorderFromSlice(recorder, data);
+	uint32 magic = 0;
+	uint32 version = 0;
+	recorder.Read(magic);
+	recorder.Read(version);
+	if (recorder.IsFailed() || magic != SHAPE_BINARY_MAGIC || version != SHAPE_BINARY_VERSION) {
+		return nullptr;
+	}
+
+	// Jolt looks up the shape registry with the sub type unchecked, at least validate the root shape
+	constexpr size_t subTypeOffset = 3 * sizeof(uint32); // magic, version, shape id
+	if (data.size() <= subTypeOffset) {
+		return nullptr;
+	}
+	uint8 subType = data[subTypeOffset];
+	if (subType >= NumSubShapeTypes || ShapeFunctions::sGet(EShapeSubType(subType)).mConstruct == nullptr) {
+		return nullptr;
+	}
+
+	Shape::IDToShapeMap shapeMap;
+	Shape::IDToMaterialMap materialMap;
+	Shape::ShapeResult result = Shape::sRestoreWithChildren(recorder, shapeMap, materialMap);
+	if (result.HasError() || recorder.IsFailed()) {
+		return nullptr;
+	}
+	return LeakRefT<Shape>(result.Get());
+}
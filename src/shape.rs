@@ -4,17 +4,30 @@ use glam::{Quat, Vec3, Vec3A};
 #[cfg(feature = "glam-ext")]
 use glam_ext::{Isometry3A, Transform3A};
 use static_assertions::const_assert_eq;
+use std::any::Any;
+use std::collections::HashMap;
+use std::ffi::CStr;
 use std::mem;
 use std::pin::Pin;
 use std::ptr::NonNull;
 
-use crate::base::{AABox, IndexedTriangle, JMut, JQuat, JRef, JRefTarget, JVec3, Plane, ShapeSubType, ShapeType};
+use crate::base::{
+    AABox, Color, Float3, IndexedTriangle, JMut, JQuat, JRef, JRefTarget, JVec3, Plane, ShapeSubType, ShapeType,
+    SubShapeID, SubShapeIDCreator,
+};
+use crate::body::MassProperties;
 use crate::consts::{DEFAULT_CONVEX_RADIUS, DEFAULT_ERROR_CONVEX_RADIUS, MAX_CONVEX_RADIUS, MIN_CONVEX_RADIUS};
 use crate::error::{JoltError, JoltResult};
+use crate::query::RayCastHit;
 use crate::JMutTarget;
 
 #[cxx::bridge()]
 pub(crate) mod ffi {
+    extern "Rust" {
+        #[cxx_name = "DropPhysicsMaterialPayload"]
+        unsafe fn drop_physics_material_payload(payload: *mut u8);
+    }
+
     unsafe extern "C++" {
         include!("rust/cxx.h");
         include!("jolt-physics-rs/src/ffi.h");
@@ -25,6 +38,11 @@ pub(crate) mod ffi {
         type Vec3 = crate::base::ffi::Vec3;
         type Quat = crate::base::ffi::Quat;
         type AABox = crate::base::ffi::AABox;
+        type Color = crate::base::ffi::Color;
+        type SubShapeID = crate::base::ffi::SubShapeID;
+        type Float3 = crate::base::ffi::Float3;
+        type IndexedTriangle = crate::base::ffi::IndexedTriangle;
+        type MassProperties = crate::body::MassProperties;
 
         type XSphereShapeSettings = crate::shape::SphereShapeSettings;
         type XBoxShapeSettings = crate::shape::BoxShapeSettings;
@@ -53,6 +71,10 @@ pub(crate) mod ffi {
         unsafe fn DropPhysicsMaterial(material: *mut PhysicsMaterial);
         unsafe fn ClonePhysicsMaterial(material: *mut PhysicsMaterial) -> *mut PhysicsMaterial;
         unsafe fn CountRefPhysicsMaterial(material: *const PhysicsMaterial) -> u32;
+        unsafe fn CreatePhysicsMaterial(name: &str, color: Color, payload: *mut u8) -> *mut PhysicsMaterial;
+        fn GetDebugName(self: &PhysicsMaterial) -> *const c_char;
+        fn GetDebugColor(self: &PhysicsMaterial) -> Color;
+        fn GetPhysicsMaterialPayload(material: &PhysicsMaterial) -> *const u8;
 
         type Shape;
         unsafe fn DropShape(shape: *mut Shape);
@@ -90,6 +112,53 @@ pub(crate) mod ffi {
         fn GetVolume(self: &Shape) -> f32;
         fn IsValidScale(self: &Shape, scale: Vec3) -> bool;
         fn MakeScaleValid(self: &Shape, scale: Vec3) -> Vec3;
+        fn GetDecoratedInnerShape(shape: &Shape) -> *const Shape;
+        fn GetMassProperties(self: &Shape) -> MassProperties;
+        fn SaveShapeBinary(shape: &Shape, data: &mut Vec<u8>);
+        fn RestoreShapeBinary(data: &[u8]) -> *mut Shape;
+        fn GetSurfaceNormal(self: &Shape, sub_shape_id: &SubShapeID, local_position: Vec3) -> Vec3;
+        fn CastRayOnShape(
+            shape: &Shape,
+            origin: Vec3,
+            direction: Vec3,
+            fraction: &mut f32,
+            sub_shape_id: &mut SubShapeID,
+        ) -> bool;
+        fn CollidePointOnShape(shape: &Shape, point: Vec3, hits: &mut Vec<SubShapeID>);
+        fn GetShapeSupport(shape: &Shape, direction: Vec3, scale: Vec3) -> Vec3;
+        fn CountShapeMaterials(shape: &Shape) -> u32;
+        fn CloneShapeMaterial(shape: &Shape, index: u32) -> *mut PhysicsMaterial;
+        fn GetSphereShapeSettings(shape: &Shape, settings: &mut XSphereShapeSettings) -> bool;
+        fn GetBoxShapeSettings(shape: &Shape, settings: &mut XBoxShapeSettings) -> bool;
+        fn GetCapsuleShapeSettings(shape: &Shape, settings: &mut XCapsuleShapeSettings) -> bool;
+        fn GetTaperedCapsuleShapeSettings(shape: &Shape, settings: &mut XTaperedCapsuleShapeSettings) -> bool;
+        fn GetCylinderShapeSettings(shape: &Shape, settings: &mut XCylinderShapeSettings) -> bool;
+        fn GetTaperedCylinderShapeSettings(shape: &Shape, settings: &mut XTaperedCylinderShapeSettings) -> bool;
+        fn GetConvexHullShapeSettings(
+            shape: &Shape,
+            settings: &mut XConvexHullShapeSettings,
+            points: &mut Vec<Vec3>,
+        ) -> bool;
+        fn GetTriangleShapeSettings(shape: &Shape, settings: &mut XTriangleShapeSettings) -> bool;
+        fn GetPlaneShapeSettings(shape: &Shape, settings: &mut XPlaneShapeSettings) -> bool;
+        fn GetMeshShapeTriangles(
+            shape: &Shape,
+            vertices: &mut Vec<Float3>,
+            triangles: &mut Vec<IndexedTriangle>,
+        ) -> bool;
+        fn GetHeightFieldShapeSettings(
+            shape: &Shape,
+            settings: &mut XHeightFieldShapeSettings,
+            heights: &mut Vec<f32>,
+            material_indices: &mut Vec<u8>,
+        ) -> bool;
+        fn GetEmptyShapeSettings(shape: &Shape, settings: &mut XEmptyShapeSettings) -> bool;
+        fn GetScaledShapeSettings(shape: &Shape, settings: &mut XScaledShapeSettings) -> bool;
+        fn GetRotatedTranslatedShapeSettings(shape: &Shape, settings: &mut XRotatedTranslatedShapeSettings) -> bool;
+        fn GetOffsetCenterOfMassShapeSettings(shape: &Shape, settings: &mut XOffsetCenterOfMassShapeSettings) -> bool;
+        fn GetMaterial(self: &Shape, sub_shape_id: &SubShapeID) -> *const PhysicsMaterial;
+        fn GetLeafShape(self: &Shape, sub_shape_id: &SubShapeID, remainder: &mut SubShapeID) -> *const Shape;
+        fn GetSubShapeIDBitsRecursive(self: &Shape) -> u32;
 
         type StaticCompoundShape;
         unsafe fn DropStaticCompoundShape(shape: *mut StaticCompoundShape);
@@ -107,6 +176,12 @@ pub(crate) mod ffi {
         fn GetVolume(self: &StaticCompoundShape) -> f32;
         fn IsValidScale(self: &StaticCompoundShape, scale: Vec3) -> bool;
         fn MakeScaleValid(self: &StaticCompoundShape, scale: Vec3) -> Vec3;
+        fn GetMaterial(self: &StaticCompoundShape, sub_shape_id: &SubShapeID) -> *const PhysicsMaterial;
+        fn GetLeafShape(
+            self: &StaticCompoundShape,
+            sub_shape_id: &SubShapeID,
+            remainder: &mut SubShapeID,
+        ) -> *const Shape;
         fn GetNumSubShapes(self: &StaticCompoundShape) -> u32;
         unsafe fn GetSubShape(self: &StaticCompoundShape, index: u32) -> &XCompoundSubShape;
         fn GetCompoundUserData(self: &StaticCompoundShape, idx: u32) -> u32;
@@ -128,6 +203,12 @@ pub(crate) mod ffi {
         fn GetVolume(self: &MutableCompoundShape) -> f32;
         fn IsValidScale(self: &MutableCompoundShape, scale: Vec3) -> bool;
         fn MakeScaleValid(self: &MutableCompoundShape, scale: Vec3) -> Vec3;
+        fn GetMaterial(self: &MutableCompoundShape, sub_shape_id: &SubShapeID) -> *const PhysicsMaterial;
+        fn GetLeafShape(
+            self: &MutableCompoundShape,
+            sub_shape_id: &SubShapeID,
+            remainder: &mut SubShapeID,
+        ) -> *const Shape;
         fn GetNumSubShapes(self: &MutableCompoundShape) -> u32;
         unsafe fn GetSubShape(self: &MutableCompoundShape, index: u32) -> &XCompoundSubShape;
         fn GetCompoundUserData(self: &MutableCompoundShape, idx: u32) -> u32;
@@ -165,8 +246,12 @@ pub(crate) mod ffi {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SphereShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::MaterialDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_material"))]
     pub material: Option<JRef<PhysicsMaterial>>,
     pub density: f32,
     pub radius: f32,
@@ -200,8 +285,12 @@ impl SphereShapeSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoxShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::MaterialDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_material"))]
     pub material: Option<JRef<PhysicsMaterial>>,
     pub density: f32,
     pub half_x: f32,
@@ -245,8 +334,12 @@ impl BoxShapeSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapsuleShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::MaterialDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_material"))]
     pub material: Option<JRef<PhysicsMaterial>>,
     pub density: f32,
     pub half_height: f32,
@@ -283,8 +376,12 @@ impl CapsuleShapeSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaperedCapsuleShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::MaterialDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_material"))]
     pub material: Option<JRef<PhysicsMaterial>>,
     pub density: f32,
     pub half_height: f32,
@@ -324,8 +421,12 @@ impl TaperedCapsuleShapeSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CylinderShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::MaterialDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_material"))]
     pub material: Option<JRef<PhysicsMaterial>>,
     pub density: f32,
     pub half_height: f32,
@@ -365,8 +466,12 @@ impl CylinderShapeSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaperedCylinderShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::MaterialDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_material"))]
     pub material: Option<JRef<PhysicsMaterial>>,
     pub density: f32,
     pub half_height: f32,
@@ -407,10 +512,17 @@ impl TaperedCylinderShapeSettings {
     }
 }
 
+/// Borrows its data, so it only implements `serde::Serialize`.
+/// Use the owned `ConvexHullShapeDesc` to round-trip through serde or rkyv.
 #[repr(C)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ConvexHullShapeSettings<'t> {
     pub user_data: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::serialize::serde_material::serialize")
+    )]
     pub material: Option<JRef<PhysicsMaterial>>,
     pub density: f32,
     pub points: &'t [Vec3A],
@@ -449,9 +561,13 @@ impl<'t> ConvexHullShapeSettings<'t> {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TriangleShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::MaterialDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_material"))]
     pub material: Option<JRef<PhysicsMaterial>>,
     pub density: f32,
     pub convex_radius: f32,
@@ -492,9 +608,13 @@ impl TriangleShapeSettings {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlaneShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::MaterialDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_material"))]
     pub material: Option<JRef<PhysicsMaterial>>,
     pub plane: Plane,
     pub half_extent: f32,
@@ -527,12 +647,19 @@ impl PlaneShapeSettings {
     }
 }
 
+/// Borrows its data, so it only implements `serde::Serialize`.
+/// Use the owned `MeshShapeDesc` to round-trip through serde or rkyv.
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MeshShapeSettings<'t> {
     pub user_data: u64,
     pub triangle_vertices: &'t [Vec3],
     pub indexed_triangles: &'t [IndexedTriangle],
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::serialize::serde_materials::serialize")
+    )]
     pub materials: &'t [JRef<PhysicsMaterial>],
     pub max_triangles_per_leaf: u32,
     pub active_edge_cos_threshold_angle: f32,
@@ -567,8 +694,11 @@ impl<'t> MeshShapeSettings<'t> {
     }
 }
 
+/// Borrows its data, so it only implements `serde::Serialize`.
+/// Use the owned `HeightFieldShapeDesc` to round-trip through serde or rkyv.
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct HeightFieldShapeSettings<'t> {
     pub user_data: u64,
     pub offset: Vec3A,
@@ -580,6 +710,10 @@ pub struct HeightFieldShapeSettings<'t> {
     pub bits_per_sample: u32,
     pub height_samples: &'t [f32],
     pub material_indices: &'t [u8],
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::serialize::serde_materials::serialize")
+    )]
     pub materials: &'t [JRef<PhysicsMaterial>],
     pub active_edge_cos_threshold_angle: f32,
 }
@@ -620,7 +754,9 @@ impl<'t> HeightFieldShapeSettings<'t> {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EmptyShapeSettings {
     pub user_data: u64,
     pub center_of_mass: Vec3A,
@@ -652,8 +788,12 @@ impl EmptyShapeSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScaledShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::ShapeDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_shape"))]
     pub inner_shape: Option<JRef<Shape>>,
     pub scale: Vec3A,
 }
@@ -686,8 +826,12 @@ impl ScaledShapeSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RotatedTranslatedShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::ShapeDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_shape"))]
     pub inner_shape: Option<JRef<Shape>>,
     pub position: Vec3A,
     pub rotation: Quat,
@@ -723,8 +867,12 @@ impl RotatedTranslatedShapeSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OffsetCenterOfMassShapeSettings {
     pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::ShapeDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_shape"))]
     pub inner_shape: Option<JRef<Shape>>,
     pub offset: Vec3A,
 }
@@ -757,8 +905,14 @@ impl OffsetCenterOfMassShapeSettings {
 
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SubShapeSettings {
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::SkipPtr))]
+    #[cfg_attr(feature = "serde", serde(skip, default = "std::ptr::null"))]
     _shape: *const (),
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::ShapeDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_shape"))]
     pub shape: Option<JRef<Shape>>,
     pub position: Vec3A,
     pub rotation: Quat,
@@ -795,8 +949,11 @@ impl SubShapeSettings {
     }
 }
 
+/// Borrows its data, so it only implements `serde::Serialize`.
+/// Use the owned `CompoundShapeDesc` to round-trip through serde or rkyv.
 #[repr(C)]
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StaticCompoundShapeSettings<'t> {
     pub user_data: u64,
     pub sub_shapes: &'t [SubShapeSettings],
@@ -817,8 +974,11 @@ impl StaticCompoundShapeSettings<'_> {
     }
 }
 
+/// Borrows its data, so it only implements `serde::Serialize`.
+/// Use the owned `CompoundShapeDesc` to round-trip through serde or rkyv.
 #[repr(C)]
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MutableCompoundShapeSettings<'t> {
     pub user_data: u64,
     pub sub_shapes: &'t [SubShapeSettings],
@@ -1188,6 +1348,21 @@ pub fn create_mutable_compound_shape_mut(
     }
 }
 
+type MaterialPayload = Box<dyn Any + Send + Sync>;
+
+unsafe fn drop_physics_material_payload(payload: *mut u8) {
+    let _ = Box::from_raw(payload as *mut MaterialPayload);
+}
+
+/// Creates a material carrying an arbitrary payload, e.g. friction or a surface sound id.
+pub fn create_physics_material<P: Any + Send + Sync>(name: &str, color: Color, payload: P) -> JRef<PhysicsMaterial> {
+    let payload: Box<MaterialPayload> = Box::new(Box::new(payload));
+    unsafe {
+        let ptr = ffi::CreatePhysicsMaterial(name, color, Box::into_raw(payload) as *mut u8);
+        JRef(NonNull::new_unchecked(ptr as *mut PhysicsMaterial))
+    }
+}
+
 pub struct PhysicsMaterial(ffi::PhysicsMaterial);
 
 const_assert_eq!(mem::size_of::<JRef<PhysicsMaterial>>(), mem::size_of::<usize>());
@@ -1199,7 +1374,10 @@ const_assert_eq!(
 
 impl fmt::Debug for PhysicsMaterial {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("PhysicsMaterial").finish()
+        f.debug_struct("PhysicsMaterial")
+            .field("debug_name", &self.get_debug_name())
+            .field("debug_color", &self.get_debug_color())
+            .finish()
     }
 }
 
@@ -1237,6 +1415,42 @@ impl PhysicsMaterial {
     pub(crate) unsafe fn cast_ptr(p: *const ffi::PhysicsMaterial) -> *const PhysicsMaterial {
         p as *const PhysicsMaterial
     }
+
+    #[cfg(any(feature = "serde", feature = "rkyv"))]
+    #[inline]
+    pub(crate) fn new_simple(name: &str, color: Color) -> JRef<PhysicsMaterial> {
+        unsafe {
+            let ptr = ffi::CreatePhysicsMaterial(name, color, std::ptr::null_mut());
+            JRef(NonNull::new_unchecked(ptr as *mut PhysicsMaterial))
+        }
+    }
+
+    #[inline]
+    fn as_ref(&self) -> &ffi::PhysicsMaterial {
+        &self.0
+    }
+
+    #[inline]
+    pub fn get_debug_name(&self) -> &str {
+        unsafe { CStr::from_ptr(self.as_ref().GetDebugName()) }
+            .to_str()
+            .unwrap_or("")
+    }
+
+    #[inline]
+    pub fn get_debug_color(&self) -> Color {
+        self.as_ref().GetDebugColor()
+    }
+
+    /// Returns None for materials not created by `create_physics_material` or with another payload type.
+    #[inline]
+    pub fn get_payload<P: Any>(&self) -> Option<&P> {
+        let payload = ffi::GetPhysicsMaterialPayload(self.as_ref());
+        match payload.is_null() {
+            false => unsafe { &*(payload as *const MaterialPayload) }.downcast_ref::<P>(),
+            true => None,
+        }
+    }
 }
 
 pub struct Shape(pub(crate) ffi::Shape);
@@ -1377,6 +1591,41 @@ macro_rules! shape_methods {
             pub fn make_scale_valid(&self, scale: Vec3A) -> Vec3A {
                 self.as_ref().MakeScaleValid(scale.into()).into()
             }
+
+            #[allow(clippy::unnecessary_cast)]
+            #[inline]
+            fn as_shape(&self) -> &Shape {
+                unsafe { &*(self as *const Self as *const Shape) }
+            }
+
+            /// None if `sub_shape_id` doesn't belong to this shape.
+            #[inline]
+            pub fn get_material(&self, sub_shape_id: SubShapeID) -> Option<&PhysicsMaterial> {
+                if !self.as_shape().is_valid_sub_shape_id(sub_shape_id) {
+                    return None;
+                }
+                let material = self.as_ref().GetMaterial(&sub_shape_id);
+                unsafe { PhysicsMaterial::cast_ptr(material).as_ref() }
+            }
+
+            /// The `user_data` of the compound child holding the leaf, see `get_compound_user_data`.
+            /// None outside compounds or if `sub_shape_id` doesn't belong to this shape.
+            #[inline]
+            pub fn get_sub_shape_user_data(&self, sub_shape_id: SubShapeID) -> Option<u32> {
+                let (child, _, _) = self.as_shape().walk_sub_shape_id(sub_shape_id, |_| {})?;
+                child.map(|child| child.user_data)
+            }
+
+            /// Returns the leaf shape and the remainder of the sub shape id that is relative to the leaf.
+            #[inline]
+            pub fn get_leaf_shape(&self, sub_shape_id: SubShapeID) -> Option<(&Shape, SubShapeID)> {
+                if !self.as_shape().is_valid_sub_shape_id(sub_shape_id) {
+                    return None;
+                }
+                let mut remainder = SubShapeID::EMPTY;
+                let leaf = self.as_ref().GetLeafShape(&sub_shape_id, &mut remainder);
+                unsafe { Shape::cast_ptr(leaf).as_ref() }.map(|leaf| (leaf, remainder))
+            }
         }
     };
 }
@@ -1388,6 +1637,209 @@ impl Shape {
     pub(crate) unsafe fn cast_ptr(p: *const ffi::Shape) -> *const Shape {
         p as *const Shape
     }
+
+    /// Saves the shape with its sub shapes and materials, shared ones are written once.
+    /// Payloads of materials from `create_physics_material` are not saved.
+    #[inline]
+    pub fn save_binary(&self, data: &mut Vec<u8>) {
+        ffi::SaveShapeBinary(self.as_ref(), data);
+    }
+
+    /// Only the header and the root sub type are validated. Jolt reads the rest of the stream unchecked
+    /// (child sub types, mesh and height field trees, shape and material ids).
+    ///
+    /// # Safety
+    ///
+    /// `data` must come from `save_binary`, corrupted or hostile data is undefined behavior.
+    #[inline]
+    pub unsafe fn restore_binary(data: &[u8]) -> JoltResult<JRef<Shape>> {
+        let ptr = ffi::RestoreShapeBinary(data);
+        if ptr.is_null() {
+            return Err(JoltError::RestoreShape);
+        }
+        Ok(unsafe { JRef::<Shape>::new_unchecked(ptr) })
+    }
+
+    #[inline]
+    pub fn get_mass_properties(&self) -> MassProperties {
+        self.as_ref().GetMassProperties()
+    }
+
+    /// `local_position` is in the local space of the shape (not relative to the center of mass), usually a hit position.
+    /// None if `sub_shape_id` doesn't belong to this shape.
+    #[inline]
+    pub fn get_surface_normal(&self, sub_shape_id: SubShapeID, local_position: Vec3A) -> Option<Vec3A> {
+        if !self.is_valid_sub_shape_id(sub_shape_id) {
+            return None;
+        }
+        let position = local_position - self.get_center_of_mass();
+        Some(self.as_ref().GetSurfaceNormal(&sub_shape_id, position.into()).into())
+    }
+
+    /// Casts a ray from `origin` to `origin + direction` in the local space of the shape.
+    /// The `body_id` of the hit is always invalid.
+    pub fn cast_ray(&self, origin: Vec3A, direction: Vec3A) -> Option<RayCastHit> {
+        let mut hit = RayCastHit::default();
+        // Jolt shapes are queried relative to their center of mass
+        if !ffi::CastRayOnShape(
+            self.as_ref(),
+            (origin - self.get_center_of_mass()).into(),
+            direction.into(),
+            &mut hit.fraction,
+            &mut hit.sub_shape_id,
+        ) {
+            return None;
+        }
+        hit.position = origin + direction * hit.fraction;
+        hit.normal = self.get_surface_normal(hit.sub_shape_id, hit.position)?;
+        Some(hit)
+    }
+
+    /// Returns the sub shapes containing `point`, which is in the local space of the shape.
+    pub fn collide_point(&self, point: Vec3A) -> Vec<SubShapeID> {
+        let mut hits = Vec::new();
+        let point = point - self.get_center_of_mass();
+        ffi::CollidePointOnShape(self.as_ref(), point.into(), &mut hits);
+        hits
+    }
+
+    /// Returns the furthest point (convex radius included) along `direction` in the scaled local space of the shape.
+    /// None for non-convex shapes.
+    #[inline]
+    pub fn get_support(&self, direction: Vec3A, scale: Vec3A) -> Option<Vec3A> {
+        match self.get_type() {
+            ShapeType::Convex => {
+                let support: Vec3A = ffi::GetShapeSupport(self.as_ref(), direction.into(), scale.into()).into();
+                Some(support + self.get_center_of_mass() * scale)
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn as_compound(&self) -> Option<CompoundRef<'_>> {
+        match self.get_sub_type() {
+            ShapeSubType::StaticCompound => Some(CompoundRef::Static(unsafe {
+                &*(self as *const Shape as *const StaticCompoundShape)
+            })),
+            ShapeSubType::MutableCompound => Some(CompoundRef::Mutable(unsafe {
+                &*(self as *const Shape as *const MutableCompoundShape)
+            })),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn get_decorated_inner_shape(&self) -> Option<&Shape> {
+        unsafe { Shape::cast_ptr(ffi::GetDecoratedInnerShape(self.as_ref())).as_ref() }
+    }
+
+    /// Checks the compound child indices of `sub_shape_id` and that the leaf remainder fits the leaf.
+    /// Triangle and height field sample bits are only checked for their width.
+    pub fn is_valid_sub_shape_id(&self, sub_shape_id: SubShapeID) -> bool {
+        self.walk_sub_shape_id(sub_shape_id, |_| {}).is_some()
+    }
+
+    /// Pops `sub_shape_id` the way Jolt does, returns the innermost compound child, the leaf and its remainder.
+    /// `visit` receives the child index of every compound level.
+    fn walk_sub_shape_id(
+        &self,
+        sub_shape_id: SubShapeID,
+        mut visit: impl FnMut(u32),
+    ) -> Option<(Option<&CompoundSubShape>, &Shape, SubShapeID)> {
+        let mut child = None;
+        let mut shape = self;
+        let mut remainder = sub_shape_id;
+        loop {
+            if let Some(compound) = shape.as_compound() {
+                let (idx, next) = remainder.pop_id(compound.get_sub_shape_id_bits());
+                if idx >= compound.get_num_sub_shapes() {
+                    return None;
+                }
+                visit(idx);
+                let sub_shape = compound.get_sub_shape(idx);
+                child = Some(sub_shape);
+                shape = sub_shape.shape.as_ref();
+                remainder = next;
+            } else if let Some(inner) = shape.get_decorated_inner_shape() {
+                shape = inner;
+            } else {
+                let (_, rest) = remainder.pop_id(shape.as_ref().GetSubShapeIDBitsRecursive());
+                return match rest.is_empty() {
+                    true => Some((child, shape, remainder)),
+                    false => None,
+                };
+            }
+        }
+    }
+
+    /// Walks the compound hierarchy (skipping decorated shapes), returns the child indices and the leaf remainder.
+    /// Every compound level pops its index, also when it has a single child (0 bits) or the bits are all ones.
+    /// None if an index is out of range or the remainder doesn't fit the leaf, see `is_valid_sub_shape_id`.
+    pub fn decode_sub_shape_id(&self, sub_shape_id: SubShapeID) -> Option<(Vec<u32>, SubShapeID)> {
+        let mut path = Vec::new();
+        let (_, _, remainder) = self.walk_sub_shape_id(sub_shape_id, |idx| path.push(idx))?;
+        Some((path, remainder))
+    }
+
+    /// Builds the sub shape id of a compound child path, the inverse of `decode_sub_shape_id`.
+    /// The path needs one index per compound level down to the leaf, returns None if it doesn't match the hierarchy.
+    pub fn encode_sub_shape_id(&self, path: &[u32]) -> Option<SubShapeID> {
+        let mut creator = SubShapeIDCreator::new();
+        let mut shape = self;
+        let mut path = path.iter();
+        loop {
+            if let Some(compound) = shape.as_compound() {
+                let idx = *path.next()?;
+                if idx >= compound.get_num_sub_shapes() {
+                    return None;
+                }
+                creator = creator.push_id(idx, compound.get_sub_shape_id_bits());
+                shape = compound.get_sub_shape(idx).shape.as_ref();
+            } else if let Some(inner) = shape.get_decorated_inner_shape() {
+                shape = inner;
+            } else {
+                break;
+            }
+        }
+        match path.next() {
+            Some(_) => None,
+            None => Some(creator.get_id()),
+        }
+    }
+}
+
+#[inline]
+fn sub_shape_id_bits(num_sub_shapes: u32) -> u32 {
+    u32::BITS - num_sub_shapes.saturating_sub(1).leading_zeros()
+}
+
+enum CompoundRef<'t> {
+    Static(&'t StaticCompoundShape),
+    Mutable(&'t MutableCompoundShape),
+}
+
+impl<'t> CompoundRef<'t> {
+    #[inline]
+    fn get_num_sub_shapes(&self) -> u32 {
+        match self {
+            CompoundRef::Static(shape) => shape.get_num_sub_shapes(),
+            CompoundRef::Mutable(shape) => shape.get_num_sub_shapes(),
+        }
+    }
+
+    #[inline]
+    fn get_sub_shape_id_bits(&self) -> u32 {
+        sub_shape_id_bits(self.get_num_sub_shapes())
+    }
+
+    #[inline]
+    fn get_sub_shape(&self, idx: u32) -> &'t CompoundSubShape {
+        match self {
+            CompoundRef::Static(shape) => shape.get_sub_shape(idx),
+            CompoundRef::Mutable(shape) => shape.get_sub_shape(idx),
+        }
+    }
 }
 
 pub struct StaticCompoundShape(pub(crate) ffi::StaticCompoundShape);
@@ -1477,6 +1929,17 @@ impl StaticCompoundShape {
         self.as_ref().GetNumSubShapes()
     }
 
+    #[inline]
+    pub fn get_sub_shape_id_bits(&self) -> u32 {
+        sub_shape_id_bits(self.get_num_sub_shapes())
+    }
+
+    /// Returns the child index encoded in `sub_shape_id` and the remainder for that child.
+    #[inline]
+    pub fn get_sub_shape_index_from_id(&self, sub_shape_id: SubShapeID) -> (u32, SubShapeID) {
+        sub_shape_id.pop_id(self.get_sub_shape_id_bits())
+    }
+
     #[inline]
     pub fn get_sub_shape(&self, idx: u32) -> &CompoundSubShape {
         unsafe { self.as_ref().GetSubShape(idx) }
@@ -1580,6 +2043,17 @@ impl MutableCompoundShape {
         self.as_ref().GetNumSubShapes()
     }
 
+    #[inline]
+    pub fn get_sub_shape_id_bits(&self) -> u32 {
+        sub_shape_id_bits(self.get_num_sub_shapes())
+    }
+
+    /// Returns the child index encoded in `sub_shape_id` and the remainder for that child.
+    #[inline]
+    pub fn get_sub_shape_index_from_id(&self, sub_shape_id: SubShapeID) -> (u32, SubShapeID) {
+        sub_shape_id.pop_id(self.get_sub_shape_id_bits())
+    }
+
     #[inline]
     pub fn get_sub_shape(&self, idx: u32) -> &CompoundSubShape {
         unsafe { self.as_ref().GetSubShape(idx) }
@@ -1691,3 +2165,1127 @@ impl MutableCompoundShape {
         );
     }
 }
+
+//
+// ShapeDesc
+//
+
+/// Owned description of a shape tree, see `ShapeDesc::build`.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(
+        serialize_bounds(__S: rkyv::ser::Writer + rkyv::ser::Allocator, __S::Error: rkyv::rancor::Source),
+        deserialize_bounds(__D::Error: rkyv::rancor::Source),
+        bytecheck(bounds(__C: rkyv::validation::ArchiveContext, __C::Error: rkyv::rancor::Source))
+    )
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShapeDesc {
+    Sphere(SphereShapeSettings),
+    Box(BoxShapeSettings),
+    Capsule(CapsuleShapeSettings),
+    TaperedCapsule(TaperedCapsuleShapeSettings),
+    Cylinder(CylinderShapeSettings),
+    TaperedCylinder(TaperedCylinderShapeSettings),
+    ConvexHull(ConvexHullShapeDesc),
+    Triangle(TriangleShapeSettings),
+    Plane(PlaneShapeSettings),
+    Mesh(MeshShapeDesc),
+    HeightField(HeightFieldShapeDesc),
+    Empty(EmptyShapeSettings),
+    Scaled(#[cfg_attr(feature = "rkyv", rkyv(omit_bounds))] ScaledShapeDesc),
+    RotatedTranslated(#[cfg_attr(feature = "rkyv", rkyv(omit_bounds))] RotatedTranslatedShapeDesc),
+    OffsetCenterOfMass(#[cfg_attr(feature = "rkyv", rkyv(omit_bounds))] OffsetCenterOfMassShapeDesc),
+    StaticCompound(#[cfg_attr(feature = "rkyv", rkyv(omit_bounds))] CompoundShapeDesc),
+    MutableCompound(#[cfg_attr(feature = "rkyv", rkyv(omit_bounds))] CompoundShapeDesc),
+}
+
+impl ShapeDesc {
+    /// Builds the whole tree, identical leaves are created once and shared.
+    pub fn build(&self) -> JoltResult<JRef<Shape>> {
+        let mut leaves = HashMap::new();
+        self.build_with(&mut leaves)
+    }
+
+    #[inline]
+    pub fn is_leaf(&self) -> bool {
+        !matches!(
+            self,
+            ShapeDesc::Scaled(_)
+                | ShapeDesc::RotatedTranslated(_)
+                | ShapeDesc::OffsetCenterOfMass(_)
+                | ShapeDesc::StaticCompound(_)
+                | ShapeDesc::MutableCompound(_)
+        )
+    }
+
+    fn build_with(&self, leaves: &mut HashMap<LeafKey, JRef<Shape>>) -> JoltResult<JRef<Shape>> {
+        let key = self.leaf_key();
+        if let Some(shape) = key.as_ref().and_then(|key| leaves.get(key)) {
+            return Ok(shape.clone());
+        }
+
+        let shape = match self {
+            ShapeDesc::Sphere(settings) => create_sphere_shape(settings)?,
+            ShapeDesc::Box(settings) => create_box_shape(settings)?,
+            ShapeDesc::Capsule(settings) => create_capsule_shape(settings)?,
+            ShapeDesc::TaperedCapsule(settings) => create_tapered_capsule_shape(settings)?,
+            ShapeDesc::Cylinder(settings) => create_cylinder_shape(settings)?,
+            ShapeDesc::TaperedCylinder(settings) => create_tapered_cylinder_shape(settings)?,
+            ShapeDesc::ConvexHull(desc) => create_convex_hull_shape(&desc.as_settings())?,
+            ShapeDesc::Triangle(settings) => create_triangle_shape(settings)?,
+            ShapeDesc::Plane(settings) => create_plane_shape(settings)?,
+            ShapeDesc::Mesh(desc) => create_mesh_shape(&desc.as_settings())?,
+            ShapeDesc::HeightField(desc) => create_height_field_shape(&desc.as_settings())?,
+            ShapeDesc::Empty(settings) => create_empty_shape(settings)?,
+            ShapeDesc::Scaled(desc) => create_scaled_shape(&ScaledShapeSettings {
+                user_data: desc.user_data,
+                inner_shape: Some(desc.inner_shape.build_with(leaves)?),
+                scale: desc.scale,
+            })?,
+            ShapeDesc::RotatedTranslated(desc) => create_rotated_translated_shape(&RotatedTranslatedShapeSettings {
+                user_data: desc.user_data,
+                inner_shape: Some(desc.inner_shape.build_with(leaves)?),
+                position: desc.position,
+                rotation: desc.rotation,
+            })?,
+            ShapeDesc::OffsetCenterOfMass(desc) => {
+                create_offset_center_of_mass_shape(&OffsetCenterOfMassShapeSettings {
+                    user_data: desc.user_data,
+                    inner_shape: Some(desc.inner_shape.build_with(leaves)?),
+                    offset: desc.offset,
+                })?
+            }
+            ShapeDesc::StaticCompound(desc) => create_static_compound_shape(&StaticCompoundShapeSettings {
+                user_data: desc.user_data,
+                sub_shapes: &desc.build_sub_shapes(leaves)?,
+            })?
+            .into(),
+            ShapeDesc::MutableCompound(desc) => create_mutable_compound_shape(&MutableCompoundShapeSettings {
+                user_data: desc.user_data,
+                sub_shapes: &desc.build_sub_shapes(leaves)?,
+            })?
+            .into(),
+        };
+
+        if let Some(key) = key {
+            leaves.insert(key, shape.clone());
+        }
+        Ok(shape)
+    }
+
+    /// Bit exact key of a leaf, None for decorated and compound shapes.
+    fn leaf_key(&self) -> Option<LeafKey> {
+        let mut key = LeafKey::default();
+        match self {
+            ShapeDesc::Sphere(st) => key
+                .u32(0)
+                .u64(st.user_data)
+                .material(&st.material)
+                .f32s(&[st.density, st.radius]),
+            ShapeDesc::Box(st) => key.u32(1).u64(st.user_data).material(&st.material).f32s(&[
+                st.density,
+                st.half_x,
+                st.half_y,
+                st.half_z,
+                st.convex_radius,
+            ]),
+            ShapeDesc::Capsule(st) => {
+                key.u32(2)
+                    .u64(st.user_data)
+                    .material(&st.material)
+                    .f32s(&[st.density, st.half_height, st.radius])
+            }
+            ShapeDesc::TaperedCapsule(st) => key.u32(3).u64(st.user_data).material(&st.material).f32s(&[
+                st.density,
+                st.half_height,
+                st.top_radius,
+                st.bottom_radius,
+            ]),
+            ShapeDesc::Cylinder(st) => key.u32(4).u64(st.user_data).material(&st.material).f32s(&[
+                st.density,
+                st.half_height,
+                st.radius,
+                st.convex_radius,
+            ]),
+            ShapeDesc::TaperedCylinder(st) => key.u32(5).u64(st.user_data).material(&st.material).f32s(&[
+                st.density,
+                st.half_height,
+                st.top_radius,
+                st.bottom_radius,
+                st.convex_radius,
+            ]),
+            ShapeDesc::ConvexHull(desc) => key
+                .u32(6)
+                .u64(desc.user_data)
+                .material(&desc.material)
+                .f32s(&[
+                    desc.density,
+                    desc.max_convex_radius,
+                    desc.max_error_convex_radius,
+                    desc.hull_tolerance,
+                ])
+                .f32s(&desc.points.iter().flat_map(|p| p.to_array()).collect::<Vec<_>>()),
+            ShapeDesc::Triangle(st) => key
+                .u32(7)
+                .u64(st.user_data)
+                .material(&st.material)
+                .f32s(&[st.density, st.convex_radius])
+                .f32s(&[st.v1.to_array(), st.v2.to_array(), st.v3.to_array()].concat()),
+            ShapeDesc::Plane(st) => key.u32(8).u64(st.user_data).material(&st.material).f32s(&[
+                st.plane.normal.x,
+                st.plane.normal.y,
+                st.plane.normal.z,
+                st.plane.distance,
+                st.half_extent,
+            ]),
+            ShapeDesc::Mesh(desc) => key
+                .u32(9)
+                .u64(desc.user_data)
+                .materials(&desc.materials)
+                .f32s(
+                    &desc
+                        .triangle_vertices
+                        .iter()
+                        .flat_map(|v| v.to_array())
+                        .collect::<Vec<_>>(),
+                )
+                .u32s(
+                    &desc
+                        .indexed_triangles
+                        .iter()
+                        .flat_map(|t| [t.idx[0], t.idx[1], t.idx[2], t.material_index, t.user_data])
+                        .collect::<Vec<_>>(),
+                )
+                .u32(desc.max_triangles_per_leaf)
+                .f32s(&[desc.active_edge_cos_threshold_angle]),
+            ShapeDesc::HeightField(desc) => key
+                .u32(10)
+                .u64(desc.user_data)
+                .materials(&desc.materials)
+                .f32s(&[desc.offset.to_array(), desc.scale.to_array()].concat())
+                .u32s(&[desc.sample_count, desc.block_size, desc.bits_per_sample])
+                .f32s(&[
+                    desc.min_height_value,
+                    desc.max_height_value,
+                    desc.active_edge_cos_threshold_angle,
+                ])
+                .f32s(&desc.height_samples)
+                .u32s(&desc.material_indices.iter().map(|idx| *idx as u32).collect::<Vec<_>>()),
+            ShapeDesc::Empty(st) => key.u32(11).u64(st.user_data).f32s(&st.center_of_mass.to_array()),
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    /// Describes an existing shape tree, fails for soft body and user shapes.
+    /// Settings Jolt doesn't keep fall back to their defaults: convex hull building tolerances, mesh triangle user
+    /// data, height field quantization and active edge thresholds. Mesh and height field materials are kept.
+    pub fn from_shape(shape: &Shape) -> JoltResult<ShapeDesc> {
+        ShapeDesc::describe(shape).ok_or(JoltError::DescribeShape)
+    }
+
+    fn describe(shape: &Shape) -> Option<ShapeDesc> {
+        let raw = shape.as_ref();
+
+        macro_rules! leaf_desc {
+            ($variant:ident, $settings:ty, $get:ident) => {{
+                let mut settings = <$settings>::default();
+                ffi::$get(raw, &mut settings).then_some(ShapeDesc::$variant(settings))?
+            }};
+        }
+
+        let desc = match shape.get_sub_type() {
+            ShapeSubType::Sphere => leaf_desc!(Sphere, SphereShapeSettings, GetSphereShapeSettings),
+            ShapeSubType::Box => leaf_desc!(Box, BoxShapeSettings, GetBoxShapeSettings),
+            ShapeSubType::Capsule => leaf_desc!(Capsule, CapsuleShapeSettings, GetCapsuleShapeSettings),
+            ShapeSubType::TaperedCapsule => {
+                leaf_desc!(
+                    TaperedCapsule,
+                    TaperedCapsuleShapeSettings,
+                    GetTaperedCapsuleShapeSettings
+                )
+            }
+            ShapeSubType::Cylinder => leaf_desc!(Cylinder, CylinderShapeSettings, GetCylinderShapeSettings),
+            ShapeSubType::TaperedCylinder => {
+                leaf_desc!(
+                    TaperedCylinder,
+                    TaperedCylinderShapeSettings,
+                    GetTaperedCylinderShapeSettings
+                )
+            }
+            ShapeSubType::Triangle => leaf_desc!(Triangle, TriangleShapeSettings, GetTriangleShapeSettings),
+            ShapeSubType::Plane => leaf_desc!(Plane, PlaneShapeSettings, GetPlaneShapeSettings),
+            ShapeSubType::Empty => leaf_desc!(Empty, EmptyShapeSettings, GetEmptyShapeSettings),
+            ShapeSubType::ConvexHull => {
+                let mut settings = ConvexHullShapeSettings::default();
+                let mut points = Vec::new();
+                let points_ref = unsafe { mem::transmute::<&mut Vec<Vec3A>, &mut Vec<JVec3>>(&mut points) };
+                if !ffi::GetConvexHullShapeSettings(raw, &mut settings, points_ref) {
+                    return None;
+                }
+                ShapeDesc::ConvexHull(ConvexHullShapeDesc {
+                    user_data: settings.user_data,
+                    material: settings.material,
+                    density: settings.density,
+                    points,
+                    max_convex_radius: settings.max_convex_radius,
+                    max_error_convex_radius: settings.max_error_convex_radius,
+                    hull_tolerance: settings.hull_tolerance,
+                })
+            }
+            ShapeSubType::Mesh => {
+                let mut desc = MeshShapeDesc {
+                    user_data: shape.get_user_data(),
+                    materials: get_shape_materials(raw),
+                    ..Default::default()
+                };
+                let vertices =
+                    unsafe { mem::transmute::<&mut Vec<Vec3>, &mut Vec<Float3>>(&mut desc.triangle_vertices) };
+                if !ffi::GetMeshShapeTriangles(raw, vertices, &mut desc.indexed_triangles) {
+                    return None;
+                }
+                ShapeDesc::Mesh(desc)
+            }
+            ShapeSubType::HeightField => {
+                let mut settings = HeightFieldShapeSettings::default();
+                let mut desc = HeightFieldShapeDesc::default();
+                if !ffi::GetHeightFieldShapeSettings(
+                    raw,
+                    &mut settings,
+                    &mut desc.height_samples,
+                    &mut desc.material_indices,
+                ) {
+                    return None;
+                }
+                ShapeDesc::HeightField(HeightFieldShapeDesc {
+                    user_data: settings.user_data,
+                    offset: settings.offset,
+                    scale: settings.scale,
+                    sample_count: settings.sample_count,
+                    block_size: settings.block_size,
+                    materials: get_shape_materials(raw),
+                    ..desc
+                })
+            }
+            ShapeSubType::Scaled => {
+                let mut settings = ScaledShapeSettings::default();
+                if !ffi::GetScaledShapeSettings(raw, &mut settings) {
+                    return None;
+                }
+                ShapeDesc::Scaled(ScaledShapeDesc {
+                    user_data: settings.user_data,
+                    inner_shape: Box::new(ShapeDesc::describe(settings.inner_shape.as_ref()?)?),
+                    scale: settings.scale,
+                })
+            }
+            ShapeSubType::RotatedTranslated => {
+                let mut settings = RotatedTranslatedShapeSettings::default();
+                if !ffi::GetRotatedTranslatedShapeSettings(raw, &mut settings) {
+                    return None;
+                }
+                ShapeDesc::RotatedTranslated(RotatedTranslatedShapeDesc {
+                    user_data: settings.user_data,
+                    inner_shape: Box::new(ShapeDesc::describe(settings.inner_shape.as_ref()?)?),
+                    position: settings.position,
+                    rotation: settings.rotation,
+                })
+            }
+            ShapeSubType::OffsetCenterOfMass => {
+                let mut settings = OffsetCenterOfMassShapeSettings::default();
+                if !ffi::GetOffsetCenterOfMassShapeSettings(raw, &mut settings) {
+                    return None;
+                }
+                ShapeDesc::OffsetCenterOfMass(OffsetCenterOfMassShapeDesc {
+                    user_data: settings.user_data,
+                    inner_shape: Box::new(ShapeDesc::describe(settings.inner_shape.as_ref()?)?),
+                    offset: settings.offset,
+                })
+            }
+            ShapeSubType::StaticCompound | ShapeSubType::MutableCompound => {
+                let compound = shape.as_compound()?;
+                let center_of_mass = shape.get_center_of_mass();
+                let sub_shapes = (0..compound.get_num_sub_shapes())
+                    .map(|idx| {
+                        let sub_shape = compound.get_sub_shape(idx);
+                        let rotation = sub_shape.rotation();
+                        // Jolt keeps the center of mass of the child relative to the center of mass of the compound
+                        let position = Vec3A::from(sub_shape.position) + center_of_mass
+                            - rotation * sub_shape.shape.get_center_of_mass();
+                        Some(SubShapeDesc {
+                            shape: ShapeDesc::describe(&sub_shape.shape)?,
+                            position,
+                            rotation,
+                            user_data: sub_shape.user_data,
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                let desc = CompoundShapeDesc {
+                    user_data: shape.get_user_data(),
+                    sub_shapes,
+                };
+                match shape.get_sub_type() {
+                    ShapeSubType::StaticCompound => ShapeDesc::StaticCompound(desc),
+                    _ => ShapeDesc::MutableCompound(desc),
+                }
+            }
+            _ => return None,
+        };
+        Some(desc)
+    }
+}
+
+fn get_shape_materials(shape: &ffi::Shape) -> Vec<JRef<PhysicsMaterial>> {
+    (0..ffi::CountShapeMaterials(shape))
+        .filter_map(|idx| NonNull::new(ffi::CloneShapeMaterial(shape, idx) as *mut PhysicsMaterial).map(JRef))
+        .collect()
+}
+
+/// Leaves are compared bit by bit, materials by identity.
+#[derive(Default, PartialEq, Eq, Hash)]
+struct LeafKey(Vec<u32>);
+
+impl LeafKey {
+    #[inline]
+    fn u32(&mut self, value: u32) -> &mut LeafKey {
+        self.0.push(value);
+        self
+    }
+
+    #[inline]
+    fn u64(&mut self, value: u64) -> &mut LeafKey {
+        self.0.extend([value as u32, (value >> 32) as u32]);
+        self
+    }
+
+    #[inline]
+    fn u32s(&mut self, values: &[u32]) -> &mut LeafKey {
+        self.u32(values.len() as u32);
+        self.0.extend_from_slice(values);
+        self
+    }
+
+    #[inline]
+    fn f32s(&mut self, values: &[f32]) -> &mut LeafKey {
+        self.u32(values.len() as u32);
+        self.0.extend(values.iter().map(|value| value.to_bits()));
+        self
+    }
+
+    #[inline]
+    fn material(&mut self, material: &Option<JRef<PhysicsMaterial>>) -> &mut LeafKey {
+        let ptr = material
+            .as_ref()
+            .map_or(std::ptr::null(), |material| material.as_ref() as *const PhysicsMaterial);
+        self.u64(ptr as usize as u64)
+    }
+
+    #[inline]
+    fn materials(&mut self, materials: &[JRef<PhysicsMaterial>]) -> &mut LeafKey {
+        self.u32(materials.len() as u32);
+        for material in materials {
+            self.u64(material.as_ref() as *const PhysicsMaterial as usize as u64);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConvexHullShapeDesc {
+    pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::serialize::MaterialDescWith))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_material"))]
+    pub material: Option<JRef<PhysicsMaterial>>,
+    pub density: f32,
+    pub points: Vec<Vec3A>,
+    pub max_convex_radius: f32,
+    pub max_error_convex_radius: f32,
+    pub hull_tolerance: f32,
+}
+
+impl Default for ConvexHullShapeDesc {
+    fn default() -> ConvexHullShapeDesc {
+        let settings = ConvexHullShapeSettings::default();
+        ConvexHullShapeDesc {
+            user_data: settings.user_data,
+            material: None,
+            density: settings.density,
+            points: Vec::new(),
+            max_convex_radius: settings.max_convex_radius,
+            max_error_convex_radius: settings.max_error_convex_radius,
+            hull_tolerance: settings.hull_tolerance,
+        }
+    }
+}
+
+impl ConvexHullShapeDesc {
+    pub fn new(points: Vec<Vec3A>) -> ConvexHullShapeDesc {
+        ConvexHullShapeDesc {
+            points,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn as_settings(&self) -> ConvexHullShapeSettings<'_> {
+        ConvexHullShapeSettings {
+            user_data: self.user_data,
+            material: self.material.clone(),
+            density: self.density,
+            points: &self.points,
+            max_convex_radius: self.max_convex_radius,
+            max_error_convex_radius: self.max_error_convex_radius,
+            hull_tolerance: self.hull_tolerance,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshShapeDesc {
+    pub user_data: u64,
+    pub triangle_vertices: Vec<Vec3>,
+    pub indexed_triangles: Vec<IndexedTriangle>,
+    #[cfg_attr(feature = "rkyv", rkyv(with = rkyv::with::Map<crate::serialize::MaterialDescWith>))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_materials"))]
+    pub materials: Vec<JRef<PhysicsMaterial>>,
+    pub max_triangles_per_leaf: u32,
+    pub active_edge_cos_threshold_angle: f32,
+}
+
+impl Default for MeshShapeDesc {
+    fn default() -> MeshShapeDesc {
+        let settings = MeshShapeSettings::default();
+        MeshShapeDesc {
+            user_data: settings.user_data,
+            triangle_vertices: Vec::new(),
+            indexed_triangles: Vec::new(),
+            materials: Vec::new(),
+            max_triangles_per_leaf: settings.max_triangles_per_leaf,
+            active_edge_cos_threshold_angle: settings.active_edge_cos_threshold_angle,
+        }
+    }
+}
+
+impl MeshShapeDesc {
+    pub fn new(triangle_vertices: Vec<Vec3>, indexed_triangles: Vec<IndexedTriangle>) -> MeshShapeDesc {
+        MeshShapeDesc {
+            triangle_vertices,
+            indexed_triangles,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn as_settings(&self) -> MeshShapeSettings<'_> {
+        MeshShapeSettings {
+            user_data: self.user_data,
+            triangle_vertices: &self.triangle_vertices,
+            indexed_triangles: &self.indexed_triangles,
+            materials: &self.materials,
+            max_triangles_per_leaf: self.max_triangles_per_leaf,
+            active_edge_cos_threshold_angle: self.active_edge_cos_threshold_angle,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeightFieldShapeDesc {
+    pub user_data: u64,
+    pub offset: Vec3A,
+    pub scale: Vec3A,
+    pub sample_count: u32,
+    pub min_height_value: f32,
+    pub max_height_value: f32,
+    pub block_size: u32,
+    pub bits_per_sample: u32,
+    pub height_samples: Vec<f32>,
+    pub material_indices: Vec<u8>,
+    #[cfg_attr(feature = "rkyv", rkyv(with = rkyv::with::Map<crate::serialize::MaterialDescWith>))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::serde_materials"))]
+    pub materials: Vec<JRef<PhysicsMaterial>>,
+    pub active_edge_cos_threshold_angle: f32,
+}
+
+impl Default for HeightFieldShapeDesc {
+    fn default() -> HeightFieldShapeDesc {
+        let settings = HeightFieldShapeSettings::default();
+        HeightFieldShapeDesc {
+            user_data: settings.user_data,
+            offset: settings.offset,
+            scale: settings.scale,
+            sample_count: settings.sample_count,
+            min_height_value: settings.min_height_value,
+            max_height_value: settings.max_height_value,
+            block_size: settings.block_size,
+            bits_per_sample: settings.bits_per_sample,
+            height_samples: Vec::new(),
+            material_indices: Vec::new(),
+            materials: Vec::new(),
+            active_edge_cos_threshold_angle: settings.active_edge_cos_threshold_angle,
+        }
+    }
+}
+
+impl HeightFieldShapeDesc {
+    pub fn new(height_samples: Vec<f32>, sample_count: u32) -> HeightFieldShapeDesc {
+        HeightFieldShapeDesc {
+            height_samples,
+            sample_count,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn as_settings(&self) -> HeightFieldShapeSettings<'_> {
+        HeightFieldShapeSettings {
+            user_data: self.user_data,
+            offset: self.offset,
+            scale: self.scale,
+            sample_count: self.sample_count,
+            min_height_value: self.min_height_value,
+            max_height_value: self.max_height_value,
+            block_size: self.block_size,
+            bits_per_sample: self.bits_per_sample,
+            height_samples: &self.height_samples,
+            material_indices: &self.material_indices,
+            materials: &self.materials,
+            active_edge_cos_threshold_angle: self.active_edge_cos_threshold_angle,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(
+        serialize_bounds(__S: rkyv::ser::Writer + rkyv::ser::Allocator, __S::Error: rkyv::rancor::Source),
+        deserialize_bounds(__D::Error: rkyv::rancor::Source),
+        bytecheck(bounds(__C: rkyv::validation::ArchiveContext, __C::Error: rkyv::rancor::Source))
+    )
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScaledShapeDesc {
+    pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(omit_bounds))]
+    pub inner_shape: Box<ShapeDesc>,
+    pub scale: Vec3A,
+}
+
+impl ScaledShapeDesc {
+    pub fn new(inner_shape: ShapeDesc, scale: Vec3A) -> ScaledShapeDesc {
+        ScaledShapeDesc {
+            user_data: 0,
+            inner_shape: Box::new(inner_shape),
+            scale,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(
+        serialize_bounds(__S: rkyv::ser::Writer + rkyv::ser::Allocator, __S::Error: rkyv::rancor::Source),
+        deserialize_bounds(__D::Error: rkyv::rancor::Source),
+        bytecheck(bounds(__C: rkyv::validation::ArchiveContext, __C::Error: rkyv::rancor::Source))
+    )
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotatedTranslatedShapeDesc {
+    pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(omit_bounds))]
+    pub inner_shape: Box<ShapeDesc>,
+    pub position: Vec3A,
+    pub rotation: Quat,
+}
+
+impl RotatedTranslatedShapeDesc {
+    pub fn new(inner_shape: ShapeDesc, position: Vec3A, rotation: Quat) -> RotatedTranslatedShapeDesc {
+        RotatedTranslatedShapeDesc {
+            user_data: 0,
+            inner_shape: Box::new(inner_shape),
+            position,
+            rotation,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(
+        serialize_bounds(__S: rkyv::ser::Writer + rkyv::ser::Allocator, __S::Error: rkyv::rancor::Source),
+        deserialize_bounds(__D::Error: rkyv::rancor::Source),
+        bytecheck(bounds(__C: rkyv::validation::ArchiveContext, __C::Error: rkyv::rancor::Source))
+    )
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OffsetCenterOfMassShapeDesc {
+    pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(omit_bounds))]
+    pub inner_shape: Box<ShapeDesc>,
+    pub offset: Vec3A,
+}
+
+impl OffsetCenterOfMassShapeDesc {
+    pub fn new(inner_shape: ShapeDesc, offset: Vec3A) -> OffsetCenterOfMassShapeDesc {
+        OffsetCenterOfMassShapeDesc {
+            user_data: 0,
+            inner_shape: Box::new(inner_shape),
+            offset,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(
+        serialize_bounds(__S: rkyv::ser::Writer + rkyv::ser::Allocator, __S::Error: rkyv::rancor::Source),
+        deserialize_bounds(__D::Error: rkyv::rancor::Source),
+        bytecheck(bounds(__C: rkyv::validation::ArchiveContext, __C::Error: rkyv::rancor::Source))
+    )
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubShapeDesc {
+    #[cfg_attr(feature = "rkyv", rkyv(omit_bounds))]
+    pub shape: ShapeDesc,
+    pub position: Vec3A,
+    pub rotation: Quat,
+    pub user_data: u32,
+}
+
+impl SubShapeDesc {
+    pub fn new(shape: ShapeDesc, position: Vec3A, rotation: Quat) -> SubShapeDesc {
+        SubShapeDesc {
+            shape,
+            position,
+            rotation,
+            user_data: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(
+        serialize_bounds(__S: rkyv::ser::Writer + rkyv::ser::Allocator, __S::Error: rkyv::rancor::Source),
+        deserialize_bounds(__D::Error: rkyv::rancor::Source),
+        bytecheck(bounds(__C: rkyv::validation::ArchiveContext, __C::Error: rkyv::rancor::Source))
+    )
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompoundShapeDesc {
+    pub user_data: u64,
+    #[cfg_attr(feature = "rkyv", rkyv(omit_bounds))]
+    pub sub_shapes: Vec<SubShapeDesc>,
+}
+
+impl CompoundShapeDesc {
+    pub fn new(sub_shapes: Vec<SubShapeDesc>) -> CompoundShapeDesc {
+        CompoundShapeDesc {
+            user_data: 0,
+            sub_shapes,
+        }
+    }
+
+    fn build_sub_shapes(&self, leaves: &mut HashMap<LeafKey, JRef<Shape>>) -> JoltResult<Vec<SubShapeSettings>> {
+        self.sub_shapes
+            .iter()
+            .map(|sub| {
+                let shape = sub.shape.build_with(leaves)?;
+                Ok(SubShapeSettings {
+                    user_data: sub.user_data,
+                    ..SubShapeSettings::new(shape, sub.position, sub.rotation)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::system::global_initialize;
+
+    fn create_nested_compound() -> (JRef<Shape>, JRef<Shape>) {
+        global_initialize();
+        let cube = create_box_shape(&BoxShapeSettings::new(0.5, 0.5, 0.5)).unwrap();
+        let ball = create_sphere_shape(&SphereShapeSettings::new(0.5)).unwrap();
+
+        // A single child needs 0 bits.
+        let single = create_static_compound_shape(&StaticCompoundShapeSettings::new(&[SubShapeSettings::new(
+            ball.clone(),
+            Vec3A::ZERO,
+            Quat::IDENTITY,
+        )]))
+        .unwrap();
+        let moved = create_rotated_translated_shape(&RotatedTranslatedShapeSettings::new(
+            single.into(),
+            Vec3A::X,
+            Quat::IDENTITY,
+        ))
+        .unwrap();
+
+        // 3 children, index 3 is out of range.
+        let three = create_static_compound_shape(&StaticCompoundShapeSettings::new(&[
+            SubShapeSettings::new(cube.clone(), Vec3A::ZERO, Quat::IDENTITY),
+            SubShapeSettings::new(ball.clone(), Vec3A::Y, Quat::IDENTITY),
+            SubShapeSettings::new(moved, Vec3A::Z, Quat::IDENTITY),
+        ]))
+        .unwrap();
+
+        // 4 children, index 3 encodes as all ones.
+        let four = create_mutable_compound_shape(&MutableCompoundShapeSettings::new(&[
+            SubShapeSettings::new(three.clone().into(), Vec3A::X, Quat::IDENTITY),
+            SubShapeSettings::new(ball, Vec3A::Y, Quat::IDENTITY),
+            SubShapeSettings::new(cube.clone(), Vec3A::Z, Quat::IDENTITY),
+            SubShapeSettings::new(cube, Vec3A::ZERO, Quat::IDENTITY),
+        ]))
+        .unwrap();
+        (three.into(), four.into())
+    }
+
+    #[test]
+    fn test_compound_sub_shape_id_round_trip() {
+        let (three, four) = create_nested_compound();
+
+        for path in [&[0][..], &[1], &[2, 0]] {
+            let id = three.encode_sub_shape_id(path).unwrap();
+            assert_eq!(three.decode_sub_shape_id(id), Some((path.to_vec(), SubShapeID::EMPTY)));
+        }
+        for path in [&[0, 0][..], &[0, 1], &[0, 2, 0], &[1], &[2], &[3]] {
+            let id = four.encode_sub_shape_id(path).unwrap();
+            assert_eq!(four.decode_sub_shape_id(id), Some((path.to_vec(), SubShapeID::EMPTY)));
+        }
+
+        // All bits set is the last child of the outer compound, but out of range for the inner one.
+        assert_eq!(four.encode_sub_shape_id(&[3]), Some(SubShapeID::EMPTY));
+        assert_eq!(three.decode_sub_shape_id(SubShapeID::EMPTY), None);
+        assert_eq!(three.encode_sub_shape_id(&[3]), None);
+
+        // The path has to end at a leaf.
+        assert_eq!(three.encode_sub_shape_id(&[]), None);
+        assert_eq!(four.encode_sub_shape_id(&[0]), None);
+        assert_eq!(three.encode_sub_shape_id(&[0, 0]), None);
+        assert_eq!(three.encode_sub_shape_id(&[2, 0, 0]), None);
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        global_initialize();
+        let material = create_physics_material("rubber", Color::RED, 7u32);
+        let cube = create_box_shape(&BoxShapeSettings {
+            material: Some(material.clone()),
+            ..BoxShapeSettings::new(0.5, 1.0, 1.5)
+        })
+        .unwrap();
+        let vertices = [Vec3::ZERO, Vec3::X, Vec3::Z, Vec3::new(1.0, 0.0, 1.0)];
+        let triangles = [IndexedTriangle::new(0, 2, 1, 0), IndexedTriangle::new(1, 2, 3, 0)];
+        let materials = [material];
+        let mesh = create_mesh_shape(&MeshShapeSettings {
+            materials: &materials,
+            ..MeshShapeSettings::new(&vertices, &triangles)
+        })
+        .unwrap();
+        let compound = create_static_compound_shape(&StaticCompoundShapeSettings::new(&[
+            SubShapeSettings::new(cube.clone(), Vec3A::ZERO, Quat::IDENTITY),
+            SubShapeSettings::new(cube, Vec3A::Y * 3.0, Quat::IDENTITY),
+            SubShapeSettings::new(mesh, Vec3A::NEG_Y * 2.0, Quat::IDENTITY),
+        ]))
+        .unwrap();
+        let shape: JRef<Shape> = compound.into();
+
+        let mut data = Vec::new();
+        shape.save_binary(&mut data);
+        let restored = unsafe { Shape::restore_binary(&data) }.unwrap();
+        assert_eq!(restored.get_sub_type(), ShapeSubType::StaticCompound);
+        assert_eq!(restored.get_local_bounds(), shape.get_local_bounds());
+        assert_eq!(restored.get_mass_properties().mass, shape.get_mass_properties().mass);
+
+        // The shared box is written once, material payloads are not saved.
+        let id0 = restored.encode_sub_shape_id(&[0]).unwrap();
+        let id1 = restored.encode_sub_shape_id(&[1]).unwrap();
+        let (leaf0, _) = restored.get_leaf_shape(id0).unwrap();
+        let (leaf1, _) = restored.get_leaf_shape(id1).unwrap();
+        assert!(ptr::eq(leaf0, leaf1));
+        assert_eq!(leaf0.get_sub_type(), ShapeSubType::Box);
+        let material = restored.get_material(id0).unwrap();
+        assert_eq!(material.get_debug_name(), "rubber");
+        assert!(material.get_payload::<u32>().is_none());
+
+        assert!(unsafe { Shape::restore_binary(&[]) }.is_err());
+        assert!(unsafe { Shape::restore_binary(&data[..8]) }.is_err());
+        let mut wrong_version = data.clone();
+        wrong_version[4] ^= 0xFF;
+        assert!(unsafe { Shape::restore_binary(&wrong_version) }.is_err());
+    }
+
+    struct Surface {
+        sound: u32,
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl Drop for Surface {
+        fn drop(&mut self) {
+            self.dropped.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_material_payload() {
+        global_initialize();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let material = create_physics_material(
+            "gravel",
+            Color::GREEN,
+            Surface {
+                sound: 42,
+                dropped: dropped.clone(),
+            },
+        );
+        assert_eq!(material.get_debug_name(), "gravel");
+        assert_eq!(material.get_debug_color(), Color::GREEN);
+        assert_eq!(material.get_payload::<Surface>().unwrap().sound, 42);
+        assert!(material.get_payload::<u32>().is_none());
+
+        // The shape keeps the material and its payload alive.
+        let shape = create_box_shape(&BoxShapeSettings {
+            material: Some(material),
+            ..BoxShapeSettings::new(0.5, 0.5, 0.5)
+        })
+        .unwrap();
+        assert!(!dropped.load(Ordering::SeqCst));
+        let material = shape.get_material(SubShapeID::EMPTY).unwrap();
+        assert_eq!(material.get_payload::<Surface>().unwrap().sound, 42);
+
+        // Released by the XPhysicsMaterial destructor.
+        drop(shape);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    /// A box at the origin and a two triangle mesh quad at x = 5, with different materials and user data.
+    fn create_box_and_mesh() -> JRef<Shape> {
+        global_initialize();
+        let stone = create_physics_material("stone", Color::GREY, ());
+        let grass = create_physics_material("grass", Color::GREEN, ());
+        let cube = create_box_shape(&BoxShapeSettings {
+            material: Some(stone.clone()),
+            ..BoxShapeSettings::new(0.5, 0.5, 0.5)
+        })
+        .unwrap();
+        let vertices = [Vec3::ZERO, Vec3::X, Vec3::Z, Vec3::new(1.0, 0.0, 1.0)];
+        let triangles = [IndexedTriangle::new(0, 2, 1, 0), IndexedTriangle::new(1, 2, 3, 1)];
+        let materials = [stone, grass];
+        let mesh = create_mesh_shape(&MeshShapeSettings {
+            materials: &materials,
+            ..MeshShapeSettings::new(&vertices, &triangles)
+        })
+        .unwrap();
+        create_static_compound_shape(&StaticCompoundShapeSettings::new(&[
+            SubShapeSettings {
+                user_data: 10,
+                ..SubShapeSettings::new(cube, Vec3A::ZERO, Quat::IDENTITY)
+            },
+            SubShapeSettings {
+                user_data: 20,
+                ..SubShapeSettings::new(mesh, Vec3A::X * 5.0, Quat::IDENTITY)
+            },
+        ]))
+        .unwrap()
+        .into()
+    }
+
+    #[test]
+    fn test_sub_shape_lookups() {
+        let shape = create_box_and_mesh();
+        let down = Vec3A::NEG_Y * 2.0;
+
+        let hit = shape.cast_ray(Vec3A::Y, down).unwrap();
+        assert_eq!(shape.get_material(hit.sub_shape_id).unwrap().get_debug_name(), "stone");
+        assert_eq!(shape.get_sub_shape_user_data(hit.sub_shape_id), Some(10));
+        let (leaf, remainder) = shape.get_leaf_shape(hit.sub_shape_id).unwrap();
+        assert_eq!(leaf.get_sub_type(), ShapeSubType::Box);
+        assert!(remainder.is_empty());
+
+        let hit = shape.cast_ray(Vec3A::new(5.2, 1.0, 0.2), down).unwrap();
+        assert_eq!(shape.get_material(hit.sub_shape_id).unwrap().get_debug_name(), "stone");
+        assert_eq!(shape.get_sub_shape_user_data(hit.sub_shape_id), Some(20));
+        let (leaf, remainder) = shape.get_leaf_shape(hit.sub_shape_id).unwrap();
+        assert_eq!(leaf.get_sub_type(), ShapeSubType::Mesh);
+        assert!(!remainder.is_empty());
+
+        let hit = shape.cast_ray(Vec3A::new(5.8, 1.0, 0.8), down).unwrap();
+        assert_eq!(shape.get_material(hit.sub_shape_id).unwrap().get_debug_name(), "grass");
+        assert_eq!(shape.get_sub_shape_user_data(hit.sub_shape_id), Some(20));
+        let (leaf, remainder) = shape.get_leaf_shape(hit.sub_shape_id).unwrap();
+
+        // Leaves have no compound user data and only accept their own ids.
+        assert_eq!(leaf.get_sub_shape_user_data(remainder), None);
+        assert_eq!(leaf.get_material(remainder).unwrap().get_debug_name(), "grass");
+        let (cube, _) = shape.get_leaf_shape(shape.encode_sub_shape_id(&[0]).unwrap()).unwrap();
+        assert!(cube.get_material(SubShapeID(0)).is_none());
+        assert!(cube.get_leaf_shape(SubShapeID(0)).is_none());
+    }
+
+    #[test]
+    fn test_offset_center_of_mass_queries() {
+        global_initialize();
+        let cube = create_box_shape(&BoxShapeSettings::new(0.5, 0.5, 0.5)).unwrap();
+        let shape =
+            create_offset_center_of_mass_shape(&OffsetCenterOfMassShapeSettings::new(cube, Vec3A::X * 0.3)).unwrap();
+        assert!(shape.get_center_of_mass().abs_diff_eq(Vec3A::X * 0.3, 1e-5));
+
+        // The box doesn't move with its center of mass
+        let hit = shape.cast_ray(Vec3A::X * -2.0, Vec3A::X * 4.0).unwrap();
+        assert!((hit.fraction - 0.375).abs() < 1e-4);
+        assert!(hit.position.abs_diff_eq(Vec3A::X * -0.5, 1e-4));
+        assert!(hit.normal.abs_diff_eq(Vec3A::NEG_X, 1e-4));
+        let hit = shape.cast_ray(Vec3A::X * 2.0, Vec3A::X * -4.0).unwrap();
+        assert!(hit.position.abs_diff_eq(Vec3A::X * 0.5, 1e-4));
+        assert!(hit.normal.abs_diff_eq(Vec3A::X, 1e-4));
+
+        assert_eq!(shape.collide_point(Vec3A::X * 0.4).len(), 1);
+        assert!(shape.collide_point(Vec3A::X * -0.6).is_empty());
+
+        let normal = shape
+            .get_surface_normal(SubShapeID::EMPTY, Vec3A::new(-0.45, 0.0, 0.4))
+            .unwrap();
+        assert!(normal.abs_diff_eq(Vec3A::NEG_X, 1e-4));
+
+        // Decorated shapes have no support function
+        assert!(shape.get_support(Vec3A::X, Vec3A::ONE).is_none());
+    }
+
+    #[test]
+    fn test_convex_support() {
+        global_initialize();
+        let points = [
+            Vec3A::ZERO,
+            Vec3A::X,
+            Vec3A::Y,
+            Vec3A::Z,
+            Vec3A::new(1.0, 1.0, 0.0),
+            Vec3A::new(1.0, 0.0, 1.0),
+            Vec3A::new(0.0, 1.0, 1.0),
+            Vec3A::ONE,
+        ];
+        let hull = create_convex_hull_shape(&ConvexHullShapeSettings::new(&points)).unwrap();
+        assert!(hull.get_center_of_mass().abs_diff_eq(Vec3A::splat(0.5), 1e-4));
+
+        let support = hull.get_support(Vec3A::X, Vec3A::ONE).unwrap();
+        assert!((support.x - 1.0).abs() < 1e-3);
+        let support = hull.get_support(Vec3A::NEG_Y, Vec3A::ONE).unwrap();
+        assert!(support.y.abs() < 1e-3);
+        let support = hull.get_support(Vec3A::X, Vec3A::new(2.0, 1.0, 1.0)).unwrap();
+        assert!((support.x - 2.0).abs() < 1e-3);
+
+        let hit = hull.cast_ray(Vec3A::new(0.5, 2.0, 0.5), Vec3A::NEG_Y * 4.0).unwrap();
+        assert!(hit.position.abs_diff_eq(Vec3A::new(0.5, 1.0, 0.5), 1e-3));
+        assert!(hit.normal.abs_diff_eq(Vec3A::Y, 1e-3));
+    }
+
+    fn leaf_at<'t>(shape: &'t Shape, path: &[u32]) -> &'t Shape {
+        shape
+            .get_leaf_shape(shape.encode_sub_shape_id(path).unwrap())
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn test_shape_desc_dedup() {
+        global_initialize();
+        let cube = ShapeDesc::Box(BoxShapeSettings::new(0.5, 0.5, 0.5));
+        let desc = ShapeDesc::StaticCompound(CompoundShapeDesc::new(vec![
+            SubShapeDesc::new(cube.clone(), Vec3A::ZERO, Quat::IDENTITY),
+            SubShapeDesc::new(cube.clone(), Vec3A::X, Quat::IDENTITY),
+            SubShapeDesc::new(
+                ShapeDesc::Box(BoxShapeSettings::new(0.5, 0.5, 0.6)),
+                Vec3A::Y,
+                Quat::IDENTITY,
+            ),
+            SubShapeDesc::new(
+                ShapeDesc::Box(BoxShapeSettings {
+                    user_data: 1,
+                    ..BoxShapeSettings::new(0.5, 0.5, 0.5)
+                }),
+                Vec3A::Z,
+                Quat::IDENTITY,
+            ),
+            SubShapeDesc::new(
+                ShapeDesc::RotatedTranslated(RotatedTranslatedShapeDesc::new(cube, Vec3A::X, Quat::IDENTITY)),
+                Vec3A::NEG_X,
+                Quat::IDENTITY,
+            ),
+        ]));
+        let shape = desc.build().unwrap();
+
+        let first = leaf_at(&shape, &[0]);
+        assert!(ptr::eq(first, leaf_at(&shape, &[1])));
+        assert!(ptr::eq(first, leaf_at(&shape, &[4])));
+        assert!(!ptr::eq(first, leaf_at(&shape, &[2])));
+        assert!(!ptr::eq(first, leaf_at(&shape, &[3])));
+
+        // Separate builds don't share leaves.
+        let other = desc.build().unwrap();
+        assert!(!ptr::eq(first, leaf_at(&other, &[0])));
+    }
+
+    fn assert_same_tree(a: &Shape, b: &Shape) {
+        assert_eq!(a.get_sub_type(), b.get_sub_type());
+        assert_eq!(a.get_user_data(), b.get_user_data());
+        assert!(a.get_center_of_mass().abs_diff_eq(b.get_center_of_mass(), 1e-4));
+        assert!((a.get_volume() - b.get_volume()).abs() < 1e-4);
+        if let (Some(ca), Some(cb)) = (a.as_compound(), b.as_compound()) {
+            assert_eq!(ca.get_num_sub_shapes(), cb.get_num_sub_shapes());
+            for idx in 0..ca.get_num_sub_shapes() {
+                let (sa, sb) = (ca.get_sub_shape(idx), cb.get_sub_shape(idx));
+                assert!(Vec3A::from(sa.position).abs_diff_eq(Vec3A::from(sb.position), 1e-4));
+                assert!(sa.rotation().abs_diff_eq(sb.rotation(), 1e-4));
+                assert_eq!(sa.user_data, sb.user_data);
+                assert_same_tree(&sa.shape, &sb.shape);
+            }
+        } else if let (Some(ia), Some(ib)) = (a.get_decorated_inner_shape(), b.get_decorated_inner_shape()) {
+            assert_same_tree(ia, ib);
+        } else {
+            assert!(a.as_compound().is_none() && b.as_compound().is_none());
+            assert!(a.get_decorated_inner_shape().is_none() && b.get_decorated_inner_shape().is_none());
+        }
+    }
+
+    #[test]
+    fn test_shape_desc_round_trip() {
+        let (_, four) = create_nested_compound();
+        let desc = ShapeDesc::from_shape(&four).unwrap();
+        assert!(matches!(&desc, ShapeDesc::MutableCompound(compound) if compound.sub_shapes.len() == 4));
+
+        let rebuilt = desc.build().unwrap();
+        assert_same_tree(&four, &rebuilt);
+        for path in [&[0, 0][..], &[0, 1], &[0, 2, 0], &[1], &[2], &[3]] {
+            assert_eq!(four.encode_sub_shape_id(path), rebuilt.encode_sub_shape_id(path));
+        }
+
+        // Leaves shared by the original tree are shared again.
+        assert!(ptr::eq(leaf_at(&rebuilt, &[2]), leaf_at(&rebuilt, &[3])));
+        assert!(ptr::eq(leaf_at(&rebuilt, &[0, 0]), leaf_at(&rebuilt, &[3])));
+        assert!(ptr::eq(leaf_at(&rebuilt, &[0, 2, 0]), leaf_at(&rebuilt, &[1])));
+    }
+}
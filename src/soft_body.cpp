@@ -0,0 +1,157 @@
+#include "jolt-physics-rs/src/ffi.h"
+#include "jolt-physics-rs/src/soft_body.rs.h"
+
+struct XSoftBodyVertex {
+	Float3 position;
+	Float3 velocity;
+	float invMass;
+};
+static_assert(sizeof(XSoftBodyVertex) == 28, "XSoftBodyVertex size");
+
+struct XSoftBodyFace {
+	uint32 vertex[3];
+	uint32 materialIndex;
+};
+static_assert(sizeof(XSoftBodyFace) == 16, "XSoftBodyFace size");
+
+struct XSoftBodyEdge {
+	uint32 vertex[2];
+	float restLength;
+	float compliance;
+};
+static_assert(sizeof(XSoftBodyEdge) == 16, "XSoftBodyEdge size");
+
+struct XSoftBodyVolume {
+	uint32 vertex[4];
+	float sixRestVolume;
+	float compliance;
+};
+static_assert(sizeof(XSoftBodyVolume) == 24, "XSoftBodyVolume size");
+
+struct XSoftBodySharedSettings {
+	rust::Slice<const XSoftBodyVertex> vertices;
+	rust::Slice<const XSoftBodyFace> faces;
+	rust::Slice<const XSoftBodyEdge> edges;
+	rust::Slice<const XSoftBodyVolume> volumes;
+	rust::Slice<PhysicsMaterial*> materials;
+	bool calculateEdgeLengths;
+	bool calculateVolumes;
+	bool optimize;
+};
+static_assert(sizeof(XSoftBodySharedSettings) == 88, "XSoftBodySharedSettings size");
+
+SoftBodySharedSettings* CreateSoftBodySharedSettings(const XSoftBodySharedSettings& st) {
+	Ref<SoftBodySharedSettings> settings = new SoftBodySharedSettings();
+	settings->mVertices.reserve(st.vertices.size());
+	for (const XSoftBodyVertex& v : st.vertices) {
+		settings->mVertices.push_back(SoftBodySharedSettings::Vertex(v.position, v.velocity, v.invMass));
+	}
+	settings->mFaces.reserve(st.faces.size());
+	for (const XSoftBodyFace& f : st.faces) {
+		settings->mFaces.push_back(SoftBodySharedSettings::Face(f.vertex[0], f.vertex[1], f.vertex[2], f.materialIndex));
+	}
+	settings->mEdgeConstraints.reserve(st.edges.size());
+	for (const XSoftBodyEdge& e : st.edges) {
+		SoftBodySharedSettings::Edge edge(e.vertex[0], e.vertex[1], e.compliance);
+		edge.mRestLength = e.restLength;
+		settings->mEdgeConstraints.push_back(edge);
+	}
+	settings->mVolumeConstraints.reserve(st.volumes.size());
+	for (const XSoftBodyVolume& v : st.volumes) {
+		SoftBodySharedSettings::Volume volume(v.vertex[0], v.vertex[1], v.vertex[2], v.vertex[3], v.compliance);
+		volume.mSixRestVolume = v.sixRestVolume;
+		settings->mVolumeConstraints.push_back(volume);
+	}
+	if (!st.materials.empty()) {
+		settings->mMaterials = PhysicsMaterialList(
+			(PhysicsMaterialRefC*)st.materials.data(),
+			(PhysicsMaterialRefC*)st.materials.data() + st.materials.size()
+		);
+	}
+	if (st.calculateEdgeLengths) {
+		settings->CalculateEdgeLengths();
+	}
+	if (st.calculateVolumes) {
+		settings->CalculateVolumeConstraintVolumes();
+	}
+	if (st.optimize) {
+		settings->Optimize();
+	}
+	return LeakRefT<SoftBodySharedSettings>(settings);
+}
+
+struct XSoftBodyCreationSettings {
+	const SoftBodySharedSettings* settings;
+	Vec3 position;
+	Quat rotation;
+	uint64 userData;
+	ObjectLayer objectLayer;
+	uint32 numIterations;
+	float linearDamping;
+	float maxLinearVelocity;
+	float restitution;
+	float friction;
+	float pressure;
+	float gravityFactor;
+	bool updatePosition;
+	bool makeRotationIdentity;
+	bool allowSleeping;
+};
+static_assert(sizeof(XSoftBodyCreationSettings) == 96, "XSoftBodyCreationSettings size");
+
+static SoftBodyCreationSettings AsSoftBodyCreationSettings(const XSoftBodyCreationSettings& st) {
+	SoftBodyCreationSettings settings(st.settings, st.position, st.rotation, st.objectLayer);
+	settings.mUserData = st.userData;
+	settings.mNumIterations = st.numIterations;
+	settings.mLinearDamping = st.linearDamping;
+	settings.mMaxLinearVelocity = st.maxLinearVelocity;
+	settings.mRestitution = st.restitution;
+	settings.mFriction = st.friction;
+	settings.mPressure = st.pressure;
+	settings.mGravityFactor = st.gravityFactor;
+	settings.mUpdatePosition = st.updatePosition;
+	settings.mMakeRotationIdentity = st.makeRotationIdentity;
+	settings.mAllowSleeping = st.allowSleeping;
+	return settings;
+}
+
+BodyID CreateSoftBody(XBodyInterface& bodyItf, const XSoftBodyCreationSettings& st) {
+	if (st.settings == nullptr) {
+		return BodyID();
+	}
+	auto body = bodyItf.CreateSoftBody(AsSoftBodyCreationSettings(st));
+	if (body == nullptr) {
+		return BodyID();
+	}
+	return body->GetID();
+}
+
+BodyID CreateAddSoftBody(XBodyInterface& bodyItf, const XSoftBodyCreationSettings& st, EActivation activation) {
+	if (st.settings == nullptr) {
+		return BodyID();
+	}
+	return bodyItf.CreateAndAddSoftBody(AsSoftBodyCreationSettings(st), activation);
+}
+
+bool GetSoftBodyVertices(const Body& body, bool worldSpace, rust::Vec<Vec3>& vertices) {
+	if (!body.IsSoftBody()) {
+		return false;
+	}
+	auto motion = static_cast<const SoftBodyMotionProperties*>(body.GetMotionProperties());
+	const Array<SoftBodyVertex>& src = motion->GetVertices();
+	RMat44 transform = body.GetCenterOfMassTransform();
+	vertices.clear();
+	vertices.reserve(src.size());
+	for (const SoftBodyVertex& v : src) {
+		vertices.push_back(worldSpace ? Vec3(transform * v.mPosition) : v.mPosition);
+	}
+	return true;
+}
+
+bool GetSoftBodyVerticesByID(const XPhysicsSystem& system, const BodyID& bodyId, bool worldSpace, rust::Vec<Vec3>& vertices) {
+	BodyLockRead lock(system.PhySys().GetBodyLockInterface(), bodyId);
+	if (!lock.Succeeded()) {
+		return false;
+	}
+	return GetSoftBodyVertices(lock.GetBody(), worldSpace, vertices);
+}
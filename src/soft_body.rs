@@ -0,0 +1,735 @@
+use core::fmt;
+use cxx::{kind, type_id, ExternType};
+use glam::{Quat, Vec3, Vec3A};
+use jolt_macros::vtable;
+use static_assertions::const_assert_eq;
+use std::mem;
+use std::ptr::NonNull;
+
+use crate::base::{BodyID, JMut, JMutTarget, JRef, JRefTarget, JVec3, ObjectLayer};
+use crate::body::Body;
+use crate::error::{JoltError, JoltResult};
+use crate::shape::PhysicsMaterial;
+use crate::system::{BodyActivationListener, BodyInterface, ContactListener, PhysicsSystem};
+
+#[cxx::bridge()]
+pub(crate) mod ffi {
+    #[repr(u32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum SoftBodyValidateResult {
+        AcceptContact,
+        RejectContact,
+    }
+
+    unsafe extern "C++" {
+        include!("rust/cxx.h");
+        include!("jolt-physics-rs/src/ffi.h");
+
+        type Activation = crate::base::ffi::Activation;
+        type SoftBodyValidateResult;
+
+        type Vec3 = crate::base::ffi::Vec3;
+        type BodyID = crate::base::ffi::BodyID;
+        type Body = crate::body::ffi::Body;
+        type XBodyInterface = crate::system::ffi::XBodyInterface;
+        type XPhysicsSystem = crate::system::ffi::XPhysicsSystem;
+
+        type XSoftBodySharedSettings = crate::soft_body::SoftBodySharedSettingsDesc<'static>;
+        type XSoftBodyCreationSettings = crate::soft_body::SoftBodyCreationSettings;
+
+        type SoftBodySharedSettings;
+        unsafe fn DropSoftBodySharedSettings(settings: *mut SoftBodySharedSettings);
+        unsafe fn CloneSoftBodySharedSettings(settings: *mut SoftBodySharedSettings) -> *mut SoftBodySharedSettings;
+        unsafe fn CountRefSoftBodySharedSettings(settings: *const SoftBodySharedSettings) -> u32;
+
+        fn CreateSoftBodySharedSettings(settings: &XSoftBodySharedSettings) -> *mut SoftBodySharedSettings;
+        fn GetSoftBodyNumVertices(settings: &SoftBodySharedSettings) -> u32;
+        fn GetSoftBodyNumFaces(settings: &SoftBodySharedSettings) -> u32;
+        fn GetSoftBodyNumEdges(settings: &SoftBodySharedSettings) -> u32;
+        fn GetSoftBodyNumVolumes(settings: &SoftBodySharedSettings) -> u32;
+
+        fn CreateSoftBody(body_itf: Pin<&mut XBodyInterface>, settings: &XSoftBodyCreationSettings) -> BodyID;
+        fn CreateAddSoftBody(
+            body_itf: Pin<&mut XBodyInterface>,
+            settings: &XSoftBodyCreationSettings,
+            active: Activation,
+        ) -> BodyID;
+        fn GetSoftBodyVertices(body: &Body, world_space: bool, vertices: &mut Vec<Vec3>) -> bool;
+        fn GetSoftBodyVerticesByID(
+            system: &XPhysicsSystem,
+            body_id: &BodyID,
+            world_space: bool,
+            vertices: &mut Vec<Vec3>,
+        ) -> bool;
+
+        type SoftBodyManifold;
+        fn GetSoftBodyManifoldNumVertices(manifold: &SoftBodyManifold) -> u32;
+        fn GetSoftBodyManifoldVertexPosition(manifold: &SoftBodyManifold, index: u32) -> Vec3;
+        fn SoftBodyManifoldHasContact(manifold: &SoftBodyManifold, index: u32) -> bool;
+        fn GetSoftBodyManifoldLocalContactPoint(manifold: &SoftBodyManifold, index: u32) -> Vec3;
+        fn GetSoftBodyManifoldContactNormal(manifold: &SoftBodyManifold, index: u32) -> Vec3;
+        fn GetSoftBodyManifoldContactBodyID(manifold: &SoftBodyManifold, index: u32) -> BodyID;
+    }
+}
+
+pub type SoftBodyValidateResult = ffi::SoftBodyValidateResult;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoftBodyVertex {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub inv_mass: f32,
+}
+const_assert_eq!(mem::size_of::<SoftBodyVertex>(), 28);
+
+impl Default for SoftBodyVertex {
+    fn default() -> SoftBodyVertex {
+        SoftBodyVertex {
+            position: Vec3::ZERO,
+            velocity: Vec3::ZERO,
+            inv_mass: 1.0,
+        }
+    }
+}
+
+impl SoftBodyVertex {
+    pub fn new(position: Vec3, inv_mass: f32) -> SoftBodyVertex {
+        SoftBodyVertex {
+            position,
+            velocity: Vec3::ZERO,
+            inv_mass,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SoftBodyFace {
+    pub vertex: [u32; 3],
+    pub material_index: u32,
+}
+const_assert_eq!(mem::size_of::<SoftBodyFace>(), 16);
+
+impl SoftBodyFace {
+    pub fn new(v1: u32, v2: u32, v3: u32, material_index: u32) -> SoftBodyFace {
+        SoftBodyFace {
+            vertex: [v1, v2, v3],
+            material_index,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoftBodyEdge {
+    pub vertex: [u32; 2],
+    pub rest_length: f32,
+    pub compliance: f32,
+}
+const_assert_eq!(mem::size_of::<SoftBodyEdge>(), 16);
+
+impl Default for SoftBodyEdge {
+    fn default() -> SoftBodyEdge {
+        SoftBodyEdge {
+            vertex: [0, 0],
+            rest_length: 1.0,
+            compliance: 0.0,
+        }
+    }
+}
+
+impl SoftBodyEdge {
+    pub fn new(v1: u32, v2: u32, compliance: f32) -> SoftBodyEdge {
+        SoftBodyEdge {
+            vertex: [v1, v2],
+            compliance,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoftBodyVolume {
+    pub vertex: [u32; 4],
+    pub six_rest_volume: f32,
+    pub compliance: f32,
+}
+const_assert_eq!(mem::size_of::<SoftBodyVolume>(), 24);
+
+impl Default for SoftBodyVolume {
+    fn default() -> SoftBodyVolume {
+        SoftBodyVolume {
+            vertex: [0, 0, 0, 0],
+            six_rest_volume: 1.0,
+            compliance: 0.0,
+        }
+    }
+}
+
+impl SoftBodyVolume {
+    pub fn new(v1: u32, v2: u32, v3: u32, v4: u32, compliance: f32) -> SoftBodyVolume {
+        SoftBodyVolume {
+            vertex: [v1, v2, v3, v4],
+            compliance,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct SoftBodySharedSettingsDesc<'t> {
+    pub vertices: &'t [SoftBodyVertex],
+    pub faces: &'t [SoftBodyFace],
+    pub edges: &'t [SoftBodyEdge],
+    pub volumes: &'t [SoftBodyVolume],
+    pub materials: &'t [JRef<PhysicsMaterial>],
+    /// Recalculate `rest_length` of edges from the vertex positions.
+    pub calculate_edge_lengths: bool,
+    /// Recalculate `six_rest_volume` of volumes from the vertex positions.
+    pub calculate_volumes: bool,
+    pub optimize: bool,
+}
+const_assert_eq!(mem::size_of::<SoftBodySharedSettingsDesc>(), 88);
+
+unsafe impl ExternType for SoftBodySharedSettingsDesc<'_> {
+    type Id = type_id!("XSoftBodySharedSettings");
+    type Kind = kind::Trivial;
+}
+
+impl<'t> Default for SoftBodySharedSettingsDesc<'t> {
+    fn default() -> SoftBodySharedSettingsDesc<'t> {
+        SoftBodySharedSettingsDesc {
+            vertices: &[],
+            faces: &[],
+            edges: &[],
+            volumes: &[],
+            materials: &[],
+            calculate_edge_lengths: true,
+            calculate_volumes: true,
+            optimize: true,
+        }
+    }
+}
+
+impl<'t> SoftBodySharedSettingsDesc<'t> {
+    pub fn new(
+        vertices: &'t [SoftBodyVertex],
+        faces: &'t [SoftBodyFace],
+        edges: &'t [SoftBodyEdge],
+        volumes: &'t [SoftBodyVolume],
+    ) -> SoftBodySharedSettingsDesc<'t> {
+        SoftBodySharedSettingsDesc {
+            vertices,
+            faces,
+            edges,
+            volumes,
+            ..Default::default()
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let num = self.vertices.len() as u32;
+        // Without materials Jolt uses a single default material.
+        let num_materials = self.materials.len().max(1) as u32;
+        self.faces
+            .iter()
+            .all(|f| f.vertex.iter().all(|v| *v < num) && f.material_index < num_materials)
+            && self.edges.iter().all(|e| e.vertex.iter().all(|v| *v < num))
+            && self.volumes.iter().all(|v| v.vertex.iter().all(|v| *v < num))
+    }
+}
+
+#[inline]
+pub fn create_soft_body_shared_settings(
+    settings: &SoftBodySharedSettingsDesc,
+) -> JoltResult<JRef<SoftBodySharedSettings>> {
+    create_soft_body_shared_settings_mut(settings).map(|s| s.into())
+}
+
+#[inline]
+pub fn create_soft_body_shared_settings_mut(
+    settings: &SoftBodySharedSettingsDesc,
+) -> JoltResult<JMut<SoftBodySharedSettings>> {
+    if settings.vertices.is_empty() || !settings.is_valid() {
+        return Err(JoltError::CreateSoftBody);
+    }
+    unsafe {
+        let ptr = ffi::CreateSoftBodySharedSettings(mem::transmute::<
+            &SoftBodySharedSettingsDesc,
+            &ffi::XSoftBodySharedSettings,
+        >(settings));
+        if ptr.is_null() {
+            return Err(JoltError::CreateSoftBody);
+        }
+        Ok(JMut::<SoftBodySharedSettings>::new_unchecked(ptr))
+    }
+}
+
+pub struct SoftBodySharedSettings(pub(crate) ffi::SoftBodySharedSettings);
+
+const_assert_eq!(mem::size_of::<JRef<SoftBodySharedSettings>>(), mem::size_of::<usize>());
+const_assert_eq!(mem::size_of::<Option<JRef<SoftBodySharedSettings>>>(), 8);
+const_assert_eq!(
+    unsafe { mem::transmute::<Option<JRef<SoftBodySharedSettings>>, usize>(None) },
+    0
+);
+
+impl fmt::Debug for SoftBodySharedSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoftBodySharedSettings")
+            .field("num_vertices", &self.get_num_vertices())
+            .field("num_faces", &self.get_num_faces())
+            .field("num_edges", &self.get_num_edges())
+            .field("num_volumes", &self.get_num_volumes())
+            .finish()
+    }
+}
+
+unsafe impl JRefTarget for SoftBodySharedSettings {
+    type JRaw = NonNull<SoftBodySharedSettings>;
+
+    #[inline]
+    fn name() -> &'static str {
+        "SoftBodySharedSettings"
+    }
+
+    #[inline]
+    unsafe fn make_ref(raw: &Self::JRaw) -> &Self {
+        unsafe { raw.as_ref() }
+    }
+
+    #[inline]
+    unsafe fn clone_raw(raw: &Self::JRaw) -> Self::JRaw {
+        NonNull::new_unchecked(ffi::CloneSoftBodySharedSettings(raw.as_ptr() as *mut _) as *mut _)
+    }
+
+    #[inline]
+    unsafe fn drop_raw(raw: &mut Self::JRaw) {
+        ffi::DropSoftBodySharedSettings(raw.as_ptr() as *mut _);
+    }
+
+    #[inline]
+    unsafe fn count_ref(raw: &Self::JRaw) -> u32 {
+        unsafe { ffi::CountRefSoftBodySharedSettings(raw.as_ptr() as *const _) }
+    }
+}
+
+unsafe impl JMutTarget for SoftBodySharedSettings {
+    #[inline]
+    unsafe fn make_mut(raw: &mut Self::JRaw) -> &mut Self {
+        unsafe { raw.as_mut() }
+    }
+
+    #[inline]
+    unsafe fn steal_raw(raw: &Self::JRaw) -> Self::JRaw {
+        *raw
+    }
+}
+
+impl JMut<SoftBodySharedSettings> {
+    #[inline]
+    pub(crate) unsafe fn new_unchecked(raw: *mut ffi::SoftBodySharedSettings) -> JMut<SoftBodySharedSettings> {
+        JMut(unsafe { NonNull::new_unchecked(raw as *mut _) })
+    }
+}
+
+impl SoftBodySharedSettings {
+    #[inline]
+    fn as_ref(&self) -> &ffi::SoftBodySharedSettings {
+        &self.0
+    }
+
+    #[inline]
+    pub fn get_num_vertices(&self) -> u32 {
+        ffi::GetSoftBodyNumVertices(self.as_ref())
+    }
+
+    #[inline]
+    pub fn get_num_faces(&self) -> u32 {
+        ffi::GetSoftBodyNumFaces(self.as_ref())
+    }
+
+    #[inline]
+    pub fn get_num_edges(&self) -> u32 {
+        ffi::GetSoftBodyNumEdges(self.as_ref())
+    }
+
+    #[inline]
+    pub fn get_num_volumes(&self) -> u32 {
+        ffi::GetSoftBodyNumVolumes(self.as_ref())
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct SoftBodyCreationSettings {
+    pub settings: Option<JRef<SoftBodySharedSettings>>,
+    pub position: Vec3A,
+    pub rotation: Quat,
+    pub user_data: u64,
+    pub object_layer: ObjectLayer,
+    pub num_iterations: u32,
+    pub linear_damping: f32,
+    pub max_linear_velocity: f32,
+    pub restitution: f32,
+    pub friction: f32,
+    pub pressure: f32,
+    pub gravity_factor: f32,
+    pub update_position: bool,
+    pub make_rotation_identity: bool,
+    pub allow_sleeping: bool,
+}
+const_assert_eq!(mem::size_of::<SoftBodyCreationSettings>(), 96);
+
+unsafe impl ExternType for SoftBodyCreationSettings {
+    type Id = type_id!("XSoftBodyCreationSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for SoftBodyCreationSettings {
+    fn default() -> SoftBodyCreationSettings {
+        SoftBodyCreationSettings {
+            settings: None,
+            position: Vec3A::ZERO,
+            rotation: Quat::IDENTITY,
+            user_data: 0,
+            object_layer: 0,
+            num_iterations: 5,
+            linear_damping: 0.1,
+            max_linear_velocity: 500.0,
+            restitution: 0.0,
+            friction: 0.2,
+            pressure: 0.0,
+            gravity_factor: 1.0,
+            update_position: true,
+            make_rotation_identity: true,
+            allow_sleeping: true,
+        }
+    }
+}
+
+impl SoftBodyCreationSettings {
+    pub fn new(
+        settings: JRef<SoftBodySharedSettings>,
+        layer: ObjectLayer,
+        position: Vec3A,
+        rotation: Quat,
+    ) -> SoftBodyCreationSettings {
+        SoftBodyCreationSettings {
+            settings: Some(settings),
+            position,
+            rotation,
+            object_layer: layer,
+            ..Default::default()
+        }
+    }
+}
+
+impl BodyInterface {
+    pub fn create_soft_body(&mut self, settings: &SoftBodyCreationSettings) -> JoltResult<BodyID> {
+        if settings.settings.is_none() {
+            return Err(JoltError::CreateSoftBody);
+        }
+        let body_id = ffi::CreateSoftBody(self.as_mut(), settings);
+        if body_id.is_invalid() {
+            return Err(JoltError::CreateBody);
+        }
+        Ok(body_id)
+    }
+
+    pub fn create_add_soft_body(&mut self, settings: &SoftBodyCreationSettings, active: bool) -> JoltResult<BodyID> {
+        if settings.settings.is_none() {
+            return Err(JoltError::CreateSoftBody);
+        }
+        let body_id = ffi::CreateAddSoftBody(self.as_mut(), settings, active.into());
+        if body_id.is_invalid() {
+            return Err(JoltError::CreateBody);
+        }
+        Ok(body_id)
+    }
+}
+
+impl Body {
+    /// Copies the deformed vertex positions of a soft body into `vertices`.
+    /// Returns false if this is not a soft body.
+    #[inline]
+    pub fn get_soft_body_vertices(&self, vertices: &mut Vec<Vec3A>, world_space: bool) -> bool {
+        ffi::GetSoftBodyVertices(self.as_ref(), world_space, unsafe {
+            mem::transmute::<&mut Vec<Vec3A>, &mut Vec<JVec3>>(vertices)
+        })
+    }
+}
+
+impl<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener> PhysicsSystem<CL, BAL, SBCL> {
+    /// Copies the deformed vertex positions of a soft body into `vertices`, locking the body.
+    /// Returns false if the body doesn't exist or is not a soft body.
+    #[inline]
+    pub fn get_soft_body_vertices(&self, body_id: BodyID, vertices: &mut Vec<Vec3A>, world_space: bool) -> bool {
+        ffi::GetSoftBodyVerticesByID(self.as_x_ref(), &body_id, world_space, unsafe {
+            mem::transmute::<&mut Vec<Vec3A>, &mut Vec<JVec3>>(vertices)
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SoftBodyContactSettings {
+    /// Scale factor for the inverse mass of the soft body vertices.
+    pub inv_mass_scale1: f32,
+    pub inv_mass_scale2: f32,
+    pub inv_inertia_scale2: f32,
+    pub is_sensor: bool,
+}
+const_assert_eq!(mem::size_of::<SoftBodyContactSettings>(), 16);
+
+unsafe impl ExternType for SoftBodyContactSettings {
+    type Id = type_id!("SoftBodyContactSettings");
+    type Kind = kind::Trivial;
+}
+
+impl Default for SoftBodyContactSettings {
+    fn default() -> SoftBodyContactSettings {
+        SoftBodyContactSettings {
+            inv_mass_scale1: 1.0,
+            inv_mass_scale2: 1.0,
+            inv_inertia_scale2: 1.0,
+            is_sensor: false,
+        }
+    }
+}
+
+pub struct SoftBodyManifold(ffi::SoftBodyManifold);
+
+impl fmt::Debug for SoftBodyManifold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoftBodyManifold")
+            .field("num_vertices", &self.get_num_vertices())
+            .finish()
+    }
+}
+
+impl SoftBodyManifold {
+    #[inline]
+    fn as_ref(&self) -> &ffi::SoftBodyManifold {
+        &self.0
+    }
+
+    #[inline]
+    pub fn get_num_vertices(&self) -> u32 {
+        ffi::GetSoftBodyManifoldNumVertices(self.as_ref())
+    }
+
+    /// Vertex position in the local space of the soft body.
+    #[inline]
+    pub fn get_vertex_position(&self, index: u32) -> Vec3A {
+        assert!(index < self.get_num_vertices());
+        ffi::GetSoftBodyManifoldVertexPosition(self.as_ref(), index).into()
+    }
+
+    #[inline]
+    pub fn has_contact(&self, index: u32) -> bool {
+        assert!(index < self.get_num_vertices());
+        ffi::SoftBodyManifoldHasContact(self.as_ref(), index)
+    }
+
+    #[inline]
+    pub fn get_local_contact_point(&self, index: u32) -> Vec3A {
+        assert!(index < self.get_num_vertices());
+        ffi::GetSoftBodyManifoldLocalContactPoint(self.as_ref(), index).into()
+    }
+
+    #[inline]
+    pub fn get_contact_normal(&self, index: u32) -> Vec3A {
+        assert!(index < self.get_num_vertices());
+        ffi::GetSoftBodyManifoldContactNormal(self.as_ref(), index).into()
+    }
+
+    #[inline]
+    pub fn get_contact_body_id(&self, index: u32) -> BodyID {
+        assert!(index < self.get_num_vertices());
+        ffi::GetSoftBodyManifoldContactBodyID(self.as_ref(), index)
+    }
+}
+
+#[vtable(allow_empty)]
+#[repr(C)]
+pub struct SoftBodyContactListenerVTable {
+    pub drop: extern "C" fn(*mut u8),
+    pub on_soft_body_contact_validate: extern "C" fn(
+        *const u8,
+        soft_body: &Body,
+        other_body: &Body,
+        settings: &mut SoftBodyContactSettings,
+    ) -> SoftBodyValidateResult,
+    pub on_soft_body_contact_added: extern "C" fn(*const u8, soft_body: &Body, manifold: &SoftBodyManifold),
+}
+
+#[cfg(test)]
+mod tests {
+    use jolt_macros::vdata;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::body::{BodyCreationSettings, MotionType};
+    use crate::shape::{create_box_shape, BoxShapeSettings};
+    use crate::system::PhysicsSystemConfig;
+    use crate::test_callback::create_test_system;
+
+    use crate as jolt_physics_rs;
+
+    /// A 3x3 vertex cloth in the XZ plane, optionally with the first vertex pinned.
+    fn cloth_vertices(pinned: bool) -> Vec<SoftBodyVertex> {
+        (0..9)
+            .map(|idx| {
+                let inv_mass = if pinned && idx == 0 { 0.0 } else { 1.0 };
+                SoftBodyVertex::new(Vec3::new((idx % 3) as f32, 0.0, (idx / 3) as f32), inv_mass)
+            })
+            .collect()
+    }
+
+    fn cloth_faces(material_index: u32) -> Vec<SoftBodyFace> {
+        let mut faces = Vec::new();
+        for z in 0..2 {
+            for x in 0..2 {
+                let v = z * 3 + x;
+                faces.push(SoftBodyFace::new(v, v + 3, v + 1, material_index));
+                faces.push(SoftBodyFace::new(v + 1, v + 3, v + 4, material_index));
+            }
+        }
+        faces
+    }
+
+    fn cloth_edges(faces: &[SoftBodyFace]) -> Vec<SoftBodyEdge> {
+        let mut edges: Vec<_> = (faces.iter())
+            .flat_map(|f| {
+                [
+                    (f.vertex[0], f.vertex[1]),
+                    (f.vertex[1], f.vertex[2]),
+                    (f.vertex[2], f.vertex[0]),
+                ]
+            })
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        edges.sort();
+        edges.dedup();
+        edges.into_iter().map(|(a, b)| SoftBodyEdge::new(a, b, 0.0)).collect()
+    }
+
+    #[test]
+    fn test_soft_body_vertices() {
+        let vertices = cloth_vertices(true);
+        let bad_faces = cloth_faces(1);
+        let bad = SoftBodySharedSettingsDesc::new(&vertices, &bad_faces, &[], &[]);
+        assert!(create_soft_body_shared_settings(&bad).is_err());
+
+        let faces = cloth_faces(0);
+        let edges = cloth_edges(&faces);
+        let desc = SoftBodySharedSettingsDesc::new(&vertices, &faces, &edges, &[]);
+        let shared = create_soft_body_shared_settings(&desc).unwrap();
+        assert_eq!(shared.get_num_vertices(), 9);
+        assert_eq!(shared.get_num_faces(), 8);
+
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let position = Vec3A::new(0.0, 5.0, 0.0);
+        let settings = SoftBodyCreationSettings {
+            update_position: false,
+            ..SoftBodyCreationSettings::new(shared, 0, position, Quat::IDENTITY)
+        };
+        let body_id = system.body_itf().create_add_soft_body(&settings, true).unwrap();
+
+        let mut read = Vec::new();
+        assert!(system.get_soft_body_vertices(body_id, &mut read, true));
+        assert_eq!(read.len(), 9);
+        for (vertex, read) in vertices.iter().zip(&read) {
+            assert!((Vec3A::from(vertex.position) + position - *read).length() < 1e-4);
+        }
+
+        for _ in 0..30 {
+            system.update(1.0 / 60.0, 1).unwrap();
+        }
+        assert!(system.get_soft_body_vertices(body_id, &mut read, true));
+        assert!((read[0] - position).length() < 1e-4);
+        assert!(read[8].y < position.y - 0.1);
+        assert!(!system.get_soft_body_vertices(BodyID::INVALID, &mut read, true));
+    }
+
+    #[vdata(SoftBodyContactListenerVTable)]
+    struct ScaleSbContactListener {
+        inv_mass_scale2: f32,
+        added: AtomicU32,
+    }
+
+    impl SoftBodyContactListener for ScaleSbContactListener {
+        fn on_soft_body_contact_validate(
+            &self,
+            _soft_body: &Body,
+            _other_body: &Body,
+            settings: &mut SoftBodyContactSettings,
+        ) -> SoftBodyValidateResult {
+            settings.inv_mass_scale2 = self.inv_mass_scale2;
+            SoftBodyValidateResult::AcceptContact
+        }
+
+        fn on_soft_body_contact_added(&self, _soft_body: &Body, manifold: &SoftBodyManifold) {
+            if (0..manifold.get_num_vertices()).any(|idx| manifold.has_contact(idx)) {
+                self.added.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Drops a cloth on a weightless light box, returns the box velocity and the number of contact callbacks.
+    fn drop_cloth_on_box(inv_mass_scale2: f32) -> (Vec3A, u32) {
+        let mut system: PhysicsSystem<(), (), ScaleSbContactListener> =
+            create_test_system(&PhysicsSystemConfig::default());
+        system.set_soft_body_contact_listener(Some(ScaleSbContactListener::new_vbox(ScaleSbContactListener {
+            inv_mass_scale2,
+            added: AtomicU32::new(0),
+        })));
+
+        let shape = create_box_shape(&BoxShapeSettings {
+            density: 10.0,
+            ..BoxShapeSettings::new(1.0, 0.1, 1.0)
+        })
+        .unwrap();
+        let box_settings = BodyCreationSettings {
+            gravity_factor: 0.0,
+            allow_sleeping: false,
+            ..BodyCreationSettings::new(shape, 0, MotionType::Dynamic, Vec3A::new(1.0, 0.0, 1.0), Quat::IDENTITY)
+        };
+        let box_id = system.body_itf().create_add_body(&box_settings, true).unwrap();
+
+        let vertices = cloth_vertices(false);
+        let faces = cloth_faces(0);
+        let edges = cloth_edges(&faces);
+        let desc = SoftBodySharedSettingsDesc::new(&vertices, &faces, &edges, &[]);
+        let shared = create_soft_body_shared_settings(&desc).unwrap();
+        let cloth_settings = SoftBodyCreationSettings::new(shared, 0, Vec3A::new(0.0, 0.5, 0.0), Quat::IDENTITY);
+        system.body_itf().create_add_soft_body(&cloth_settings, true).unwrap();
+
+        for _ in 0..30 {
+            system.update(1.0 / 60.0, 1).unwrap();
+        }
+        let velocity = system.body_itf().get_linear_velocity(box_id);
+        let added = system
+            .get_soft_body_contact_listener()
+            .unwrap()
+            .added
+            .load(Ordering::Relaxed);
+        (velocity, added)
+    }
+
+    #[test]
+    fn test_soft_body_contact_inv_mass_scale() {
+        let (velocity, added) = drop_cloth_on_box(1.0);
+        assert!(added > 0);
+        assert!(velocity.y < -0.01);
+
+        // The box acts as if it had infinite mass.
+        let (velocity, added) = drop_cloth_on_box(0.0);
+        assert!(added > 0);
+        assert!(velocity.length() < 1e-4);
+    }
+}
@@ -2,7 +2,10 @@
 #include "jolt-physics-rs/src/shape.rs.h"
 #include "jolt-physics-rs/src/system.rs.h"
 
+#include <algorithm>
+
 static_assert(sizeof(PhysicsSettings) == 84, "PhysicsSettings size");
+static_assert(sizeof(PhysicsStepListenerContext) == 16, "PhysicsStepListenerContext size");
 static_assert(sizeof(CollideShapeResult) == 1120, "CollideShapeResult size");
 static_assert(sizeof(ContactManifold) == 2128, "ContactManifold size");
 
@@ -36,6 +39,7 @@ void GlobalInitialize() {
 	JPH_IF_ENABLE_ASSERTS(AssertFailed = AssertFailedImpl;)
 	Factory::sInstance = new Factory();
 	RegisterTypes();
+	Factory::sInstance->Register(JPH_RTTI(XPhysicsMaterial));
 }
 
 void GlobalFinalize() {
@@ -50,25 +54,50 @@ void GlobalFinalize() {
 // PhysicsSystem
 //
 
+struct XPhysicsSystemConfig {
+	int32 numThreads;
+	uint32 maxJobs;
+	uint32 maxBarriers;
+	uint32 maxBodies;
+	uint32 numBodyMutexes;
+	uint32 maxBodyPairs;
+	uint32 maxContactConstraints;
+	uint32 tempAllocatorSize;
+};
+static_assert(sizeof(XPhysicsSystemConfig) == 32, "XPhysicsSystemConfig size");
+
+static JobSystem* CreateJobSystem(const XPhysicsSystemConfig& config) {
+	if (config.numThreads == 0) {
+		return new JobSystemSingleThreaded(config.maxJobs);
+	} else {
+		return new JobSystemThreadPool(config.maxJobs, config.maxBarriers, (int)config.numThreads);
+	}
+}
+
 XPhysicsSystem::XPhysicsSystem(
+	const XPhysicsSystemConfig& config,
 	rust::Fn<void (XPhysicsSystem&)> rustCleanUp,
 	const BroadPhaseLayerInterface* bpli,
 	const ObjectVsBroadPhaseLayerFilter* obplf,
 	const ObjectLayerPairFilter* olpf
 ):
-	_allocator(TempAllocatorImpl(10 * 1024 * 1024)),
-	_jobSys(JobSystemSingleThreaded(cMaxPhysicsJobs)),
+	_allocator(TempAllocatorImpl(config.tempAllocatorSize)),
+	_jobSys(CreateJobSystem(config)),
 	_phySys(PhysicsSystem()),
 	_rustCleanUp(rustCleanUp),
 	_bpli(bpli),
 	_obplf(obplf),
 	_olpf(olpf)
 {
-	const uint cMaxBodies = 20480;
-	const uint cNumBodyMutexes = 0;
-	const uint cMaxBodyPairs = 20480;
-	const uint cMaxContactConstraints = 5120;
-	_phySys.Init(cMaxBodies, cNumBodyMutexes, cMaxBodyPairs, cMaxContactConstraints, *bpli, *obplf, *olpf);
+	_phySys.Init(
+		config.maxBodies,
+		config.numBodyMutexes,
+		config.maxBodyPairs,
+		config.maxContactConstraints,
+		*bpli,
+		*obplf,
+		*olpf
+	);
 }
 
 XPhysicsSystem::~XPhysicsSystem() {
@@ -108,6 +137,93 @@ void XPhysicsSystem::GetActiveBodies(EBodyType bodyType, rust::Vec<BodyID>& bodi
 	}
 }
 
+void XPhysicsSystem::RemoveCharacterVirtual(XCharacterVirtual* character) {
+	auto iter = find(this->_characters.begin(), this->_characters.end(), character);
+	if (iter != this->_characters.end()) {
+		this->_characters.erase(iter);
+	}
+}
+
+void XPhysicsSystem::SaveState(EStateRecorderState state, const StateRecorderFilter* filter, rust::Vec<uint8>& data) const {
+	StateRecorderImpl recorder;
+	this->_phySys.SaveState(recorder, state, filter);
+
+	// CharacterVirtual is not part of PhysicsSystem, append the live ones sorted by id
+	Array<const XCharacterVirtual*> characters(this->_characters.begin(), this->_characters.end());
+	sort(characters.begin(), characters.end(), [](const XCharacterVirtual* a, const XCharacterVirtual* b) {
+		return a->GetID().GetValue() < b->GetID().GetValue();
+	});
+	recorder.Write((uint32)characters.size());
+	for (const XCharacterVirtual* character : characters) {
+		recorder.Write(character->GetID().GetValue());
+		character->CharacterVirtual::SaveState(recorder);
+	}
+
+	StateRecorderToVec(recorder, data);
+}
+
+bool XPhysicsSystem::RestoreState(rust::Slice<const uint8> data) {
+	StateRecorderImpl recorder;
+	StateRecorderFromSlice(recorder, data);
+	if (!this->_phySys.RestoreState(recorder)) {
+		return false;
+	}
+
+	uint32 count = 0;
+	recorder.Read(count);
+	for (uint32 idx = 0; idx < count && !recorder.IsFailed(); ++idx) {
+		uint32 id = 0;
+		recorder.Read(id);
+		auto iter = find_if(this->_characters.begin(), this->_characters.end(), [id](const XCharacterVirtual* character) {
+			return character->GetID().GetValue() == id;
+		});
+		if (iter == this->_characters.end()) {
+			return false;
+		}
+		(*iter)->CharacterVirtual::RestoreState(recorder);
+	}
+	return !recorder.IsFailed();
+}
+
+static uint64 HashFloats(uint64 hash, const float* values, uint count) {
+	for (uint i = 0; i < count; ++i) {
+		hash = HashBytes(&values[i], sizeof(float), hash);
+	}
+	return hash;
+}
+
+uint64 XPhysicsSystem::StateHash() const {
+	BodyIDVector bodyIds;
+	this->_phySys.GetBodies(bodyIds);
+	const BodyLockInterfaceNoLock& lockItf = this->_phySys.GetBodyLockInterfaceNoLock();
+
+	uint64 hash = HashBytes(nullptr, 0);
+	for (BodyID bodyId : bodyIds) {
+		BodyLockRead lock(lockItf, bodyId);
+		if (!lock.Succeeded()) {
+			continue;
+		}
+		const Body& body = lock.GetBody();
+		uint32 id = bodyId.GetIndexAndSequenceNumber();
+		hash = HashBytes(&id, sizeof(id), hash);
+
+		RVec3 pos = body.GetPosition();
+		Real position[3] = { pos.GetX(), pos.GetY(), pos.GetZ() };
+		hash = HashBytes(position, sizeof(position), hash);
+		Quat rot = body.GetRotation();
+		float rotation[4] = { rot.GetX(), rot.GetY(), rot.GetZ(), rot.GetW() };
+		hash = HashFloats(hash, rotation, 4);
+		Vec3 lv = body.GetLinearVelocity();
+		Vec3 av = body.GetAngularVelocity();
+		float velocity[6] = { lv.GetX(), lv.GetY(), lv.GetZ(), av.GetX(), av.GetY(), av.GetZ() };
+		hash = HashFloats(hash, velocity, 6);
+
+		uint8 active = body.IsActive() ? 1 : 0;
+		hash = HashBytes(&active, sizeof(active), hash);
+	}
+	return hash;
+}
+
 #if defined(JPH_DEBUG_RENDERER)
 void XPhysicsSystem::DebugRender(DebugRenderer* debugRenderer) {
 	for (auto renderable : this->_renderables) {
@@ -117,15 +233,62 @@ void XPhysicsSystem::DebugRender(DebugRenderer* debugRenderer) {
 #endif
 
 XPhysicsSystem* CreatePhysicSystem(
+	const XPhysicsSystemConfig& config,
 	rust::Fn<void (XPhysicsSystem&)> rustCleanUp,
 	const BroadPhaseLayerInterface* bpli,
 	const ObjectVsBroadPhaseLayerFilter* obplf,
 	const ObjectLayerPairFilter* olpf
 ) {
-	Ref<XPhysicsSystem> system = Ref(new XPhysicsSystem(rustCleanUp, bpli, obplf, olpf));
+	Ref<XPhysicsSystem> system = Ref(new XPhysicsSystem(config, rustCleanUp, bpli, obplf, olpf));
 	return LeakRefT<XPhysicsSystem>(system);
 }
 
+static bool IsSystemBody(const XPhysicsSystem* system, const Body* body) {
+	return body == &Body::sFixedToWorld || system->PhySys().GetBodyLockInterfaceNoLock().TryGetBody(body->GetID()) == body;
+}
+
+bool XPhysicsSystem::AddConstraint(Constraint* constraint) {
+	if (this->_constraints.count(constraint) != 0) {
+		return false;
+	}
+	// The constraint may belong to another system, its bodies tell
+	if (constraint->GetSubType() == EConstraintSubType::Vehicle) {
+		if (!IsSystemBody(this, static_cast<VehicleConstraint*>(constraint)->GetVehicleBody())) {
+			return false;
+		}
+	} else if (constraint->GetType() == EConstraintType::TwoBodyConstraint) {
+		TwoBodyConstraint* twoBody = static_cast<TwoBodyConstraint*>(constraint);
+		if (!IsSystemBody(this, twoBody->GetBody1()) || !IsSystemBody(this, twoBody->GetBody2())) {
+			return false;
+		}
+	}
+	this->_constraints.insert(constraint);
+	this->_phySys.AddConstraint(constraint);
+	// Vehicles must also be stepped, otherwise their wheels are never updated
+	if (constraint->GetSubType() == EConstraintSubType::Vehicle) {
+		this->_phySys.AddStepListener(static_cast<VehicleConstraint*>(constraint));
+	}
+	return true;
+}
+
+bool XPhysicsSystem::RemoveConstraint(Constraint* constraint) {
+	if (this->_constraints.erase(constraint) == 0) {
+		return false;
+	}
+	// Drop the step listener first, the system may hold the last reference to the vehicle
+	if (constraint->GetSubType() == EConstraintSubType::Vehicle) {
+		this->_phySys.RemoveStepListener(static_cast<VehicleConstraint*>(constraint));
+	}
+	this->_phySys.RemoveConstraint(constraint);
+	return true;
+}
+
+void XPhysicsSystem::RemoveAllConstraints() {
+	while (!this->_constraints.empty()) {
+		this->RemoveConstraint(*this->_constraints.begin());
+	}
+}
+
 //
 // BodyInterface
 //
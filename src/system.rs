@@ -13,12 +13,26 @@ use crate::base::{
     StaticArray, SubShapeID, ValidateResult,
 };
 use crate::body::{Body, BodyCreationSettings};
+use crate::constraint::Constraint;
+use crate::consts::{MAX_PHYSICS_BARRIERS, MAX_PHYSICS_JOBS};
 use crate::error::{JoltError, JoltResult};
 use crate::shape::Shape;
+use crate::soft_body::{SoftBodyContactListener, SoftBodyContactListenerVTable};
 use crate::vtable::{VBox, VPair};
 
 #[cxx::bridge()]
 pub(crate) mod ffi {
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum StateRecorderState {
+        None = 0,
+        Global = 1,
+        Bodies = 2,
+        Contacts = 4,
+        Constraints = 8,
+        All = 15,
+    }
+
     unsafe extern "C++" {
         include!("rust/cxx.h");
         include!("jolt-physics-rs/src/ffi.h");
@@ -27,6 +41,7 @@ pub(crate) mod ffi {
         type MotionType = crate::base::ffi::MotionType;
         type MotionQuality = crate::base::ffi::MotionQuality;
         type Activation = crate::base::ffi::Activation;
+        type StateRecorderState;
 
         type Vec3 = crate::base::ffi::Vec3;
         type Quat = crate::base::ffi::Quat;
@@ -34,7 +49,9 @@ pub(crate) mod ffi {
         type AABox = crate::base::ffi::AABox;
         type BodyID = crate::base::ffi::BodyID;
         type Shape = crate::shape::ffi::Shape;
+        type Constraint = crate::constraint::ffi::Constraint;
 
+        type XPhysicsSystemConfig = crate::system::PhysicsSystemConfig;
         type PhysicsSettings = crate::system::PhysicsSettings;
         type XBodyStats = crate::system::BodyStats;
         #[allow(dead_code)]
@@ -51,12 +68,16 @@ pub(crate) mod ffi {
         type ObjectLayerPairFilter;
         type BodyActivationListener;
         type ContactListener;
+        type SoftBodyContactListener;
+        type PhysicsStepListener;
+        type StateRecorderFilter;
 
         fn GlobalInitialize();
         fn GlobalFinalize();
 
         type XPhysicsSystem;
         unsafe fn CreatePhysicSystem(
+            config: &XPhysicsSystemConfig,
             clean_up: fn(zelf: Pin<&mut XPhysicsSystem>),
             bpli: *const BroadPhaseLayerInterface,
             obplf: *const ObjectVsBroadPhaseLayerFilter,
@@ -65,6 +86,9 @@ pub(crate) mod ffi {
         unsafe fn DropXPhysicsSystem(system: *mut XPhysicsSystem);
         unsafe fn CloneXPhysicsSystem(system: *mut XPhysicsSystem) -> *mut XPhysicsSystem;
         unsafe fn CountRefXPhysicsSystem(system: *const XPhysicsSystem) -> u32;
+        unsafe fn AddSystemConstraint(system: *mut XPhysicsSystem, constraint: *mut Constraint) -> bool;
+        unsafe fn RemoveSystemConstraint(system: *mut XPhysicsSystem, constraint: *mut Constraint) -> bool;
+        unsafe fn RemoveAllSystemConstraints(system: *mut XPhysicsSystem);
         fn GetPhysicsSystem(self: Pin<&mut XPhysicsSystem>) -> *mut PhysicsSystem;
         unsafe fn GetBodyInterface(self: Pin<&mut XPhysicsSystem>, lock: bool) -> *mut XBodyInterface;
         unsafe fn GetBroadPhaseLayerInterface(self: &XPhysicsSystem) -> *const BroadPhaseLayerInterface;
@@ -73,17 +97,25 @@ pub(crate) mod ffi {
         fn Update(self: Pin<&mut XPhysicsSystem>, delta: f32, step: u32) -> u32;
         fn GetBodies(self: &XPhysicsSystem, bodies: &mut Vec<BodyID>);
         fn GetActiveBodies(self: &XPhysicsSystem, body_type: BodyType, bodies: &mut Vec<BodyID>);
+        unsafe fn SaveState(
+            self: &XPhysicsSystem,
+            state: StateRecorderState,
+            filter: *const StateRecorderFilter,
+            data: &mut Vec<u8>,
+        );
+        fn RestoreState(self: Pin<&mut XPhysicsSystem>, data: &[u8]) -> bool;
+        fn StateHash(self: &XPhysicsSystem) -> u64;
 
         type PhysicsSystem;
         unsafe fn SetBodyActivationListener(self: Pin<&mut PhysicsSystem>, listener: *mut BodyActivationListener);
         unsafe fn GetBodyActivationListener(self: &PhysicsSystem) -> *mut BodyActivationListener;
         unsafe fn SetContactListener(self: Pin<&mut PhysicsSystem>, inListener: *mut ContactListener);
         unsafe fn GetContactListener(self: &PhysicsSystem) -> *mut ContactListener;
-        // void SetSoftBodyContactListener(SoftBodyContactListener *inListener);
-        // SoftBodyContactListener* GetSoftBodyContactListener() const;
-        // void SetCombineFriction(ContactConstraintManager::CombineFunction inCombineFriction);
+        unsafe fn SetSoftBodyContactListener(self: Pin<&mut PhysicsSystem>, inListener: *mut SoftBodyContactListener);
+        unsafe fn GetSoftBodyContactListener(self: &PhysicsSystem) -> *mut SoftBodyContactListener;
+        unsafe fn SetPhysicsSystemCombineFriction(system: Pin<&mut PhysicsSystem>, func: *const u8);
         // ContactConstraintManager::CombineFunction GetCombineFriction() const;
-        // void SetCombineRestitution(ContactConstraintManager::CombineFunction inCombineRestition);
+        unsafe fn SetPhysicsSystemCombineRestitution(system: Pin<&mut PhysicsSystem>, func: *const u8);
         // ContactConstraintManager::CombineFunction GetCombineRestitution() const;
         fn SetPhysicsSettings(self: Pin<&mut PhysicsSystem>, settings: &PhysicsSettings);
         fn GetPhysicsSettings(self: &PhysicsSystem) -> &PhysicsSettings;
@@ -97,16 +129,19 @@ pub(crate) mod ffi {
         // const NarrowPhaseQuery & GetNarrowPhaseQuery() const;
         // const NarrowPhaseQuery & GetNarrowPhaseQueryNoLock() const;
         fn OptimizeBroadPhase(self: Pin<&mut PhysicsSystem>);
+        unsafe fn AddConstraint(self: Pin<&mut PhysicsSystem>, constraint: *mut Constraint);
+        unsafe fn RemoveConstraint(self: Pin<&mut PhysicsSystem>, constraint: *mut Constraint);
         fn GetGravity(self: &PhysicsSystem) -> Vec3;
         fn SetGravity(self: Pin<&mut PhysicsSystem>, gravity: Vec3);
-        // void AddStepListener(PhysicsStepListener *inListener);
-        // void RemoveStepListener(PhysicsStepListener *inListener);
+        unsafe fn AddStepListener(self: Pin<&mut PhysicsSystem>, listener: *mut PhysicsStepListener);
+        unsafe fn RemoveStepListener(self: Pin<&mut PhysicsSystem>, listener: *mut PhysicsStepListener);
         fn GetNumBodies(self: &PhysicsSystem) -> u32;
         fn GetNumActiveBodies(self: &PhysicsSystem, body_type: BodyType) -> u32;
         fn GetMaxBodies(self: &PhysicsSystem) -> u32;
         fn GetBodyStats(self: &PhysicsSystem) -> XBodyStats;
         fn WereBodiesInContact(self: &PhysicsSystem, body1: &BodyID, body2: &BodyID) -> bool;
         fn GetBounds(self: &PhysicsSystem) -> AABox;
+        fn GetPhysicsSystemBodyInterface(system: Pin<&mut PhysicsSystem>) -> *mut XBodyInterface;
 
         type XBodyInterface;
         type BodyCreationSettings;
@@ -267,8 +302,59 @@ pub(crate) mod ffi {
     }
 }
 
+pub type StateRecorderState = ffi::StateRecorderState;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicsSystemConfig {
+    /// Worker threads of the job system. 0 runs all jobs on the calling thread, -1 uses all CPU cores.
+    /// With a thread pool, contact and activation listeners are called in parallel.
+    pub num_threads: i32,
+    pub max_jobs: u32,
+    pub max_barriers: u32,
+    pub max_bodies: u32,
+    /// Number of body mutexes, 0 picks a default based on the number of CPU cores.
+    pub num_body_mutexes: u32,
+    pub max_body_pairs: u32,
+    pub max_contact_constraints: u32,
+    pub temp_allocator_size: u32,
+}
+const_assert_eq!(mem::size_of::<PhysicsSystemConfig>(), 32);
+
+unsafe impl ExternType for PhysicsSystemConfig {
+    type Id = type_id!("XPhysicsSystemConfig");
+    type Kind = kind::Trivial;
+}
+
+impl Default for PhysicsSystemConfig {
+    fn default() -> Self {
+        PhysicsSystemConfig {
+            num_threads: 0,
+            max_jobs: MAX_PHYSICS_JOBS,
+            max_barriers: MAX_PHYSICS_BARRIERS,
+            max_bodies: 20480,
+            num_body_mutexes: 0,
+            max_body_pairs: 20480,
+            max_contact_constraints: 5120,
+            temp_allocator_size: 10 * 1024 * 1024,
+        }
+    }
+}
+
+impl PhysicsSystemConfig {
+    #[inline]
+    pub fn new_thread_pool(num_threads: i32) -> PhysicsSystemConfig {
+        PhysicsSystemConfig {
+            num_threads,
+            ..Default::default()
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PhysicsSettings {
     pub max_in_flight_body_pairs: i32,
     pub step_listeners_batch_size: i32,
@@ -397,6 +483,8 @@ unsafe impl ExternType for ContactManifold {
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ContactSettings {
     pub combined_friction: f32,
     pub combined_restitution: f32,
@@ -468,28 +556,75 @@ pub fn global_finalize() {
     JOLT_INITED.store(false, Ordering::SeqCst);
 }
 
+/// Jolt keeps a single global `DebugRenderer`, owned by a `DebugFrameRecorder` or the debug application.
+#[cfg(any(feature = "debug-frame", feature = "debug-renderer"))]
+pub(crate) static DEBUG_RENDERER_ALIVE: AtomicBool = AtomicBool::new(false);
+
 //
 // PhysicsSystem
 //
 
-pub struct PhysicsSystem<CL: ContactListener = (), BAL: BodyActivationListener = ()> {
+pub struct PhysicsSystem<CL: ContactListener = (), BAL: BodyActivationListener = (), SBCL: SoftBodyContactListener = ()>
+{
     inner: Box<PhysicsSystemInner>,
     cl_phantom: PhantomData<CL>,
     bal_phantom: PhantomData<BAL>,
+    sbcl_phantom: PhantomData<SBCL>,
 }
 
 struct PhysicsSystemInner {
     x_system: NonNull<ffi::XPhysicsSystem>,
     raw_system: NonNull<ffi::PhysicsSystem>,
+    step_listeners: Vec<StepListenerEntry>,
+    step_listener_counter: u32,
+}
+
+struct StepListenerEntry {
+    id: StepListenerID,
+    listener: *mut u8,
+    drop: unsafe fn(*mut u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StepListenerID(u32);
+
+unsafe fn drop_step_listener<SL: PhysicsStepListener>(listener: *mut u8) {
+    let _ = VBox::<SL, PhysicsStepListenerVTable>::from_raw(listener as *mut _);
 }
 
-impl<CL: ContactListener, BAL: BodyActivationListener> fmt::Debug for PhysicsSystem<CL, BAL> {
+#[allow(improper_ctypes_definitions)]
+extern "C" fn combine_function<F: Fn(&Body, SubShapeID, &Body, SubShapeID) -> f32 + Copy>(
+    body1: &Body,
+    sub_shape_id1: &SubShapeID,
+    body2: &Body,
+    sub_shape_id2: &SubShapeID,
+) -> f32 {
+    // F is zero sized, checked at compile time in make_combine_function().
+    let func: F = unsafe { mem::zeroed() };
+    func(body1, *sub_shape_id1, body2, *sub_shape_id2)
+}
+
+fn make_combine_function<F: Fn(&Body, SubShapeID, &Body, SubShapeID) -> f32 + Copy>(_func: F) -> *const u8 {
+    const {
+        assert!(
+            mem::size_of::<F>() == 0,
+            "Combine function must be a fn item or a closure without captures"
+        )
+    };
+    combine_function::<F> as *const u8
+}
+
+impl<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener> fmt::Debug
+    for PhysicsSystem<CL, BAL, SBCL>
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PhysicsSystem").finish()
     }
 }
 
-impl<CL: ContactListener, BAL: BodyActivationListener> Drop for PhysicsSystem<CL, BAL> {
+impl<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener> Drop
+    for PhysicsSystem<CL, BAL, SBCL>
+{
     fn drop(&mut self) {
         #[cfg(feature = "debug-print")]
         println!(
@@ -511,23 +646,56 @@ impl<CL: ContactListener, BAL: BodyActivationListener> Drop for PhysicsSystem<CL
             }
             self.as_raw_mut().SetBodyActivationListener(ptr::null_mut());
 
+            let sbcl = self.as_raw_ref().GetSoftBodyContactListener();
+            if !sbcl.is_null() {
+                let _ = VBox::<SBCL, SoftBodyContactListenerVTable>::from_raw(sbcl as *mut _);
+            }
+            self.as_raw_mut().SetSoftBodyContactListener(ptr::null_mut());
+
+            for entry in mem::take(&mut self.inner.step_listeners) {
+                self.as_raw_mut().RemoveStepListener(entry.listener as *mut _);
+                (entry.drop)(entry.listener);
+            }
+
+            // Constraints keep the system alive, the added ones would form a cycle.
+            ffi::RemoveAllSystemConstraints(self.as_x_ptr());
+
             ffi::DropXPhysicsSystem(self.as_x_ptr());
         }
     }
 }
 
-impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
+impl<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener> PhysicsSystem<CL, BAL, SBCL> {
+    #[inline]
     pub fn new<BPLI: BroadPhaseLayerInterface, OBPLF: ObjectVsBroadPhaseLayerFilter, OLPF: ObjectLayerPairFilter>(
         bpli: VBox<BPLI, BroadPhaseLayerInterfaceVTable>,
         obplf: VBox<OBPLF, ObjectVsBroadPhaseLayerFilterVTable>,
         olpf: VBox<OLPF, ObjectLayerPairFilterVTable>,
-    ) -> PhysicsSystem<CL, BAL> {
+    ) -> PhysicsSystem<CL, BAL, SBCL> {
+        Self::new_with_config(&PhysicsSystemConfig::default(), bpli, obplf, olpf)
+    }
+
+    pub fn new_with_config<
+        BPLI: BroadPhaseLayerInterface,
+        OBPLF: ObjectVsBroadPhaseLayerFilter,
+        OLPF: ObjectLayerPairFilter,
+    >(
+        config: &PhysicsSystemConfig,
+        bpli: VBox<BPLI, BroadPhaseLayerInterfaceVTable>,
+        obplf: VBox<OBPLF, ObjectVsBroadPhaseLayerFilterVTable>,
+        olpf: VBox<OLPF, ObjectLayerPairFilterVTable>,
+    ) -> PhysicsSystem<CL, BAL, SBCL> {
+        assert!(
+            config.num_threads >= -1,
+            "num_threads must be -1, 0 or a positive thread count"
+        );
         unsafe {
             let bpli_ptr = Box::into_raw(bpli) as *mut u8;
             let obplf_ptr = Box::into_raw(obplf) as *mut u8;
             let olpf_ptr = Box::into_raw(olpf) as *mut u8;
 
             let mut x_system = NonNull::new_unchecked(ffi::CreatePhysicSystem(
+                config,
                 Self::clean_up::<BPLI, OBPLF, OLPF>,
                 bpli_ptr as *const _,
                 obplf_ptr as *const _,
@@ -536,9 +704,15 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
             let raw_system = NonNull::new_unchecked(Pin::new_unchecked(x_system.as_mut()).GetPhysicsSystem());
 
             PhysicsSystem {
-                inner: Box::new(PhysicsSystemInner { x_system, raw_system }),
+                inner: Box::new(PhysicsSystemInner {
+                    x_system,
+                    raw_system,
+                    step_listeners: Vec::new(),
+                    step_listener_counter: 0,
+                }),
                 cl_phantom: PhantomData,
                 bal_phantom: PhantomData,
+                sbcl_phantom: PhantomData,
             }
         }
     }
@@ -573,7 +747,7 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
     }
 
     #[inline]
-    fn as_x_ref(&self) -> &ffi::XPhysicsSystem {
+    pub(crate) fn as_x_ref(&self) -> &ffi::XPhysicsSystem {
         unsafe { self.inner.x_system.as_ref() }
     }
 
@@ -627,8 +801,12 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
         self.steal_body_interface()
     }
 
+    /// Activation callbacks run on worker threads, hence `Send + Sync`.
     #[inline]
-    pub fn set_body_activation_listener(&mut self, listener: Option<VBox<BAL, BodyActivationListenerVTable>>) {
+    pub fn set_body_activation_listener(&mut self, listener: Option<VBox<BAL, BodyActivationListenerVTable>>)
+    where
+        BAL: Send + Sync,
+    {
         unsafe {
             let old = self.as_raw_ref().GetBodyActivationListener() as *mut u8;
             if !old.is_null() {
@@ -665,8 +843,12 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
         }
     }
 
+    /// Contact callbacks run in parallel on worker threads when a thread pool is configured, hence `Send + Sync`.
     #[inline]
-    pub fn set_contact_listener(&mut self, listener: Option<VBox<CL, ContactListenerVTable>>) {
+    pub fn set_contact_listener(&mut self, listener: Option<VBox<CL, ContactListenerVTable>>)
+    where
+        CL: Send + Sync,
+    {
         unsafe {
             let old = self.as_raw_ref().GetContactListener() as *mut u8;
             if !old.is_null() {
@@ -703,6 +885,99 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
         }
     }
 
+    /// Soft body contacts are collided in parallel on worker threads when a thread pool is configured, hence `Send + Sync`.
+    #[inline]
+    pub fn set_soft_body_contact_listener(&mut self, listener: Option<VBox<SBCL, SoftBodyContactListenerVTable>>)
+    where
+        SBCL: Send + Sync,
+    {
+        unsafe {
+            let old = self.as_raw_ref().GetSoftBodyContactListener() as *mut u8;
+            if !old.is_null() {
+                let _ = VBox::<SBCL, SoftBodyContactListenerVTable>::from_raw(old as *mut _);
+            }
+            if let Some(listener) = listener {
+                self.as_raw_mut()
+                    .SetSoftBodyContactListener(
+                        VBox::<SBCL, SoftBodyContactListenerVTable>::into_raw(listener) as *mut _
+                    );
+            } else {
+                self.as_raw_mut().SetSoftBodyContactListener(ptr::null_mut());
+            }
+        };
+    }
+
+    #[inline]
+    pub fn get_soft_body_contact_listener(&self) -> Option<&VPair<SBCL, SoftBodyContactListenerVTable>> {
+        unsafe {
+            let current = self.as_raw_ref().GetSoftBodyContactListener() as *const u8;
+            match current.is_null() {
+                false => Some(&*(current as *const _)),
+                true => None,
+            }
+        }
+    }
+
+    #[inline]
+    pub fn get_soft_body_contact_listener_mut(&mut self) -> Option<&mut VPair<SBCL, SoftBodyContactListenerVTable>> {
+        unsafe {
+            let current = self.as_raw_ref().GetSoftBodyContactListener() as *mut u8;
+            match current.is_null() {
+                false => Some(&mut *(current as *mut _)),
+                true => None,
+            }
+        }
+    }
+
+    /// The system owns the listener until it is removed or the system is dropped.
+    /// Listeners run in parallel on worker threads, hence `Send + Sync`.
+    pub fn add_step_listener<SL: PhysicsStepListener + Send + Sync>(
+        &mut self,
+        listener: VBox<SL, PhysicsStepListenerVTable>,
+    ) -> StepListenerID {
+        self.inner.step_listener_counter += 1;
+        let id = StepListenerID(self.inner.step_listener_counter);
+        let listener = VBox::<SL, PhysicsStepListenerVTable>::into_raw(listener) as *mut u8;
+        unsafe { self.as_raw_mut().AddStepListener(listener as *mut _) };
+        self.inner.step_listeners.push(StepListenerEntry {
+            id,
+            listener,
+            drop: drop_step_listener::<SL>,
+        });
+        id
+    }
+
+    pub fn remove_step_listener(&mut self, id: StepListenerID) -> bool {
+        let Some(idx) = self.inner.step_listeners.iter().position(|e| e.id == id) else {
+            return false;
+        };
+        let entry = self.inner.step_listeners.remove(idx);
+        unsafe {
+            self.as_raw_mut().RemoveStepListener(entry.listener as *mut _);
+            (entry.drop)(entry.listener);
+        }
+        true
+    }
+
+    /// Replaces the geometric mean of body frictions. `func` must be a fn item or a closure without captures.
+    /// It runs on worker threads, the sub shape ids can be used to look up per material friction.
+    #[inline]
+    pub fn set_combine_friction<F>(&mut self, func: F)
+    where
+        F: Fn(&Body, SubShapeID, &Body, SubShapeID) -> f32 + Copy + Send + Sync + 'static,
+    {
+        unsafe { ffi::SetPhysicsSystemCombineFriction(self.as_raw_mut(), make_combine_function(func)) };
+    }
+
+    /// Replaces the max of body restitutions, same requirements as `set_combine_friction`.
+    #[inline]
+    pub fn set_combine_restitution<F>(&mut self, func: F)
+    where
+        F: Fn(&Body, SubShapeID, &Body, SubShapeID) -> f32 + Copy + Send + Sync + 'static,
+    {
+        unsafe { ffi::SetPhysicsSystemCombineRestitution(self.as_raw_mut(), make_combine_function(func)) };
+    }
+
     #[inline]
     pub fn set_physics_settings(&mut self, settings: &PhysicsSettings) {
         self.as_raw_mut()
@@ -719,6 +994,20 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
         self.as_raw_mut().OptimizeBroadPhase();
     }
 
+    /// Vehicle constraints are also added as step listener.
+    /// Returns false if the constraint is already added, or its bodies belong to another system.
+    #[inline]
+    pub fn add_constraint(&mut self, constraint: &Constraint) -> bool {
+        unsafe { ffi::AddSystemConstraint(self.as_x_ptr(), constraint.as_ptr()) }
+    }
+
+    /// Vehicle constraints are also removed as step listener.
+    /// Returns false if the constraint was not added to this system.
+    #[inline]
+    pub fn remove_constraint(&mut self, constraint: &Constraint) -> bool {
+        unsafe { ffi::RemoveSystemConstraint(self.as_x_ptr(), constraint.as_ptr()) }
+    }
+
     #[inline]
     pub fn get_gravity(&self) -> Vec3A {
         self.as_raw_ref().GetGravity().into()
@@ -763,17 +1052,17 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
     pub fn update(&mut self, delta: f32, step: u32) -> JoltResult<()> {
         match self.as_x_mut().Update(delta, step) {
             0 => Ok(()),
-            err => Err(JoltError::EngineUpdate(err)),
+            err => Err(JoltError::from_update_error(err)),
         }
     }
 
     #[inline]
-    pub fn update_with_listeners<CL2: ContactListener, BAL2: BodyActivationListener>(
+    pub fn update_with_listeners<CL2: ContactListener + Send + Sync, BAL2: BodyActivationListener + Send + Sync>(
         &mut self,
         delta: f32,
         step: u32,
-        contact_listener: Option<&mut VPair<CL2, ContactListenerVTable>>,
-        body_activation_listener: Option<&mut VPair<BAL2, BodyActivationListenerVTable>>,
+        contact_listener: Option<&VPair<CL2, ContactListenerVTable>>,
+        body_activation_listener: Option<&VPair<BAL2, BodyActivationListenerVTable>>,
     ) -> JoltResult<()> {
         let old_body_activation_listener;
         let old_contact_listener;
@@ -783,13 +1072,13 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
 
             if let Some(listener) = body_activation_listener {
                 self.as_raw_mut()
-                    .SetBodyActivationListener(listener as *mut _ as *mut ffi::BodyActivationListener);
+                    .SetBodyActivationListener(listener as *const _ as *mut ffi::BodyActivationListener);
             } else {
                 self.as_raw_mut().SetBodyActivationListener(ptr::null_mut());
             }
             if let Some(listener) = contact_listener {
                 self.as_raw_mut()
-                    .SetContactListener(listener as *mut _ as *mut ffi::ContactListener);
+                    .SetContactListener(listener as *const _ as *mut ffi::ContactListener);
             } else {
                 self.as_raw_mut().SetContactListener(ptr::null_mut());
             }
@@ -797,7 +1086,7 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
 
         let res = match self.as_x_mut().Update(delta, step) {
             0 => Ok(()),
-            err => Err(JoltError::EngineUpdate(err)),
+            err => Err(JoltError::from_update_error(err)),
         };
 
         unsafe {
@@ -822,6 +1111,120 @@ impl<CL: ContactListener, BAL: BodyActivationListener> PhysicsSystem<CL, BAL> {
         self.as_x_ref().GetActiveBodies(body_type, &mut bodies);
         bodies
     }
+
+    /// Snapshots the simulation into `data`.
+    /// The state of every live `CharacterVirtual` of this system is appended, regardless of `state` and `filter`.
+    #[inline]
+    pub fn save_state<SRF: StateRecorderFilter>(
+        &self,
+        data: &mut Vec<u8>,
+        state: StateRecorderState,
+        filter: Option<&VPair<SRF, StateRecorderFilterVTable>>,
+    ) {
+        let filter = match filter {
+            Some(filter) => filter as *const _ as *const ffi::StateRecorderFilter,
+            None => ptr::null(),
+        };
+        unsafe { self.as_x_ref().SaveState(state, filter, data) };
+    }
+
+    /// Hashes position, rotation, velocity and sleep state of every body.
+    /// The result is bit-exact, intended to detect desyncs between lockstep peers.
+    #[inline]
+    pub fn state_hash(&self) -> u64 {
+        self.as_x_ref().StateHash()
+    }
+
+    /// Fails if the snapshot contains a `CharacterVirtual` that no longer exists in this system.
+    #[inline]
+    pub fn restore_state(&mut self, data: &[u8]) -> JoltResult<()> {
+        match self.as_x_mut().RestoreState(data) {
+            true => Ok(()),
+            false => Err(JoltError::RestoreState),
+        }
+    }
+}
+
+//
+// RawPhysicsSystem
+//
+
+/// The C++ physics system without listener types, handed to `PhysicsStepListener`.
+pub struct RawPhysicsSystem(pub(crate) ffi::PhysicsSystem);
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct PhysicsStepListenerContext {
+    pub delta_time: f32,
+    pub is_first_step: bool,
+    pub is_last_step: bool,
+    physics_system: *mut ffi::PhysicsSystem,
+}
+const_assert_eq!(mem::size_of::<PhysicsStepListenerContext>(), 16);
+
+impl PhysicsStepListenerContext {
+    #[inline]
+    pub fn get_physics_system(&self) -> &RawPhysicsSystem {
+        unsafe { &*(self.physics_system as *const RawPhysicsSystem) }
+    }
+}
+
+impl fmt::Debug for RawPhysicsSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawPhysicsSystem").finish()
+    }
+}
+
+impl RawPhysicsSystem {
+    #[inline]
+    fn as_ref(&self) -> &ffi::PhysicsSystem {
+        &self.0
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *mut ffi::PhysicsSystem {
+        &self.0 as *const _ as *mut _
+    }
+
+    /// Non-locking body interface, bodies are already locked during a step.
+    ///
+    /// # Safety
+    ///
+    /// Only call this from `PhysicsStepListener::on_step`. Step listeners run in parallel, so each listener
+    /// must only access bodies that no other listener touches during the same step, and must not keep the
+    /// reference beyond the callback. Adding or removing bodies is not allowed.
+    #[allow(clippy::mut_from_ref)]
+    #[inline]
+    pub unsafe fn get_body_interface(&self) -> &mut BodyInterface {
+        &mut *(ffi::GetPhysicsSystemBodyInterface(Pin::new_unchecked(&mut *self.as_ptr())) as *mut _)
+    }
+
+    /// See `get_body_interface` for the safety contract.
+    #[allow(clippy::mut_from_ref)]
+    #[inline]
+    pub unsafe fn body_itf(&self) -> &mut BodyInterface {
+        self.get_body_interface()
+    }
+
+    #[inline]
+    pub fn get_gravity(&self) -> Vec3A {
+        self.as_ref().GetGravity().into()
+    }
+
+    #[inline]
+    pub fn get_physics_settings(&self) -> &PhysicsSettings {
+        unsafe { mem::transmute::<&ffi::PhysicsSettings, &PhysicsSettings>(self.as_ref().GetPhysicsSettings()) }
+    }
+
+    #[inline]
+    pub fn get_num_bodies(&self) -> u32 {
+        self.as_ref().GetNumBodies()
+    }
+
+    #[inline]
+    pub fn get_num_active_bodies(&self, body_type: BodyType) -> u32 {
+        self.as_ref().GetNumActiveBodies(body_type)
+    }
 }
 
 //
@@ -843,7 +1246,7 @@ impl BodyInterface {
     }
 
     #[inline]
-    fn as_mut(&mut self) -> Pin<&mut ffi::XBodyInterface> {
+    pub(crate) fn as_mut(&mut self) -> Pin<&mut ffi::XBodyInterface> {
         unsafe { Pin::new_unchecked(&mut self.0) }
     }
 
@@ -1333,24 +1736,211 @@ pub struct ObjectLayerPairFilterVTable {
 #[repr(C)]
 pub struct BodyActivationListenerVTable {
     pub drop: extern "C" fn(*mut u8),
-    pub on_body_activated: extern "C" fn(*mut u8, body: &BodyID, user_data: u64),
-    pub on_body_deactivated: extern "C" fn(*mut u8, body: &BodyID, user_data: u64),
+    /// Called from worker threads while the body is locked.
+    pub on_body_activated: extern "C" fn(*const u8, body: &BodyID, user_data: u64),
+    pub on_body_deactivated: extern "C" fn(*const u8, body: &BodyID, user_data: u64),
 }
 
 #[vtable(allow_empty)]
 #[repr(C)]
 pub struct ContactListenerVTable {
     pub drop: extern "C" fn(*mut u8),
+    /// All callbacks are called from worker threads, in parallel when a thread pool is configured.
     pub on_contact_validate: extern "C" fn(
-        *mut u8,
+        *const u8,
         body1: &Body,
         body2: &Body,
         base_offset: JVec3,
         collision_result: &CollideShapeResult,
     ) -> ValidateResult,
-    pub on_contact_added:
-        extern "C" fn(*mut u8, body1: &Body, body2: &Body, manifold: &ContactManifold, settings: &mut ContactSettings),
-    pub on_contact_persisted:
-        extern "C" fn(*mut u8, body1: &Body, body2: &Body, manifold: &ContactManifold, settings: &mut ContactSettings),
-    pub on_contact_removed: extern "C" fn(*mut u8, sub_shape_pair: &SubShapeIDPair),
+    pub on_contact_added: extern "C" fn(
+        *const u8,
+        body1: &Body,
+        body2: &Body,
+        manifold: &ContactManifold,
+        settings: &mut ContactSettings,
+    ),
+    pub on_contact_persisted: extern "C" fn(
+        *const u8,
+        body1: &Body,
+        body2: &Body,
+        manifold: &ContactManifold,
+        settings: &mut ContactSettings,
+    ),
+    pub on_contact_removed: extern "C" fn(*const u8, sub_shape_pair: &SubShapeIDPair),
+}
+
+#[vtable(allow_empty)]
+#[repr(C)]
+pub struct StateRecorderFilterVTable {
+    pub drop: extern "C" fn(*mut u8),
+    pub should_save_body: extern "C" fn(*const u8, body: &Body) -> bool,
+    pub should_save_constraint: extern "C" fn(*const u8, constraint: &Constraint) -> bool,
+    pub should_save_contact: extern "C" fn(*const u8, body1: &BodyID, body2: &BodyID) -> bool,
+}
+
+#[vtable(allow_empty)]
+#[repr(C)]
+pub struct PhysicsStepListenerVTable {
+    pub drop: extern "C" fn(*mut u8),
+    /// Called before every collision step, `PhysicsSystem::update` runs `step` of them.
+    /// Listeners run in parallel on worker threads.
+    pub on_step: extern "C" fn(*const u8, context: &PhysicsStepListenerContext),
+}
+
+#[cfg(test)]
+mod tests {
+    use jolt_macros::vdata;
+    use std::sync::atomic::AtomicU32;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::shape::{create_box_shape, BoxShapeSettings};
+    use crate::test_callback::create_test_system;
+
+    use crate as jolt_physics_rs;
+
+    #[vdata(ContactListenerVTable)]
+    #[derive(Default)]
+    struct CountContactListener {
+        validated: AtomicU32,
+        added: AtomicU32,
+    }
+
+    impl ContactListener for CountContactListener {
+        fn on_contact_validate(&self, _: &Body, _: &Body, _: JVec3, _: &CollideShapeResult) -> ValidateResult {
+            self.validated.fetch_add(1, Ordering::Relaxed);
+            ValidateResult::AcceptAllContactsForThisBodyPair
+        }
+
+        fn on_contact_added(&self, _: &Body, _: &Body, _: &ContactManifold, _: &mut ContactSettings) {
+            self.added.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_contact_persisted(&self, _: &Body, _: &Body, _: &ContactManifold, _: &mut ContactSettings) {}
+
+        fn on_contact_removed(&self, _: &SubShapeIDPair) {}
+    }
+
+    #[test]
+    fn test_thread_pool_contact_listener() {
+        let mut system: PhysicsSystem<CountContactListener> =
+            create_test_system(&PhysicsSystemConfig::new_thread_pool(2));
+        system.set_contact_listener(Some(CountContactListener::new_vbox(CountContactListener::default())));
+
+        let floor = create_box_shape(&BoxShapeSettings::new(20.0, 1.0, 20.0)).unwrap();
+        let cube = create_box_shape(&BoxShapeSettings::new(0.5, 0.5, 0.5)).unwrap();
+        let body_itf = system.body_itf();
+        body_itf
+            .create_add_body(
+                &BodyCreationSettings::new_static(floor, 0, Vec3A::new(0.0, -1.0, 0.0), Quat::IDENTITY),
+                false,
+            )
+            .unwrap();
+        for idx in 0..16 {
+            let position = Vec3A::new((idx % 4) as f32 * 2.0 - 3.0, 0.45, (idx / 4) as f32 * 2.0 - 3.0);
+            let settings = BodyCreationSettings::new(cube.clone(), 0, MotionType::Dynamic, position, Quat::IDENTITY);
+            body_itf.create_add_body(&settings, true).unwrap();
+        }
+
+        for _ in 0..10 {
+            system.update(1.0 / 60.0, 1).unwrap();
+        }
+        let listener = system.get_contact_listener().unwrap();
+        assert!(listener.validated.load(Ordering::Relaxed) >= 16);
+        assert!(listener.added.load(Ordering::Relaxed) >= 16);
+    }
+
+    #[vdata(PhysicsStepListenerVTable)]
+    struct AntiGravityListener {
+        body_id: BodyID,
+        steps: Arc<AtomicU32>,
+    }
+
+    impl PhysicsStepListener for AntiGravityListener {
+        fn on_step(&self, context: &PhysicsStepListenerContext) {
+            let system = context.get_physics_system();
+            let velocity = -system.get_gravity() * context.delta_time;
+            // Only this listener touches `body_id`.
+            unsafe { system.get_body_interface() }.add_linear_velocity(self.body_id, velocity);
+            self.steps.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_step_listener_substeps() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let cube = create_box_shape(&BoxShapeSettings::new(0.5, 0.5, 0.5)).unwrap();
+        let body_itf = system.body_itf();
+        let floating = BodyCreationSettings::new(cube.clone(), 0, MotionType::Dynamic, Vec3A::ZERO, Quat::IDENTITY);
+        let floating = body_itf.create_add_body(&floating, true).unwrap();
+        let falling = BodyCreationSettings::new(cube, 0, MotionType::Dynamic, Vec3A::X * 5.0, Quat::IDENTITY);
+        let falling = body_itf.create_add_body(&falling, true).unwrap();
+
+        let steps = Arc::new(AtomicU32::new(0));
+        let id = system.add_step_listener(AntiGravityListener::new_vbox(AntiGravityListener {
+            body_id: floating,
+            steps: steps.clone(),
+        }));
+        for _ in 0..10 {
+            system.update(1.0 / 60.0, 4).unwrap();
+        }
+        assert_eq!(steps.load(Ordering::Relaxed), 40);
+        assert!(system.body_itf().get_position(floating).abs_diff_eq(Vec3A::ZERO, 1e-3));
+        assert!(system.body_itf().get_position(falling).y < -0.1);
+
+        assert!(system.remove_step_listener(id));
+        system.update(1.0 / 60.0, 4).unwrap();
+        assert_eq!(steps.load(Ordering::Relaxed), 40);
+    }
+
+    #[test]
+    fn test_combine_friction() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        // Bodies with user data 1 are ice, everything else sticks.
+        system.set_combine_friction(|body1: &Body, _, body2: &Body, _| {
+            match body1.get_user_data() == 1 || body2.get_user_data() == 1 {
+                true => 0.0,
+                false => 1.0,
+            }
+        });
+
+        let floor = create_box_shape(&BoxShapeSettings::new(50.0, 1.0, 50.0)).unwrap();
+        let tile = create_box_shape(&BoxShapeSettings::new(0.5, 0.25, 0.5)).unwrap();
+        let body_itf = system.body_itf();
+        body_itf
+            .create_add_body(
+                &BodyCreationSettings::new_static(floor, 0, Vec3A::new(0.0, -1.0, 0.0), Quat::IDENTITY),
+                false,
+            )
+            .unwrap();
+        let mut create_tile = |z: f32, user_data: u64| {
+            let settings = BodyCreationSettings {
+                linear_velocity: Vec3A::new(5.0, 0.0, 0.0),
+                user_data,
+                ..BodyCreationSettings::new(
+                    tile.clone(),
+                    0,
+                    MotionType::Dynamic,
+                    Vec3A::new(0.0, 0.25, z),
+                    Quat::IDENTITY,
+                )
+            };
+            body_itf.create_add_body(&settings, true).unwrap()
+        };
+        let ice = create_tile(-5.0, 1);
+        let rock = create_tile(5.0, 0);
+
+        for _ in 0..60 {
+            system.update(1.0 / 60.0, 1).unwrap();
+        }
+        assert!(system.body_itf().get_linear_velocity(ice).x > 4.5);
+        assert!(system.body_itf().get_linear_velocity(rock).x.abs() < 0.1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reject_negative_num_threads() {
+        let _: PhysicsSystem = create_test_system(&PhysicsSystemConfig::new_thread_pool(-2));
+    }
 }
@@ -173,3 +173,79 @@ const char* TestCharacterContactListener(
 
 	return nullptr;
 }
+
+const char* TestBodyFilter(const BodyFilter* filter, XPhysicsSystem* system) {
+	if (filter->ShouldCollide(BodyID(445566)) != true) {
+		return "ShouldCollide failed";
+	}
+
+	BodyInterface &bodyItf = system->BodyItf(false);
+	BodyCreationSettings settings(new SphereShape(0.5f), RVec3(2.0f, 4.0f, 6.0f), Quat::sIdentity(), EMotionType::Dynamic, 0);
+	Body *body = bodyItf.CreateBody(settings);
+	if (filter->ShouldCollideLocked(*body) != false) {
+		return "ShouldCollideLocked failed";
+	}
+	return nullptr;
+}
+
+const char* TestStateRecorderFilter(const StateRecorderFilter* filter, XPhysicsSystem* system) {
+	BodyInterface &bodyItf = system->BodyItf(false);
+	BodyCreationSettings settings1(new SphereShape(0.5f), RVec3(1.0f, 2.0f, 3.0f), Quat::sIdentity(), EMotionType::Dynamic, 0);
+	Body *body1 = bodyItf.CreateBody(settings1);
+	BodyCreationSettings settings2(new SphereShape(0.5f), RVec3(4.0f, 5.0f, 6.0f), Quat::sIdentity(), EMotionType::Dynamic, 0);
+	Body *body2 = bodyItf.CreateBody(settings2);
+
+	if (filter->ShouldSaveBody(*body1) != true) {
+		return "ShouldSaveBody failed";
+	}
+
+	FixedConstraintSettings constraintSettings;
+	constraintSettings.mAutoDetectPoint = true;
+	Ref<Constraint> constraint = constraintSettings.Create(*body1, *body2);
+	constraint->SetUserData(2718);
+	if (filter->ShouldSaveConstraint(*constraint) != false) {
+		return "ShouldSaveConstraint failed";
+	}
+
+	if (filter->ShouldSaveContact(BodyID(1001), BodyID(2002)) != true) {
+		return "ShouldSaveContact failed";
+	}
+	return nullptr;
+}
+
+const char* TestSoftBodyContactListener(SoftBodyContactListener* listener, XPhysicsSystem* system) {
+	BodyInterface &bodyItf = system->BodyItf(false);
+	BodyCreationSettings settings1(new SphereShape(0.5f), RVec3(1.0f, 2.0f, 3.0f), Quat::sIdentity(), EMotionType::Dynamic, 0);
+	Body *body1 = bodyItf.CreateBody(settings1);
+	BodyCreationSettings settings2(new SphereShape(0.5f), RVec3(4.0f, 5.0f, 6.0f), Quat::sIdentity(), EMotionType::Dynamic, 0);
+	Body *body2 = bodyItf.CreateBody(settings2);
+
+	SoftBodyContactSettings settings;
+	settings.mInvMassScale1 = 0.5f;
+	SoftBodyValidateResult res = listener->OnSoftBodyContactValidate(*body1, *body2, settings);
+	if (res != SoftBodyValidateResult::RejectContact) {
+		return "OnSoftBodyContactValidate failed";
+	}
+	if (settings.mInvMassScale2 != 0.25f) {
+		return "OnSoftBodyContactValidate settings failed";
+	}
+	return nullptr;
+}
+
+const char* TestPhysicsStepListener(PhysicsStepListener* listener, XPhysicsSystem* system) {
+	BodyInterface &bodyItf = system->BodyItf(false);
+	BodyCreationSettings settings(new SphereShape(0.5f), RVec3(1.0f, 2.0f, 3.0f), Quat::sIdentity(), EMotionType::Dynamic, 0);
+	Body *body = bodyItf.CreateBodyWithID(BodyID(1234), settings);
+	bodyItf.AddBody(body->GetID(), EActivation::Activate);
+
+	PhysicsStepListenerContext context;
+	context.mDeltaTime = 0.5f;
+	context.mIsFirstStep = true;
+	context.mIsLastStep = false;
+	context.mPhysicsSystem = &system->PhySys();
+	listener->OnStep(context);
+	if (bodyItf.GetLinearVelocity(body->GetID()) != Vec3(4.0f, 5.0f, 6.0f)) {
+		return "OnStep failed";
+	}
+	return nullptr;
+}
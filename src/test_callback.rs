@@ -1,7 +1,8 @@
 use glam::{Quat, Vec3A};
 use jolt_macros::vdata;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ffi::CStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::base::{BodyID, BroadPhaseLayer, JVec3, ObjectLayer, SubShapeID, ValidateResult};
 use crate::body::Body;
@@ -9,13 +10,20 @@ use crate::character::{
     CharacterContactListener, CharacterContactListenerVTable, CharacterContactSettings, CharacterVirtual,
     CharacterVirtualSettings,
 };
+use crate::constraint::Constraint;
+use crate::query::{BodyFilter, BodyFilterVTable};
 use crate::shape::PhysicsMaterial;
-use crate::system::{global_initialize, PhysicsSystem};
+use crate::soft_body::{
+    SoftBodyContactListener, SoftBodyContactListenerVTable, SoftBodyContactSettings, SoftBodyManifold,
+    SoftBodyValidateResult,
+};
+use crate::system::{global_initialize, PhysicsSystem, PhysicsSystemConfig};
 use crate::system::{
     BodyActivationListener, BodyActivationListenerVTable, BroadPhaseLayerInterface, BroadPhaseLayerInterfaceVTable,
     CollideShapeResult, ContactListener, ContactListenerVTable, ContactManifold, ContactSettings,
     ObjectLayerPairFilter, ObjectLayerPairFilterVTable, ObjectVsBroadPhaseLayerFilter,
-    ObjectVsBroadPhaseLayerFilterVTable, SubShapeIDPair,
+    ObjectVsBroadPhaseLayerFilterVTable, PhysicsStepListener, PhysicsStepListenerContext, PhysicsStepListenerVTable,
+    StateRecorderFilter, StateRecorderFilterVTable, SubShapeIDPair,
 };
 
 use crate as jolt_physics_rs;
@@ -46,6 +54,10 @@ pub(crate) mod ffi {
         type XPhysicsSystem = crate::system::ffi::XPhysicsSystem;
         type CharacterContactListener = crate::character::ffi::CharacterContactListener;
         type XCharacterVirtual = crate::character::ffi::XCharacterVirtual;
+        type BodyFilter = crate::query::ffi::BodyFilter;
+        type StateRecorderFilter = crate::system::ffi::StateRecorderFilter;
+        type SoftBodyContactListener = crate::system::ffi::SoftBodyContactListener;
+        type PhysicsStepListener = crate::system::ffi::PhysicsStepListener;
 
         unsafe fn TestBroadPhaseLayerInterface(itf: *const BroadPhaseLayerInterface) -> *const c_char;
         unsafe fn TestObjectVsBroadPhaseLayerFilter(itf: *const ObjectVsBroadPhaseLayerFilter) -> *const c_char;
@@ -58,6 +70,19 @@ pub(crate) mod ffi {
             chara1: *mut XCharacterVirtual,
             chara2: *mut XCharacterVirtual,
         ) -> *const c_char;
+        unsafe fn TestBodyFilter(filter: *const BodyFilter, system: *mut XPhysicsSystem) -> *const c_char;
+        unsafe fn TestSoftBodyContactListener(
+            listener: *mut SoftBodyContactListener,
+            system: *mut XPhysicsSystem,
+        ) -> *const c_char;
+        unsafe fn TestStateRecorderFilter(
+            filter: *const StateRecorderFilter,
+            system: *mut XPhysicsSystem,
+        ) -> *const c_char;
+        unsafe fn TestPhysicsStepListener(
+            listener: *mut PhysicsStepListener,
+            system: *mut XPhysicsSystem,
+        ) -> *const c_char;
     }
 }
 
@@ -193,22 +218,22 @@ fn test_object_layer_pair_filter() {
 #[derive(Default)]
 struct TestBaListener {
     str: String,
-    buf: Vec<(BodyID, u64)>,
-    called_on_body_activated: bool,
-    called_on_body_deactivated: bool,
+    buf: RefCell<Vec<(BodyID, u64)>>,
+    called_on_body_activated: Cell<bool>,
+    called_on_body_deactivated: Cell<bool>,
 }
 
 impl BodyActivationListener for TestBaListener {
-    fn on_body_activated(&mut self, body: &BodyID, user_data: u64) {
+    fn on_body_activated(&self, body: &BodyID, user_data: u64) {
         assert_eq!(self.str, "TestBaListener - test");
-        self.buf.push((*body, user_data));
-        self.called_on_body_activated = true;
+        self.buf.borrow_mut().push((*body, user_data));
+        self.called_on_body_activated.set(true);
     }
 
-    fn on_body_deactivated(&mut self, body: &BodyID, user_data: u64) {
+    fn on_body_deactivated(&self, body: &BodyID, user_data: u64) {
         assert_eq!(self.str, "TestBaListener - test");
-        self.buf.push((*body, user_data));
-        self.called_on_body_deactivated = true;
+        self.buf.borrow_mut().push((*body, user_data));
+        self.called_on_body_deactivated.set(true);
     }
 }
 
@@ -216,7 +241,6 @@ impl BodyActivationListener for TestBaListener {
 fn test_body_activation_listener_filter() {
     let mut listener = TestBaListener::new_vbox(TestBaListener {
         str: "TestBaListener - test".to_string(),
-        buf: vec![],
         ..Default::default()
     });
 
@@ -230,25 +254,28 @@ fn test_body_activation_listener_filter() {
         }
     }
     assert_eq!(stack, get_stack_pointer());
-    assert!(listener.called_on_body_activated);
-    assert!(listener.called_on_body_deactivated);
-    assert_eq!(listener.buf, vec![(BodyID(123456), 99999), (BodyID(654321), 88888)]);
+    assert!(listener.called_on_body_activated.get());
+    assert!(listener.called_on_body_deactivated.get());
+    assert_eq!(
+        *listener.buf.borrow(),
+        vec![(BodyID(123456), 99999), (BodyID(654321), 88888)]
+    );
 }
 
 #[vdata(ContactListenerVTable)]
 #[derive(Default)]
 struct TestContactListener {
     str: String,
-    buf: Vec<(BodyID, BodyID)>,
-    called_on_contact_validate: bool,
-    called_on_contact_added: bool,
-    called_on_contact_persisted: bool,
-    called_on_contact_removed: bool,
+    buf: RefCell<Vec<(BodyID, BodyID)>>,
+    called_on_contact_validate: Cell<bool>,
+    called_on_contact_added: Cell<bool>,
+    called_on_contact_persisted: Cell<bool>,
+    called_on_contact_removed: Cell<bool>,
 }
 
 impl ContactListener for TestContactListener {
     fn on_contact_validate(
-        &mut self,
+        &self,
         body1: &Body,
         body2: &Body,
         base_offset: JVec3,
@@ -257,31 +284,25 @@ impl ContactListener for TestContactListener {
         assert_eq!(self.str, "TestContactListener - test");
         assert_eq!(body1.get_id().0, 0x800000);
         assert_eq!(body2.get_id().0, 0x800001);
-        self.buf.push((body1.get_id(), body2.get_id()));
+        self.buf.borrow_mut().push((body1.get_id(), body2.get_id()));
         assert_eq!(base_offset, Vec3A::new(4.3, 5.4, 0.82));
         assert_eq!(collision_result.penetration_depth, 0.073);
-        self.called_on_contact_validate = true;
+        self.called_on_contact_validate.set(true);
         ValidateResult::RejectContact
     }
 
-    fn on_contact_added(
-        &mut self,
-        body1: &Body,
-        body2: &Body,
-        manifold: &ContactManifold,
-        settings: &mut ContactSettings,
-    ) {
+    fn on_contact_added(&self, body1: &Body, body2: &Body, manifold: &ContactManifold, settings: &mut ContactSettings) {
         assert_eq!(self.str, "TestContactListener - test");
         assert_eq!(body1.get_id().0, 0x800000);
         assert_eq!(body2.get_id().0, 0x800001);
         assert_eq!(manifold.penetration_depth, 0.028);
         assert_eq!(settings.relative_angular_surface_velocity, Vec3A::new(0.1, 0.2, 0.3));
-        self.buf.push((body1.get_id(), body2.get_id()));
-        self.called_on_contact_added = true;
+        self.buf.borrow_mut().push((body1.get_id(), body2.get_id()));
+        self.called_on_contact_added.set(true);
     }
 
     fn on_contact_persisted(
-        &mut self,
+        &self,
         body1: &Body,
         body2: &Body,
         manifold: &ContactManifold,
@@ -292,15 +313,15 @@ impl ContactListener for TestContactListener {
         assert_eq!(body2.get_id().0, 0x800001);
         assert_eq!(manifold.penetration_depth, 0.103);
         assert_eq!(settings.relative_linear_surface_velocity, Vec3A::new(1.1, 2.2, 3.3));
-        self.buf.push((body1.get_id(), body2.get_id()));
-        self.called_on_contact_persisted = true;
+        self.buf.borrow_mut().push((body1.get_id(), body2.get_id()));
+        self.called_on_contact_persisted.set(true);
     }
 
-    fn on_contact_removed(&mut self, sub_shape_pair: &SubShapeIDPair) {
+    fn on_contact_removed(&self, sub_shape_pair: &SubShapeIDPair) {
         assert_eq!(self.str, "TestContactListener - test");
         assert_eq!(sub_shape_pair.sub_shape_id1.0, 0xFFFFFFFF);
         assert_eq!(sub_shape_pair.sub_shape_id2.0, 0xFFFFFFFF);
-        self.called_on_contact_removed = true;
+        self.called_on_contact_removed.set(true);
     }
 }
 
@@ -315,7 +336,6 @@ fn test_contact_listener() {
 
     let mut listener = TestContactListener::new_vbox(TestContactListener {
         str: "TestContactListener - test".to_string(),
-        buf: vec![],
         ..Default::default()
     });
 
@@ -330,11 +350,11 @@ fn test_contact_listener() {
         }
     }
     assert_eq!(stack, get_stack_pointer());
-    assert!(listener.called_on_contact_validate);
-    assert!(listener.called_on_contact_added);
-    assert!(listener.called_on_contact_persisted);
-    assert!(listener.called_on_contact_removed);
-    assert_eq!(listener.buf, vec![(BodyID(0x800000), BodyID(0x800001)); 3]);
+    assert!(listener.called_on_contact_validate.get());
+    assert!(listener.called_on_contact_added.get());
+    assert!(listener.called_on_contact_persisted.get());
+    assert!(listener.called_on_contact_removed.get());
+    assert_eq!(*listener.buf.borrow(), vec![(BodyID(0x800000), BodyID(0x800001)); 3]);
 }
 
 #[vdata(CharacterContactListenerVTable)]
@@ -576,8 +596,178 @@ fn test_character_contact_listener() {
     assert!(listener.called_on_character_contact_solve);
 }
 
+#[vdata(BodyFilterVTable)]
+#[derive(Default)]
+struct TestBodyFilter {
+    str: String,
+    called_should_collide: Cell<bool>,
+    called_should_collide_locked: Cell<bool>,
+}
+
+impl BodyFilter for TestBodyFilter {
+    fn should_collide(&self, body_id: &BodyID) -> bool {
+        assert_eq!(self.str, "TestBodyFilter - test");
+        assert_eq!(body_id.0, 445566);
+        self.called_should_collide.set(true);
+        true
+    }
+
+    fn should_collide_locked(&self, body: &Body) -> bool {
+        assert_eq!(self.str, "TestBodyFilter - test");
+        assert_eq!(body.get_position(), Vec3A::new(2.0, 4.0, 6.0));
+        self.called_should_collide_locked.set(true);
+        false
+    }
+}
+
+#[test]
+fn test_body_filter() {
+    global_initialize();
+    let system: PhysicsSystem = PhysicsSystem::new(
+        EmptyBplInterface::new_vbox(EmptyBplInterface),
+        EmptyObplFilter::new_vbox(EmptyObplFilter),
+        EmptyOlpFilter::new_vbox(EmptyOlpFilter),
+    );
+
+    let flt = TestBodyFilter::new_vbox(TestBodyFilter {
+        str: "TestBodyFilter - test".to_string(),
+        ..Default::default()
+    });
+
+    let stack = get_stack_pointer();
+    unsafe {
+        let err = ffi::TestBodyFilter(
+            flt.as_ref() as *const _ as *const crate::query::ffi::BodyFilter,
+            system.as_x_ptr(),
+        );
+        if !err.is_null() {
+            panic!("Test failed in C++: {:?}", CStr::from_ptr(err));
+        }
+    }
+    assert_eq!(stack, get_stack_pointer());
+    assert!(flt.called_should_collide.get());
+    assert!(flt.called_should_collide_locked.get());
+}
+
+#[vdata(SoftBodyContactListenerVTable)]
+#[derive(Default)]
+struct TestSbContactListener {
+    num: i32,
+    called_on_soft_body_contact_validate: Cell<bool>,
+}
+
+impl SoftBodyContactListener for TestSbContactListener {
+    fn on_soft_body_contact_validate(
+        &self,
+        soft_body: &Body,
+        other_body: &Body,
+        settings: &mut SoftBodyContactSettings,
+    ) -> SoftBodyValidateResult {
+        assert_eq!(self.num, 16180);
+        assert_eq!(soft_body.get_position(), Vec3A::new(1.0, 2.0, 3.0));
+        assert_eq!(other_body.get_position(), Vec3A::new(4.0, 5.0, 6.0));
+        assert_eq!(settings.inv_mass_scale1, 0.5);
+        settings.inv_mass_scale2 = 0.25;
+        self.called_on_soft_body_contact_validate.set(true);
+        SoftBodyValidateResult::RejectContact
+    }
+
+    fn on_soft_body_contact_added(&self, _soft_body: &Body, _manifold: &SoftBodyManifold) {}
+}
+
+#[test]
+fn test_soft_body_contact_listener() {
+    global_initialize();
+    let system: PhysicsSystem = PhysicsSystem::new(
+        EmptyBplInterface::new_vbox(EmptyBplInterface),
+        EmptyObplFilter::new_vbox(EmptyObplFilter),
+        EmptyOlpFilter::new_vbox(EmptyOlpFilter),
+    );
+
+    let mut listener = TestSbContactListener::new_vbox(TestSbContactListener {
+        num: 16180,
+        ..Default::default()
+    });
+
+    let stack = get_stack_pointer();
+    unsafe {
+        let err = ffi::TestSoftBodyContactListener(
+            listener.as_mut() as *mut _ as *mut crate::system::ffi::SoftBodyContactListener,
+            system.as_x_ptr(),
+        );
+        if !err.is_null() {
+            panic!("Test failed in C++: {:?}", CStr::from_ptr(err));
+        }
+    }
+    assert_eq!(stack, get_stack_pointer());
+    assert!(listener.called_on_soft_body_contact_validate.get());
+}
+
+#[vdata(StateRecorderFilterVTable)]
+#[derive(Default)]
+struct TestSrFilter {
+    num: i32,
+    called_should_save_body: Cell<bool>,
+    called_should_save_constraint: Cell<bool>,
+    called_should_save_contact: Cell<bool>,
+}
+
+impl StateRecorderFilter for TestSrFilter {
+    fn should_save_body(&self, body: &Body) -> bool {
+        assert_eq!(self.num, 31415);
+        assert_eq!(body.get_position(), Vec3A::new(1.0, 2.0, 3.0));
+        self.called_should_save_body.set(true);
+        true
+    }
+
+    fn should_save_constraint(&self, constraint: &Constraint) -> bool {
+        assert_eq!(self.num, 31415);
+        assert_eq!(constraint.get_user_data(), 2718);
+        self.called_should_save_constraint.set(true);
+        false
+    }
+
+    fn should_save_contact(&self, body1: &BodyID, body2: &BodyID) -> bool {
+        assert_eq!(self.num, 31415);
+        assert_eq!(body1.0, 1001);
+        assert_eq!(body2.0, 2002);
+        self.called_should_save_contact.set(true);
+        true
+    }
+}
+
+#[test]
+fn test_state_recorder_filter() {
+    global_initialize();
+    let system: PhysicsSystem = PhysicsSystem::new(
+        EmptyBplInterface::new_vbox(EmptyBplInterface),
+        EmptyObplFilter::new_vbox(EmptyObplFilter),
+        EmptyOlpFilter::new_vbox(EmptyOlpFilter),
+    );
+
+    let flt = TestSrFilter::new_vbox(TestSrFilter {
+        num: 31415,
+        ..Default::default()
+    });
+
+    let stack = get_stack_pointer();
+    unsafe {
+        let err = ffi::TestStateRecorderFilter(
+            flt.as_ref() as *const _ as *const crate::system::ffi::StateRecorderFilter,
+            system.as_x_ptr(),
+        );
+        if !err.is_null() {
+            panic!("Test failed in C++: {:?}", CStr::from_ptr(err));
+        }
+    }
+    assert_eq!(stack, get_stack_pointer());
+    assert!(flt.called_should_save_body.get());
+    assert!(flt.called_should_save_constraint.get());
+    assert!(flt.called_should_save_contact.get());
+}
+
 #[vdata(BroadPhaseLayerInterfaceVTable)]
-struct EmptyBplInterface;
+pub(crate) struct EmptyBplInterface;
 
 impl BroadPhaseLayerInterface for EmptyBplInterface {
     fn get_num_broad_phase_layers(&self) -> u32 {
@@ -585,12 +775,12 @@ impl BroadPhaseLayerInterface for EmptyBplInterface {
     }
 
     fn get_broad_phase_layer(&self, _: ObjectLayer) -> BroadPhaseLayer {
-        1
+        0
     }
 }
 
 #[vdata(ObjectVsBroadPhaseLayerFilterVTable)]
-struct EmptyObplFilter;
+pub(crate) struct EmptyObplFilter;
 
 impl ObjectVsBroadPhaseLayerFilter for EmptyObplFilter {
     fn should_collide(&self, _1: ObjectLayer, _2: BroadPhaseLayer) -> bool {
@@ -599,10 +789,73 @@ impl ObjectVsBroadPhaseLayerFilter for EmptyObplFilter {
 }
 
 #[vdata(ObjectLayerPairFilterVTable)]
-struct EmptyOlpFilter;
+pub(crate) struct EmptyOlpFilter;
 
 impl ObjectLayerPairFilter for EmptyOlpFilter {
     fn should_collide(&self, _1: ObjectLayer, _2: ObjectLayer) -> bool {
         true
     }
 }
+
+/// A system with a single broad phase layer where every object layer collides with every other.
+pub(crate) fn create_test_system<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    config: &PhysicsSystemConfig,
+) -> PhysicsSystem<CL, BAL, SBCL> {
+    global_initialize();
+    PhysicsSystem::new_with_config(
+        config,
+        EmptyBplInterface::new_vbox(EmptyBplInterface),
+        EmptyObplFilter::new_vbox(EmptyObplFilter),
+        EmptyOlpFilter::new_vbox(EmptyOlpFilter),
+    )
+}
+
+#[vdata(PhysicsStepListenerVTable)]
+#[derive(Default)]
+struct TestStepListener {
+    num: i32,
+    called_on_step: AtomicBool,
+}
+
+impl PhysicsStepListener for TestStepListener {
+    fn on_step(&self, context: &PhysicsStepListenerContext) {
+        assert_eq!(self.num, 14142);
+        assert_eq!(context.delta_time, 0.5);
+        assert!(context.is_first_step);
+        assert!(!context.is_last_step);
+        let system = context.get_physics_system();
+        assert_eq!(system.get_num_bodies(), 1);
+        let body_itf = unsafe { system.body_itf() };
+        assert_eq!(body_itf.get_position(BodyID(1234)), Vec3A::new(1.0, 2.0, 3.0));
+        body_itf.set_linear_velocity(BodyID(1234), Vec3A::new(4.0, 5.0, 6.0));
+        self.called_on_step.store(true, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn test_physics_step_listener() {
+    global_initialize();
+    let system: PhysicsSystem = PhysicsSystem::new(
+        EmptyBplInterface::new_vbox(EmptyBplInterface),
+        EmptyObplFilter::new_vbox(EmptyObplFilter),
+        EmptyOlpFilter::new_vbox(EmptyOlpFilter),
+    );
+
+    let mut listener = TestStepListener::new_vbox(TestStepListener {
+        num: 14142,
+        ..Default::default()
+    });
+
+    let stack = get_stack_pointer();
+    unsafe {
+        let err = ffi::TestPhysicsStepListener(
+            listener.as_mut() as *mut _ as *mut crate::system::ffi::PhysicsStepListener,
+            system.as_x_ptr(),
+        );
+        if !err.is_null() {
+            panic!("Test failed in C++: {:?}", CStr::from_ptr(err));
+        }
+    }
+    assert_eq!(stack, get_stack_pointer());
+    assert!(listener.called_on_step.load(Ordering::Relaxed));
+}
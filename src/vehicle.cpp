@@ -0,0 +1,428 @@
+#include "jolt-physics-rs/src/ffi.h"
+#include "jolt-physics-rs/src/vehicle.rs.h"
+
+struct XLinearCurvePoint {
+	float x;
+	float y;
+};
+static_assert(sizeof(XLinearCurvePoint) == 8, "XLinearCurvePoint size");
+
+static void CopyLinearCurve(LinearCurve& curve, rust::Slice<const XLinearCurvePoint> points) {
+	if (points.empty()) {
+		return;
+	}
+	curve.Clear();
+	for (const XLinearCurvePoint& p : points) {
+		curve.AddPoint(p.x, p.y);
+	}
+	curve.Sort();
+}
+
+struct XVehicleAntiRollBar {
+	int32 leftWheel;
+	int32 rightWheel;
+	float stiffness;
+};
+static_assert(sizeof(XVehicleAntiRollBar) == 12, "XVehicleAntiRollBar size");
+
+struct XVehicleConstraintSettings {
+	uint64 userData;
+	bool enabled;
+	uint32 constraintPriority;
+	uint32 numVelocityStepsOverride;
+	uint32 numPositionStepsOverride;
+	Vec3 up;
+	Vec3 forward;
+	float maxPitchRollAngle;
+	VehicleCollisionTesterType testerType;
+	ObjectLayer testerLayer;
+	float testerMaxSlopeAngle;
+	float testerRadius;
+	float testerConvexRadiusFraction;
+	rust::Slice<const XVehicleAntiRollBar> antiRollBars;
+};
+static_assert(sizeof(XVehicleConstraintSettings) == 112, "XVehicleConstraintSettings size");
+
+struct XWheelSettingsWV {
+	Vec3 position;
+	Vec3 suspensionForcePoint;
+	Vec3 suspensionDirection;
+	Vec3 steeringAxis;
+	Vec3 wheelUp;
+	Vec3 wheelForward;
+	float suspensionMinLength;
+	float suspensionMaxLength;
+	float suspensionPreloadLength;
+	SpringSettings suspensionSpring;
+	float radius;
+	float width;
+	bool enableSuspensionForcePoint;
+	float inertia;
+	float angularDamping;
+	float maxSteerAngle;
+	float maxBrakeTorque;
+	float maxHandBrakeTorque;
+	rust::Slice<const XLinearCurvePoint> longitudinalFriction;
+	rust::Slice<const XLinearCurvePoint> lateralFriction;
+};
+static_assert(sizeof(XWheelSettingsWV) == 192, "XWheelSettingsWV size");
+
+struct XWheelSettingsTV {
+	Vec3 position;
+	Vec3 suspensionForcePoint;
+	Vec3 suspensionDirection;
+	Vec3 steeringAxis;
+	Vec3 wheelUp;
+	Vec3 wheelForward;
+	float suspensionMinLength;
+	float suspensionMaxLength;
+	float suspensionPreloadLength;
+	SpringSettings suspensionSpring;
+	float radius;
+	float width;
+	bool enableSuspensionForcePoint;
+	float longitudinalFriction;
+	float lateralFriction;
+};
+static_assert(sizeof(XWheelSettingsTV) == 144, "XWheelSettingsTV size");
+
+struct XVehicleEngineSettings {
+	float maxTorque;
+	float minRPM;
+	float maxRPM;
+	float inertia;
+	float angularDamping;
+	rust::Slice<const XLinearCurvePoint> normalizedTorque;
+};
+static_assert(sizeof(XVehicleEngineSettings) == 40, "XVehicleEngineSettings size");
+
+struct XVehicleTransmissionSettings {
+	ETransmissionMode mode;
+	float switchTime;
+	float clutchReleaseTime;
+	float switchLatency;
+	float shiftUpRPM;
+	float shiftDownRPM;
+	float clutchStrength;
+	rust::Slice<const float> gearRatios;
+	rust::Slice<const float> reverseGearRatios;
+};
+static_assert(sizeof(XVehicleTransmissionSettings) == 64, "XVehicleTransmissionSettings size");
+
+struct XVehicleDifferentialSettings {
+	int32 leftWheel;
+	int32 rightWheel;
+	float differentialRatio;
+	float leftRightSplit;
+	float limitedSlipRatio;
+	float engineTorqueRatio;
+};
+static_assert(sizeof(XVehicleDifferentialSettings) == 24, "XVehicleDifferentialSettings size");
+
+struct XWheeledVehicleControllerSettings {
+	XVehicleEngineSettings engine;
+	XVehicleTransmissionSettings transmission;
+	rust::Slice<const XVehicleDifferentialSettings> differentials;
+	float differentialLimitedSlipRatio;
+};
+static_assert(sizeof(XWheeledVehicleControllerSettings) == 128, "XWheeledVehicleControllerSettings size");
+
+struct XVehicleTrackSettings {
+	uint32 drivenWheel;
+	rust::Slice<const uint32> wheels;
+	float inertia;
+	float angularDamping;
+	float maxBrakeTorque;
+	float differentialRatio;
+};
+static_assert(sizeof(XVehicleTrackSettings) == 40, "XVehicleTrackSettings size");
+
+struct XTrackedVehicleControllerSettings {
+	XVehicleEngineSettings engine;
+	XVehicleTransmissionSettings transmission;
+	XVehicleTrackSettings tracks[(int)ETrackSide::Num];
+};
+static_assert(sizeof(XTrackedVehicleControllerSettings) == 184, "XTrackedVehicleControllerSettings size");
+
+struct XMotorcycleControllerSettings {
+	XWheeledVehicleControllerSettings wheeled;
+	float maxLeanAngle;
+	float leanSpringConstant;
+	float leanSpringDamping;
+	float leanSpringIntegrationCoefficient;
+	float leanSpringIntegrationCoefficientDecay;
+	float leanSmoothingFactor;
+};
+static_assert(sizeof(XMotorcycleControllerSettings) == 152, "XMotorcycleControllerSettings size");
+
+struct XWheelState {
+	Vec3 contactPosition;
+	Vec3 contactNormal;
+	BodyID contactBodyID;
+	bool hasContact;
+	float suspensionLength;
+	float angularVelocity;
+	float rotationAngle;
+	float steerAngle;
+};
+static_assert(sizeof(XWheelState) == 64, "XWheelState size");
+
+static void CopyVehicleConstraintSettings(VehicleConstraintSettings& settings, const XVehicleConstraintSettings& st) {
+	settings.mUserData = st.userData;
+	settings.mEnabled = st.enabled;
+	settings.mConstraintPriority = st.constraintPriority;
+	settings.mNumVelocityStepsOverride = st.numVelocityStepsOverride;
+	settings.mNumPositionStepsOverride = st.numPositionStepsOverride;
+	settings.mUp = st.up;
+	settings.mForward = st.forward;
+	settings.mMaxPitchRollAngle = st.maxPitchRollAngle;
+	settings.mAntiRollBars.reserve(st.antiRollBars.size());
+	for (const XVehicleAntiRollBar& b : st.antiRollBars) {
+		VehicleAntiRollBar bar;
+		bar.mLeftWheel = b.leftWheel;
+		bar.mRightWheel = b.rightWheel;
+		bar.mStiffness = b.stiffness;
+		settings.mAntiRollBars.push_back(bar);
+	}
+}
+
+template <class X>
+static void CopyWheelSettings(WheelSettings& wheel, const X& st) {
+	wheel.mPosition = st.position;
+	wheel.mSuspensionForcePoint = st.suspensionForcePoint;
+	wheel.mSuspensionDirection = st.suspensionDirection;
+	wheel.mSteeringAxis = st.steeringAxis;
+	wheel.mWheelUp = st.wheelUp;
+	wheel.mWheelForward = st.wheelForward;
+	wheel.mSuspensionMinLength = st.suspensionMinLength;
+	wheel.mSuspensionMaxLength = st.suspensionMaxLength;
+	wheel.mSuspensionPreloadLength = st.suspensionPreloadLength;
+	wheel.mSuspensionSpring = st.suspensionSpring;
+	wheel.mRadius = st.radius;
+	wheel.mWidth = st.width;
+	wheel.mEnableSuspensionForcePoint = st.enableSuspensionForcePoint;
+}
+
+static void CopyWheelsWV(VehicleConstraintSettings& settings, const XWheelSettingsWV* wheels, uint32 numWheels) {
+	settings.mWheels.reserve(numWheels);
+	for (uint32 idx = 0; idx < numWheels; ++idx) {
+		const XWheelSettingsWV& w = wheels[idx];
+		Ref<WheelSettingsWV> wheel = new WheelSettingsWV();
+		CopyWheelSettings(*wheel, w);
+		wheel->mInertia = w.inertia;
+		wheel->mAngularDamping = w.angularDamping;
+		wheel->mMaxSteerAngle = w.maxSteerAngle;
+		wheel->mMaxBrakeTorque = w.maxBrakeTorque;
+		wheel->mMaxHandBrakeTorque = w.maxHandBrakeTorque;
+		CopyLinearCurve(wheel->mLongitudinalFriction, w.longitudinalFriction);
+		CopyLinearCurve(wheel->mLateralFriction, w.lateralFriction);
+		settings.mWheels.push_back(wheel);
+	}
+}
+
+static void CopyEngineSettings(VehicleEngineSettings& engine, const XVehicleEngineSettings& st) {
+	engine.mMaxTorque = st.maxTorque;
+	engine.mMinRPM = st.minRPM;
+	engine.mMaxRPM = st.maxRPM;
+	engine.mInertia = st.inertia;
+	engine.mAngularDamping = st.angularDamping;
+	CopyLinearCurve(engine.mNormalizedTorque, st.normalizedTorque);
+}
+
+static void CopyTransmissionSettings(VehicleTransmissionSettings& transmission, const XVehicleTransmissionSettings& st) {
+	transmission.mMode = st.mode;
+	transmission.mSwitchTime = st.switchTime;
+	transmission.mClutchReleaseTime = st.clutchReleaseTime;
+	transmission.mSwitchLatency = st.switchLatency;
+	transmission.mShiftUpRPM = st.shiftUpRPM;
+	transmission.mShiftDownRPM = st.shiftDownRPM;
+	transmission.mClutchStrength = st.clutchStrength;
+	if (!st.gearRatios.empty()) {
+		transmission.mGearRatios.assign(st.gearRatios.begin(), st.gearRatios.end());
+	}
+	if (!st.reverseGearRatios.empty()) {
+		transmission.mReverseGearRatios.assign(st.reverseGearRatios.begin(), st.reverseGearRatios.end());
+	}
+}
+
+static void CopyWheeledControllerSettings(WheeledVehicleControllerSettings& controller, const XWheeledVehicleControllerSettings& st) {
+	CopyEngineSettings(controller.mEngine, st.engine);
+	CopyTransmissionSettings(controller.mTransmission, st.transmission);
+	controller.mDifferentials.reserve(st.differentials.size());
+	for (const XVehicleDifferentialSettings& d : st.differentials) {
+		VehicleDifferentialSettings differential;
+		differential.mLeftWheel = d.leftWheel;
+		differential.mRightWheel = d.rightWheel;
+		differential.mDifferentialRatio = d.differentialRatio;
+		differential.mLeftRightSplit = d.leftRightSplit;
+		differential.mLimitedSlipRatio = d.limitedSlipRatio;
+		differential.mEngineTorqueRatio = d.engineTorqueRatio;
+		controller.mDifferentials.push_back(differential);
+	}
+	controller.mDifferentialLimitedSlipRatio = st.differentialLimitedSlipRatio;
+}
+
+static VehicleCollisionTester* CreateVehicleCollisionTester(const XVehicleConstraintSettings& st) {
+	switch (st.testerType) {
+	case VehicleCollisionTesterType::CastSphere:
+		return new VehicleCollisionTesterCastSphere(st.testerLayer, st.testerRadius, st.up, st.testerMaxSlopeAngle);
+	case VehicleCollisionTesterType::CastCylinder:
+		return new VehicleCollisionTesterCastCylinder(st.testerLayer, st.testerConvexRadiusFraction);
+	default:
+		return new VehicleCollisionTesterRay(st.testerLayer, st.up, st.testerMaxSlopeAngle);
+	}
+}
+
+static XVehicleConstraint* CreateVehicleConstraint(
+	XPhysicsSystem* system,
+	const BodyID& body,
+	const VehicleConstraintSettings& settings,
+	VehicleControllerType controllerType,
+	const XVehicleConstraintSettings& st
+) {
+	BodyLockWrite lock(system->PhySys().GetBodyLockInterface(), body);
+	if (!lock.Succeeded()) {
+		return nullptr;
+	}
+	Ref<XVehicleConstraint> constraint = new XVehicleConstraint(Ref(system), lock.GetBody(), settings, controllerType);
+	constraint->SetVehicleCollisionTester(CreateVehicleCollisionTester(st));
+	return LeakRefT<XVehicleConstraint>(constraint);
+}
+
+XVehicleConstraint* CreateWheeledVehicleConstraint(
+	XPhysicsSystem* system,
+	const BodyID& body,
+	const XVehicleConstraintSettings& st,
+	const XWheelSettingsWV* wheels,
+	uint32 numWheels,
+	const XWheeledVehicleControllerSettings& ct
+) {
+	VehicleConstraintSettings settings;
+	CopyVehicleConstraintSettings(settings, st);
+	CopyWheelsWV(settings, wheels, numWheels);
+	Ref<WheeledVehicleControllerSettings> controller = new WheeledVehicleControllerSettings();
+	CopyWheeledControllerSettings(*controller, ct);
+	settings.mController = controller;
+	return CreateVehicleConstraint(system, body, settings, VehicleControllerType::Wheeled, st);
+}
+
+XVehicleConstraint* CreateTrackedVehicleConstraint(
+	XPhysicsSystem* system,
+	const BodyID& body,
+	const XVehicleConstraintSettings& st,
+	const XWheelSettingsTV* wheels,
+	uint32 numWheels,
+	const XTrackedVehicleControllerSettings& ct
+) {
+	VehicleConstraintSettings settings;
+	CopyVehicleConstraintSettings(settings, st);
+	settings.mWheels.reserve(numWheels);
+	for (uint32 idx = 0; idx < numWheels; ++idx) {
+		const XWheelSettingsTV& w = wheels[idx];
+		Ref<WheelSettingsTV> wheel = new WheelSettingsTV();
+		CopyWheelSettings(*wheel, w);
+		wheel->mLongitudinalFriction = w.longitudinalFriction;
+		wheel->mLateralFriction = w.lateralFriction;
+		settings.mWheels.push_back(wheel);
+	}
+	Ref<TrackedVehicleControllerSettings> controller = new TrackedVehicleControllerSettings();
+	CopyEngineSettings(controller->mEngine, ct.engine);
+	CopyTransmissionSettings(controller->mTransmission, ct.transmission);
+	for (int side = 0; side < (int)ETrackSide::Num; ++side) {
+		const XVehicleTrackSettings& t = ct.tracks[side];
+		VehicleTrackSettings& track = controller->mTracks[side];
+		track.mDrivenWheel = t.drivenWheel;
+		track.mWheels.assign(t.wheels.begin(), t.wheels.end());
+		track.mInertia = t.inertia;
+		track.mAngularDamping = t.angularDamping;
+		track.mMaxBrakeTorque = t.maxBrakeTorque;
+		track.mDifferentialRatio = t.differentialRatio;
+	}
+	settings.mController = controller;
+	return CreateVehicleConstraint(system, body, settings, VehicleControllerType::Tracked, st);
+}
+
+XVehicleConstraint* CreateMotorcycleConstraint(
+	XPhysicsSystem* system,
+	const BodyID& body,
+	const XVehicleConstraintSettings& st,
+	const XWheelSettingsWV* wheels,
+	uint32 numWheels,
+	const XMotorcycleControllerSettings& ct
+) {
+	VehicleConstraintSettings settings;
+	CopyVehicleConstraintSettings(settings, st);
+	CopyWheelsWV(settings, wheels, numWheels);
+	Ref<MotorcycleControllerSettings> controller = new MotorcycleControllerSettings();
+	CopyWheeledControllerSettings(*controller, ct.wheeled);
+	controller->mMaxLeanAngle = ct.maxLeanAngle;
+	controller->mLeanSpringConstant = ct.leanSpringConstant;
+	controller->mLeanSpringDamping = ct.leanSpringDamping;
+	controller->mLeanSpringIntegrationCoefficient = ct.leanSpringIntegrationCoefficient;
+	controller->mLeanSpringIntegrationCoefficientDecay = ct.leanSpringIntegrationCoefficientDecay;
+	controller->mLeanSmoothingFactor = ct.leanSmoothingFactor;
+	settings.mController = controller;
+	return CreateVehicleConstraint(system, body, settings, VehicleControllerType::Motorcycle, st);
+}
+
+bool AddVehicleConstraint(XPhysicsSystem* system, XVehicleConstraint* constraint) {
+	return system->AddConstraint(constraint);
+}
+
+bool RemoveVehicleConstraint(XPhysicsSystem* system, XVehicleConstraint* constraint) {
+	return system->RemoveConstraint(constraint);
+}
+
+void XVehicleConstraint::GetWheelState(uint32 index, XWheelState& state) const {
+	const Wheel* wheel = GetWheel(index);
+	state.hasContact = wheel->HasContact();
+	state.contactBodyID = wheel->GetContactBodyID();
+	state.contactPosition = Vec3(wheel->GetContactPosition());
+	state.contactNormal = wheel->GetContactNormal();
+	state.suspensionLength = wheel->GetSuspensionLength();
+	state.angularVelocity = wheel->GetAngularVelocity();
+	state.rotationAngle = wheel->GetRotationAngle();
+	state.steerAngle = wheel->GetSteerAngle();
+}
+
+const WheeledVehicleController* XVehicleConstraint::GetWheeledController() const {
+	if (this->_controllerType != VehicleControllerType::Wheeled && this->_controllerType != VehicleControllerType::Motorcycle) {
+		return nullptr;
+	}
+	return static_cast<const WheeledVehicleController*>(GetController());
+}
+
+WheeledVehicleController* XVehicleConstraint::GetWheeledController() {
+	if (this->_controllerType != VehicleControllerType::Wheeled && this->_controllerType != VehicleControllerType::Motorcycle) {
+		return nullptr;
+	}
+	return static_cast<WheeledVehicleController*>(GetController());
+}
+
+const TrackedVehicleController* XVehicleConstraint::GetTrackedController() const {
+	if (this->_controllerType != VehicleControllerType::Tracked) {
+		return nullptr;
+	}
+	return static_cast<const TrackedVehicleController*>(GetController());
+}
+
+TrackedVehicleController* XVehicleConstraint::GetTrackedController() {
+	if (this->_controllerType != VehicleControllerType::Tracked) {
+		return nullptr;
+	}
+	return static_cast<TrackedVehicleController*>(GetController());
+}
+
+const MotorcycleController* XVehicleConstraint::GetMotorcycleController() const {
+	if (this->_controllerType != VehicleControllerType::Motorcycle) {
+		return nullptr;
+	}
+	return static_cast<const MotorcycleController*>(GetController());
+}
+
+MotorcycleController* XVehicleConstraint::GetMotorcycleController() {
+	if (this->_controllerType != VehicleControllerType::Motorcycle) {
+		return nullptr;
+	}
+	return static_cast<MotorcycleController*>(GetController());
+}
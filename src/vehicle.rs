@@ -0,0 +1,1467 @@
+use core::fmt;
+use cxx::{kind, type_id, ExternType};
+use glam::{Mat4, Vec3A};
+use static_assertions::const_assert_eq;
+use std::mem;
+use std::pin::Pin;
+use std::ptr::NonNull;
+
+use crate::base::{BodyID, JMut, JMutTarget, JRef, JRefTarget, ObjectLayer};
+use crate::constraint::{Constraint, SpringMode, SpringSettings};
+use crate::error::{JoltError, JoltResult};
+use crate::soft_body::SoftBodyContactListener;
+use crate::system::{BodyActivationListener, ContactListener, PhysicsSystem};
+
+#[cxx::bridge()]
+pub(crate) mod ffi {
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum VehicleControllerType {
+        Wheeled,
+        Tracked,
+        Motorcycle,
+    }
+
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum VehicleCollisionTesterType {
+        Ray,
+        CastSphere,
+        CastCylinder,
+    }
+
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum TransmissionMode {
+        Auto,
+        Manual,
+    }
+
+    unsafe extern "C++" {
+        include!("rust/cxx.h");
+        include!("jolt-physics-rs/src/ffi.h");
+
+        type VehicleControllerType;
+        type VehicleCollisionTesterType;
+        type TransmissionMode;
+
+        type Vec3 = crate::base::ffi::Vec3;
+        type Mat44 = crate::base::ffi::Mat44;
+        type BodyID = crate::base::ffi::BodyID;
+        type XPhysicsSystem = crate::system::ffi::XPhysicsSystem;
+
+        type XVehicleConstraintSettings = crate::vehicle::VehicleConstraintSettings<'static>;
+        type XWheelSettingsWV = crate::vehicle::WheelSettingsWV<'static>;
+        type XWheeledVehicleControllerSettings = crate::vehicle::WheeledVehicleControllerSettings<'static>;
+        type XWheelSettingsTV = crate::vehicle::WheelSettingsTV;
+        type XTrackedVehicleControllerSettings = crate::vehicle::TrackedVehicleControllerSettings<'static>;
+        type XMotorcycleControllerSettings = crate::vehicle::MotorcycleControllerSettings<'static>;
+        type XWheelState = crate::vehicle::WheelState;
+
+        type XVehicleConstraint;
+        unsafe fn DropXVehicleConstraint(constraint: *mut XVehicleConstraint);
+        unsafe fn CloneXVehicleConstraint(constraint: *mut XVehicleConstraint) -> *mut XVehicleConstraint;
+        unsafe fn CountRefXVehicleConstraint(constraint: *const XVehicleConstraint) -> u32;
+
+        unsafe fn CreateWheeledVehicleConstraint(
+            system: *mut XPhysicsSystem,
+            body: &BodyID,
+            settings: &XVehicleConstraintSettings,
+            wheels: *const XWheelSettingsWV,
+            num_wheels: u32,
+            controller: &XWheeledVehicleControllerSettings,
+        ) -> *mut XVehicleConstraint;
+        unsafe fn CreateTrackedVehicleConstraint(
+            system: *mut XPhysicsSystem,
+            body: &BodyID,
+            settings: &XVehicleConstraintSettings,
+            wheels: *const XWheelSettingsTV,
+            num_wheels: u32,
+            controller: &XTrackedVehicleControllerSettings,
+        ) -> *mut XVehicleConstraint;
+        unsafe fn CreateMotorcycleConstraint(
+            system: *mut XPhysicsSystem,
+            body: &BodyID,
+            settings: &XVehicleConstraintSettings,
+            wheels: *const XWheelSettingsWV,
+            num_wheels: u32,
+            controller: &XMotorcycleControllerSettings,
+        ) -> *mut XVehicleConstraint;
+        unsafe fn AddVehicleConstraint(system: *mut XPhysicsSystem, constraint: *mut XVehicleConstraint) -> bool;
+        unsafe fn RemoveVehicleConstraint(system: *mut XPhysicsSystem, constraint: *mut XVehicleConstraint) -> bool;
+
+        fn GetControllerType(self: &XVehicleConstraint) -> VehicleControllerType;
+        fn SetMaxPitchRollAngle(self: Pin<&mut XVehicleConstraint>, angle: f32);
+        fn GetNumStepsBetweenCollisionTestActive(self: &XVehicleConstraint) -> u32;
+        fn SetNumStepsBetweenCollisionTestActive(self: Pin<&mut XVehicleConstraint>, steps: u32);
+        fn GetNumStepsBetweenCollisionTestInactive(self: &XVehicleConstraint) -> u32;
+        fn SetNumStepsBetweenCollisionTestInactive(self: Pin<&mut XVehicleConstraint>, steps: u32);
+        fn GetLocalUp(self: &XVehicleConstraint) -> Vec3;
+        fn GetLocalForward(self: &XVehicleConstraint) -> Vec3;
+        fn GetWorldUp(self: &XVehicleConstraint) -> Vec3;
+        fn GetNumWheels(self: &XVehicleConstraint) -> u32;
+        fn GetWheelState(self: &XVehicleConstraint, index: u32, state: &mut XWheelState);
+        fn GetWheelLocalTransform(self: &XVehicleConstraint, index: u32, right: Vec3, up: Vec3) -> Mat44;
+        fn GetWheelWorldTransform(self: &XVehicleConstraint, index: u32, right: Vec3, up: Vec3) -> Mat44;
+        fn GetWheeledController(self: &XVehicleConstraint) -> *const WheeledVehicleController;
+        #[rust_name = "GetWheeledControllerMut"]
+        fn GetWheeledController(self: Pin<&mut XVehicleConstraint>) -> *mut WheeledVehicleController;
+        fn GetTrackedController(self: &XVehicleConstraint) -> *const TrackedVehicleController;
+        #[rust_name = "GetTrackedControllerMut"]
+        fn GetTrackedController(self: Pin<&mut XVehicleConstraint>) -> *mut TrackedVehicleController;
+        fn GetMotorcycleController(self: &XVehicleConstraint) -> *const MotorcycleController;
+        #[rust_name = "GetMotorcycleControllerMut"]
+        fn GetMotorcycleController(self: Pin<&mut XVehicleConstraint>) -> *mut MotorcycleController;
+
+        type WheeledVehicleController;
+        fn SetDriverInput(
+            self: Pin<&mut WheeledVehicleController>,
+            forward: f32,
+            right: f32,
+            brake: f32,
+            hand_brake: f32,
+        );
+        fn SetForwardInput(self: Pin<&mut WheeledVehicleController>, forward: f32);
+        fn GetForwardInput(self: &WheeledVehicleController) -> f32;
+        fn SetRightInput(self: Pin<&mut WheeledVehicleController>, right: f32);
+        fn GetRightInput(self: &WheeledVehicleController) -> f32;
+        fn SetBrakeInput(self: Pin<&mut WheeledVehicleController>, brake: f32);
+        fn GetBrakeInput(self: &WheeledVehicleController) -> f32;
+        fn SetHandBrakeInput(self: Pin<&mut WheeledVehicleController>, hand_brake: f32);
+        fn GetHandBrakeInput(self: &WheeledVehicleController) -> f32;
+        fn GetDifferentialLimitedSlipRatio(self: &WheeledVehicleController) -> f32;
+        fn SetDifferentialLimitedSlipRatio(self: Pin<&mut WheeledVehicleController>, ratio: f32);
+        fn GetWheelSpeedAtClutch(self: &WheeledVehicleController) -> f32;
+        fn GetWheeledVehicleEngineRPM(controller: &WheeledVehicleController) -> f32;
+        fn GetWheeledVehicleCurrentGear(controller: &WheeledVehicleController) -> i32;
+        fn GetWheeledVehicleClutchFriction(controller: &WheeledVehicleController) -> f32;
+        fn SetWheeledVehicleTransmission(controller: Pin<&mut WheeledVehicleController>, gear: i32, clutch: f32);
+
+        type TrackedVehicleController;
+        fn SetDriverInput(
+            self: Pin<&mut TrackedVehicleController>,
+            forward: f32,
+            left_ratio: f32,
+            right_ratio: f32,
+            brake: f32,
+        );
+        fn SetForwardInput(self: Pin<&mut TrackedVehicleController>, forward: f32);
+        fn GetForwardInput(self: &TrackedVehicleController) -> f32;
+        fn SetLeftRatio(self: Pin<&mut TrackedVehicleController>, ratio: f32);
+        fn GetLeftRatio(self: &TrackedVehicleController) -> f32;
+        fn SetRightRatio(self: Pin<&mut TrackedVehicleController>, ratio: f32);
+        fn GetRightRatio(self: &TrackedVehicleController) -> f32;
+        fn SetBrakeInput(self: Pin<&mut TrackedVehicleController>, brake: f32);
+        fn GetBrakeInput(self: &TrackedVehicleController) -> f32;
+        fn GetTrackedVehicleEngineRPM(controller: &TrackedVehicleController) -> f32;
+        fn GetTrackedVehicleCurrentGear(controller: &TrackedVehicleController) -> i32;
+        fn GetTrackedVehicleClutchFriction(controller: &TrackedVehicleController) -> f32;
+        fn SetTrackedVehicleTransmission(controller: Pin<&mut TrackedVehicleController>, gear: i32, clutch: f32);
+        fn GetTrackedVehicleTrackAngularVelocity(controller: &TrackedVehicleController, side: u32) -> f32;
+
+        type MotorcycleController;
+        fn GetWheelBase(self: &MotorcycleController) -> f32;
+        fn EnableLeanController(self: Pin<&mut MotorcycleController>, enable: bool);
+        fn IsLeanControllerEnabled(self: &MotorcycleController) -> bool;
+        fn SetLeanSpringConstant(self: Pin<&mut MotorcycleController>, constant: f32);
+        fn GetLeanSpringConstant(self: &MotorcycleController) -> f32;
+        fn SetLeanSpringDamping(self: Pin<&mut MotorcycleController>, damping: f32);
+        fn GetLeanSpringDamping(self: &MotorcycleController) -> f32;
+        fn SetLeanSpringIntegrationCoefficient(self: Pin<&mut MotorcycleController>, coefficient: f32);
+        fn GetLeanSpringIntegrationCoefficient(self: &MotorcycleController) -> f32;
+        fn SetLeanSpringIntegrationCoefficientDecay(self: Pin<&mut MotorcycleController>, decay: f32);
+        fn GetLeanSpringIntegrationCoefficientDecay(self: &MotorcycleController) -> f32;
+        fn SetLeanSmoothingFactor(self: Pin<&mut MotorcycleController>, factor: f32);
+        fn GetLeanSmoothingFactor(self: &MotorcycleController) -> f32;
+    }
+}
+
+pub type VehicleControllerType = ffi::VehicleControllerType;
+pub type VehicleCollisionTesterType = ffi::VehicleCollisionTesterType;
+pub type TransmissionMode = ffi::TransmissionMode;
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct LinearCurvePoint {
+    pub x: f32,
+    pub y: f32,
+}
+const_assert_eq!(mem::size_of::<LinearCurvePoint>(), 8);
+
+impl LinearCurvePoint {
+    pub const fn new(x: f32, y: f32) -> LinearCurvePoint {
+        LinearCurvePoint { x, y }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VehicleAntiRollBar {
+    pub left_wheel: i32,
+    pub right_wheel: i32,
+    pub stiffness: f32,
+}
+const_assert_eq!(mem::size_of::<VehicleAntiRollBar>(), 12);
+
+impl Default for VehicleAntiRollBar {
+    fn default() -> VehicleAntiRollBar {
+        VehicleAntiRollBar {
+            left_wheel: 0,
+            right_wheel: 1,
+            stiffness: 1000.0,
+        }
+    }
+}
+
+impl VehicleAntiRollBar {
+    pub fn new(left_wheel: i32, right_wheel: i32, stiffness: f32) -> VehicleAntiRollBar {
+        VehicleAntiRollBar {
+            left_wheel,
+            right_wheel,
+            stiffness,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VehicleConstraintSettings<'t> {
+    pub user_data: u64,
+    pub enabled: bool,
+    pub constraint_priority: u32,
+    pub num_velocity_steps_override: u32,
+    pub num_position_steps_override: u32,
+    pub up: Vec3A,
+    pub forward: Vec3A,
+    pub max_pitch_roll_angle: f32,
+    pub tester_type: VehicleCollisionTesterType,
+    /// Object layer used by the wheel collision tester.
+    pub tester_layer: ObjectLayer,
+    /// Ignored by `VehicleCollisionTesterType::CastCylinder`.
+    pub tester_max_slope_angle: f32,
+    /// Only used by `VehicleCollisionTesterType::CastSphere`.
+    pub tester_radius: f32,
+    /// Only used by `VehicleCollisionTesterType::CastCylinder`.
+    pub tester_convex_radius_fraction: f32,
+    pub anti_roll_bars: &'t [VehicleAntiRollBar],
+}
+const_assert_eq!(mem::size_of::<VehicleConstraintSettings>(), 112);
+
+unsafe impl ExternType for VehicleConstraintSettings<'_> {
+    type Id = type_id!("XVehicleConstraintSettings");
+    type Kind = kind::Trivial;
+}
+
+impl<'t> Default for VehicleConstraintSettings<'t> {
+    fn default() -> VehicleConstraintSettings<'t> {
+        VehicleConstraintSettings {
+            user_data: 0,
+            enabled: true,
+            constraint_priority: 0,
+            num_velocity_steps_override: 0,
+            num_position_steps_override: 0,
+            up: Vec3A::Y,
+            forward: Vec3A::Z,
+            max_pitch_roll_angle: std::f32::consts::PI,
+            tester_type: VehicleCollisionTesterType::Ray,
+            tester_layer: 0,
+            tester_max_slope_angle: 80f32.to_radians(),
+            tester_radius: 0.3,
+            tester_convex_radius_fraction: 0.1,
+            anti_roll_bars: &[],
+        }
+    }
+}
+
+impl<'t> VehicleConstraintSettings<'t> {
+    pub fn new(tester_layer: ObjectLayer) -> VehicleConstraintSettings<'t> {
+        VehicleConstraintSettings {
+            tester_layer,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct WheelSettingsWV<'t> {
+    pub position: Vec3A,
+    pub suspension_force_point: Vec3A,
+    pub suspension_direction: Vec3A,
+    pub steering_axis: Vec3A,
+    pub wheel_up: Vec3A,
+    pub wheel_forward: Vec3A,
+    pub suspension_min_length: f32,
+    pub suspension_max_length: f32,
+    pub suspension_preload_length: f32,
+    pub suspension_spring: SpringSettings,
+    pub radius: f32,
+    pub width: f32,
+    pub enable_suspension_force_point: bool,
+    pub inertia: f32,
+    pub angular_damping: f32,
+    pub max_steer_angle: f32,
+    pub max_brake_torque: f32,
+    pub max_hand_brake_torque: f32,
+    /// Friction over longitudinal slip ratio, empty slice keeps the default curve.
+    pub longitudinal_friction: &'t [LinearCurvePoint],
+    /// Friction over lateral slip angle in degrees, empty slice keeps the default curve.
+    pub lateral_friction: &'t [LinearCurvePoint],
+}
+const_assert_eq!(mem::size_of::<WheelSettingsWV>(), 192);
+
+unsafe impl ExternType for WheelSettingsWV<'_> {
+    type Id = type_id!("XWheelSettingsWV");
+    type Kind = kind::Trivial;
+}
+
+impl<'t> Default for WheelSettingsWV<'t> {
+    fn default() -> WheelSettingsWV<'t> {
+        WheelSettingsWV {
+            position: Vec3A::ZERO,
+            suspension_force_point: Vec3A::ZERO,
+            suspension_direction: Vec3A::NEG_Y,
+            steering_axis: Vec3A::Y,
+            wheel_up: Vec3A::Y,
+            wheel_forward: Vec3A::Z,
+            suspension_min_length: 0.3,
+            suspension_max_length: 0.5,
+            suspension_preload_length: 0.0,
+            suspension_spring: SpringSettings {
+                mode: SpringMode::FrequencyAndDamping,
+                frequency_or_stiffness: 1.5,
+                damping: 0.5,
+            },
+            radius: 0.3,
+            width: 0.1,
+            enable_suspension_force_point: false,
+            inertia: 0.9,
+            angular_damping: 0.2,
+            max_steer_angle: 70f32.to_radians(),
+            max_brake_torque: 1500.0,
+            max_hand_brake_torque: 4000.0,
+            longitudinal_friction: &[],
+            lateral_friction: &[],
+        }
+    }
+}
+
+impl<'t> WheelSettingsWV<'t> {
+    pub fn new(position: Vec3A, radius: f32, width: f32) -> WheelSettingsWV<'t> {
+        WheelSettingsWV {
+            position,
+            radius,
+            width,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct WheelSettingsTV {
+    pub position: Vec3A,
+    pub suspension_force_point: Vec3A,
+    pub suspension_direction: Vec3A,
+    pub steering_axis: Vec3A,
+    pub wheel_up: Vec3A,
+    pub wheel_forward: Vec3A,
+    pub suspension_min_length: f32,
+    pub suspension_max_length: f32,
+    pub suspension_preload_length: f32,
+    pub suspension_spring: SpringSettings,
+    pub radius: f32,
+    pub width: f32,
+    pub enable_suspension_force_point: bool,
+    pub longitudinal_friction: f32,
+    pub lateral_friction: f32,
+}
+const_assert_eq!(mem::size_of::<WheelSettingsTV>(), 144);
+
+unsafe impl ExternType for WheelSettingsTV {
+    type Id = type_id!("XWheelSettingsTV");
+    type Kind = kind::Trivial;
+}
+
+impl Default for WheelSettingsTV {
+    fn default() -> WheelSettingsTV {
+        WheelSettingsTV {
+            position: Vec3A::ZERO,
+            suspension_force_point: Vec3A::ZERO,
+            suspension_direction: Vec3A::NEG_Y,
+            steering_axis: Vec3A::Y,
+            wheel_up: Vec3A::Y,
+            wheel_forward: Vec3A::Z,
+            suspension_min_length: 0.3,
+            suspension_max_length: 0.5,
+            suspension_preload_length: 0.0,
+            suspension_spring: SpringSettings {
+                mode: SpringMode::FrequencyAndDamping,
+                frequency_or_stiffness: 1.5,
+                damping: 0.5,
+            },
+            radius: 0.3,
+            width: 0.1,
+            enable_suspension_force_point: false,
+            longitudinal_friction: 4.0,
+            lateral_friction: 2.0,
+        }
+    }
+}
+
+impl WheelSettingsTV {
+    pub fn new(position: Vec3A, radius: f32, width: f32) -> WheelSettingsTV {
+        WheelSettingsTV {
+            position,
+            radius,
+            width,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VehicleEngineSettings<'t> {
+    pub max_torque: f32,
+    pub min_rpm: f32,
+    pub max_rpm: f32,
+    pub inertia: f32,
+    pub angular_damping: f32,
+    /// Torque over normalized rpm, empty slice keeps the default curve.
+    pub normalized_torque: &'t [LinearCurvePoint],
+}
+const_assert_eq!(mem::size_of::<VehicleEngineSettings>(), 40);
+
+impl<'t> Default for VehicleEngineSettings<'t> {
+    fn default() -> VehicleEngineSettings<'t> {
+        VehicleEngineSettings {
+            max_torque: 500.0,
+            min_rpm: 1000.0,
+            max_rpm: 6000.0,
+            inertia: 0.5,
+            angular_damping: 0.2,
+            normalized_torque: &[],
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VehicleTransmissionSettings<'t> {
+    pub mode: TransmissionMode,
+    pub switch_time: f32,
+    pub clutch_release_time: f32,
+    pub switch_latency: f32,
+    pub shift_up_rpm: f32,
+    pub shift_down_rpm: f32,
+    pub clutch_strength: f32,
+    /// Empty slice keeps the default ratios.
+    pub gear_ratios: &'t [f32],
+    /// Empty slice keeps the default ratios.
+    pub reverse_gear_ratios: &'t [f32],
+}
+const_assert_eq!(mem::size_of::<VehicleTransmissionSettings>(), 64);
+
+impl<'t> Default for VehicleTransmissionSettings<'t> {
+    fn default() -> VehicleTransmissionSettings<'t> {
+        VehicleTransmissionSettings {
+            mode: TransmissionMode::Auto,
+            switch_time: 0.5,
+            clutch_release_time: 0.3,
+            switch_latency: 0.5,
+            shift_up_rpm: 4000.0,
+            shift_down_rpm: 2000.0,
+            clutch_strength: 10.0,
+            gear_ratios: &[],
+            reverse_gear_ratios: &[],
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VehicleDifferentialSettings {
+    pub left_wheel: i32,
+    pub right_wheel: i32,
+    pub differential_ratio: f32,
+    pub left_right_split: f32,
+    pub limited_slip_ratio: f32,
+    pub engine_torque_ratio: f32,
+}
+const_assert_eq!(mem::size_of::<VehicleDifferentialSettings>(), 24);
+
+impl Default for VehicleDifferentialSettings {
+    fn default() -> VehicleDifferentialSettings {
+        VehicleDifferentialSettings {
+            left_wheel: -1,
+            right_wheel: -1,
+            differential_ratio: 3.42,
+            left_right_split: 0.5,
+            limited_slip_ratio: 1.4,
+            engine_torque_ratio: 1.0,
+        }
+    }
+}
+
+impl VehicleDifferentialSettings {
+    pub fn new(left_wheel: i32, right_wheel: i32) -> VehicleDifferentialSettings {
+        VehicleDifferentialSettings {
+            left_wheel,
+            right_wheel,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct WheeledVehicleControllerSettings<'t> {
+    pub engine: VehicleEngineSettings<'t>,
+    pub transmission: VehicleTransmissionSettings<'t>,
+    pub differentials: &'t [VehicleDifferentialSettings],
+    pub differential_limited_slip_ratio: f32,
+}
+const_assert_eq!(mem::size_of::<WheeledVehicleControllerSettings>(), 128);
+
+unsafe impl ExternType for WheeledVehicleControllerSettings<'_> {
+    type Id = type_id!("XWheeledVehicleControllerSettings");
+    type Kind = kind::Trivial;
+}
+
+impl<'t> Default for WheeledVehicleControllerSettings<'t> {
+    fn default() -> WheeledVehicleControllerSettings<'t> {
+        WheeledVehicleControllerSettings {
+            engine: VehicleEngineSettings::default(),
+            transmission: VehicleTransmissionSettings::default(),
+            differentials: &[],
+            differential_limited_slip_ratio: 1.4,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VehicleTrackSettings<'t> {
+    /// Index of the wheel that drives the track.
+    pub driven_wheel: u32,
+    /// Indices of all wheels on the track, including the driven wheel.
+    pub wheels: &'t [u32],
+    pub inertia: f32,
+    pub angular_damping: f32,
+    pub max_brake_torque: f32,
+    pub differential_ratio: f32,
+}
+const_assert_eq!(mem::size_of::<VehicleTrackSettings>(), 40);
+
+impl<'t> Default for VehicleTrackSettings<'t> {
+    fn default() -> VehicleTrackSettings<'t> {
+        VehicleTrackSettings {
+            driven_wheel: 0,
+            wheels: &[],
+            inertia: 10.0,
+            angular_damping: 0.5,
+            max_brake_torque: 15000.0,
+            differential_ratio: 6.0,
+        }
+    }
+}
+
+impl<'t> VehicleTrackSettings<'t> {
+    pub fn new(driven_wheel: u32, wheels: &'t [u32]) -> VehicleTrackSettings<'t> {
+        VehicleTrackSettings {
+            driven_wheel,
+            wheels,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Default)]
+pub struct TrackedVehicleControllerSettings<'t> {
+    pub engine: VehicleEngineSettings<'t>,
+    pub transmission: VehicleTransmissionSettings<'t>,
+    /// Left and right track.
+    pub tracks: [VehicleTrackSettings<'t>; 2],
+}
+const_assert_eq!(mem::size_of::<TrackedVehicleControllerSettings>(), 184);
+
+unsafe impl ExternType for TrackedVehicleControllerSettings<'_> {
+    type Id = type_id!("XTrackedVehicleControllerSettings");
+    type Kind = kind::Trivial;
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct MotorcycleControllerSettings<'t> {
+    pub wheeled: WheeledVehicleControllerSettings<'t>,
+    pub max_lean_angle: f32,
+    pub lean_spring_constant: f32,
+    pub lean_spring_damping: f32,
+    pub lean_spring_integration_coefficient: f32,
+    pub lean_spring_integration_coefficient_decay: f32,
+    pub lean_smoothing_factor: f32,
+}
+const_assert_eq!(mem::size_of::<MotorcycleControllerSettings>(), 152);
+
+unsafe impl ExternType for MotorcycleControllerSettings<'_> {
+    type Id = type_id!("XMotorcycleControllerSettings");
+    type Kind = kind::Trivial;
+}
+
+impl<'t> Default for MotorcycleControllerSettings<'t> {
+    fn default() -> MotorcycleControllerSettings<'t> {
+        MotorcycleControllerSettings {
+            wheeled: WheeledVehicleControllerSettings::default(),
+            max_lean_angle: 45f32.to_radians(),
+            lean_spring_constant: 5000.0,
+            lean_spring_damping: 1000.0,
+            lean_spring_integration_coefficient: 0.0,
+            lean_spring_integration_coefficient_decay: 4.0,
+            lean_smoothing_factor: 0.8,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct WheelState {
+    pub contact_position: Vec3A,
+    pub contact_normal: Vec3A,
+    pub contact_body_id: BodyID,
+    pub has_contact: bool,
+    pub suspension_length: f32,
+    /// Angular velocity around the wheel axle in rad/s.
+    pub angular_velocity: f32,
+    pub rotation_angle: f32,
+    pub steer_angle: f32,
+}
+const_assert_eq!(mem::size_of::<WheelState>(), 64);
+
+unsafe impl ExternType for WheelState {
+    type Id = type_id!("XWheelState");
+    type Kind = kind::Trivial;
+}
+
+impl Default for WheelState {
+    fn default() -> Self {
+        WheelState {
+            contact_position: Vec3A::ZERO,
+            contact_normal: Vec3A::ZERO,
+            contact_body_id: BodyID::INVALID,
+            has_contact: false,
+            suspension_length: 0.0,
+            angular_velocity: 0.0,
+            rotation_angle: 0.0,
+            steer_angle: 0.0,
+        }
+    }
+}
+
+impl WheelState {
+    #[inline]
+    pub fn get_rpm(&self) -> f32 {
+        self.angular_velocity * 60.0 / (2.0 * std::f32::consts::PI)
+    }
+}
+
+/// Differentials may leave a side unconnected with -1, anti-roll bars always connect two wheels.
+fn check_wheel_indices(num_wheels: usize, allow_none: bool, indices: impl Iterator<Item = i32>) -> bool {
+    let min = if allow_none { -1 } else { 0 };
+    indices.into_iter().all(|idx| idx >= min && idx < num_wheels as i32)
+}
+
+fn check_vehicle_settings(num_wheels: usize, settings: &VehicleConstraintSettings) -> bool {
+    num_wheels > 0
+        && check_wheel_indices(
+            num_wheels,
+            false,
+            (settings.anti_roll_bars.iter()).flat_map(|b| [b.left_wheel, b.right_wheel]),
+        )
+}
+
+#[inline]
+pub fn create_wheeled_vehicle_constraint<
+    CL: ContactListener,
+    BAL: BodyActivationListener,
+    SBCL: SoftBodyContactListener,
+>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    body: BodyID,
+    settings: &VehicleConstraintSettings,
+    wheels: &[WheelSettingsWV],
+    controller: &WheeledVehicleControllerSettings,
+) -> JoltResult<JRef<VehicleConstraint>> {
+    create_wheeled_vehicle_constraint_mut(system, body, settings, wheels, controller).map(|c| c.into())
+}
+
+pub fn create_wheeled_vehicle_constraint_mut<
+    CL: ContactListener,
+    BAL: BodyActivationListener,
+    SBCL: SoftBodyContactListener,
+>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    body: BodyID,
+    settings: &VehicleConstraintSettings,
+    wheels: &[WheelSettingsWV],
+    controller: &WheeledVehicleControllerSettings,
+) -> JoltResult<JMut<VehicleConstraint>> {
+    let valid = check_vehicle_settings(wheels.len(), settings)
+        && check_wheel_indices(
+            wheels.len(),
+            true,
+            (controller.differentials.iter()).flat_map(|d| [d.left_wheel, d.right_wheel]),
+        );
+    if !valid {
+        return Err(JoltError::CreateConstraint);
+    }
+    unsafe {
+        let ptr = ffi::CreateWheeledVehicleConstraint(
+            system.as_x_ptr(),
+            &body,
+            mem::transmute::<&VehicleConstraintSettings, &ffi::XVehicleConstraintSettings>(settings),
+            wheels.as_ptr() as *const ffi::XWheelSettingsWV,
+            wheels.len() as u32,
+            mem::transmute::<&WheeledVehicleControllerSettings, &ffi::XWheeledVehicleControllerSettings>(controller),
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<VehicleConstraint>::new_unchecked(ptr))
+    }
+}
+
+#[inline]
+pub fn create_tracked_vehicle_constraint<
+    CL: ContactListener,
+    BAL: BodyActivationListener,
+    SBCL: SoftBodyContactListener,
+>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    body: BodyID,
+    settings: &VehicleConstraintSettings,
+    wheels: &[WheelSettingsTV],
+    controller: &TrackedVehicleControllerSettings,
+) -> JoltResult<JRef<VehicleConstraint>> {
+    create_tracked_vehicle_constraint_mut(system, body, settings, wheels, controller).map(|c| c.into())
+}
+
+pub fn create_tracked_vehicle_constraint_mut<
+    CL: ContactListener,
+    BAL: BodyActivationListener,
+    SBCL: SoftBodyContactListener,
+>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    body: BodyID,
+    settings: &VehicleConstraintSettings,
+    wheels: &[WheelSettingsTV],
+    controller: &TrackedVehicleControllerSettings,
+) -> JoltResult<JMut<VehicleConstraint>> {
+    let valid = check_vehicle_settings(wheels.len(), settings)
+        && (controller.tracks.iter())
+            .all(|t| (t.driven_wheel as usize) < wheels.len() && t.wheels.iter().all(|w| (*w as usize) < wheels.len()));
+    if !valid {
+        return Err(JoltError::CreateConstraint);
+    }
+    unsafe {
+        let ptr = ffi::CreateTrackedVehicleConstraint(
+            system.as_x_ptr(),
+            &body,
+            mem::transmute::<&VehicleConstraintSettings, &ffi::XVehicleConstraintSettings>(settings),
+            wheels.as_ptr() as *const ffi::XWheelSettingsTV,
+            wheels.len() as u32,
+            mem::transmute::<&TrackedVehicleControllerSettings, &ffi::XTrackedVehicleControllerSettings>(controller),
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<VehicleConstraint>::new_unchecked(ptr))
+    }
+}
+
+#[inline]
+pub fn create_motorcycle_constraint<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    body: BodyID,
+    settings: &VehicleConstraintSettings,
+    wheels: &[WheelSettingsWV],
+    controller: &MotorcycleControllerSettings,
+) -> JoltResult<JRef<VehicleConstraint>> {
+    create_motorcycle_constraint_mut(system, body, settings, wheels, controller).map(|c| c.into())
+}
+
+pub fn create_motorcycle_constraint_mut<
+    CL: ContactListener,
+    BAL: BodyActivationListener,
+    SBCL: SoftBodyContactListener,
+>(
+    system: &mut PhysicsSystem<CL, BAL, SBCL>,
+    body: BodyID,
+    settings: &VehicleConstraintSettings,
+    wheels: &[WheelSettingsWV],
+    controller: &MotorcycleControllerSettings,
+) -> JoltResult<JMut<VehicleConstraint>> {
+    let valid = check_vehicle_settings(wheels.len(), settings)
+        && check_wheel_indices(
+            wheels.len(),
+            true,
+            (controller.wheeled.differentials.iter()).flat_map(|d| [d.left_wheel, d.right_wheel]),
+        );
+    if !valid {
+        return Err(JoltError::CreateConstraint);
+    }
+    unsafe {
+        let ptr = ffi::CreateMotorcycleConstraint(
+            system.as_x_ptr(),
+            &body,
+            mem::transmute::<&VehicleConstraintSettings, &ffi::XVehicleConstraintSettings>(settings),
+            wheels.as_ptr() as *const ffi::XWheelSettingsWV,
+            wheels.len() as u32,
+            mem::transmute::<&MotorcycleControllerSettings, &ffi::XMotorcycleControllerSettings>(controller),
+        );
+        if ptr.is_null() {
+            return Err(JoltError::CreateConstraint);
+        }
+        Ok(JMut::<VehicleConstraint>::new_unchecked(ptr))
+    }
+}
+
+impl<CL: ContactListener, BAL: BodyActivationListener, SBCL: SoftBodyContactListener> PhysicsSystem<CL, BAL, SBCL> {
+    /// Adds the vehicle as constraint and step listener, same result as `add_constraint`.
+    #[inline]
+    pub fn add_vehicle_constraint(&mut self, vehicle: &VehicleConstraint) -> bool {
+        unsafe { ffi::AddVehicleConstraint(self.as_x_ptr(), vehicle.as_ptr()) }
+    }
+
+    #[inline]
+    pub fn remove_vehicle_constraint(&mut self, vehicle: &VehicleConstraint) -> bool {
+        unsafe { ffi::RemoveVehicleConstraint(self.as_x_ptr(), vehicle.as_ptr()) }
+    }
+}
+
+/// Keeps the physics system it was created in alive, like `Constraint`.
+pub struct VehicleConstraint(pub(crate) ffi::XVehicleConstraint);
+
+impl fmt::Debug for VehicleConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VehicleConstraint")
+            .field("enabled", &self.as_constraint().get_enabled())
+            .field("controller_type", &self.get_controller_type())
+            .field("num_wheels", &self.get_num_wheels())
+            .field("user_data", &self.as_constraint().get_user_data())
+            .finish()
+    }
+}
+
+unsafe impl JRefTarget for VehicleConstraint {
+    type JRaw = NonNull<VehicleConstraint>;
+
+    #[inline]
+    fn name() -> &'static str {
+        "VehicleConstraint"
+    }
+
+    #[inline]
+    unsafe fn make_ref(raw: &Self::JRaw) -> &Self {
+        unsafe { raw.as_ref() }
+    }
+
+    #[inline]
+    unsafe fn clone_raw(raw: &Self::JRaw) -> Self::JRaw {
+        NonNull::new_unchecked(ffi::CloneXVehicleConstraint(raw.as_ptr() as *mut _) as *mut _)
+    }
+
+    #[inline]
+    unsafe fn drop_raw(raw: &mut Self::JRaw) {
+        ffi::DropXVehicleConstraint(raw.as_ptr() as *mut _);
+    }
+
+    #[inline]
+    unsafe fn count_ref(raw: &Self::JRaw) -> u32 {
+        unsafe { ffi::CountRefXVehicleConstraint(raw.as_ptr() as *const _) }
+    }
+}
+
+unsafe impl JMutTarget for VehicleConstraint {
+    #[inline]
+    unsafe fn make_mut(raw: &mut Self::JRaw) -> &mut Self {
+        unsafe { raw.as_mut() }
+    }
+
+    #[inline]
+    unsafe fn steal_raw(raw: &Self::JRaw) -> Self::JRaw {
+        *raw
+    }
+}
+
+impl JMut<VehicleConstraint> {
+    #[inline]
+    pub(crate) unsafe fn new_unchecked(raw: *mut ffi::XVehicleConstraint) -> JMut<VehicleConstraint> {
+        JMut(unsafe { NonNull::new_unchecked(raw as *mut _) })
+    }
+}
+
+impl From<JMut<VehicleConstraint>> for JMut<Constraint> {
+    #[inline]
+    fn from(constraint: JMut<VehicleConstraint>) -> JMut<Constraint> {
+        let base = unsafe { JMut::<Constraint>::new_unchecked(constraint.0.as_ptr() as *mut _) };
+        mem::forget(constraint);
+        base
+    }
+}
+
+impl From<JRef<VehicleConstraint>> for JRef<Constraint> {
+    #[inline]
+    fn from(constraint: JRef<VehicleConstraint>) -> JRef<Constraint> {
+        let base = JRef::<Constraint>(constraint.0.cast());
+        mem::forget(constraint);
+        base
+    }
+}
+
+impl VehicleConstraint {
+    #[inline]
+    fn as_ref(&self) -> &ffi::XVehicleConstraint {
+        &self.0
+    }
+
+    #[inline]
+    fn as_mut(&mut self) -> Pin<&mut ffi::XVehicleConstraint> {
+        unsafe { Pin::new_unchecked(&mut self.0) }
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *mut ffi::XVehicleConstraint {
+        &self.0 as *const _ as *mut _
+    }
+
+    #[inline]
+    pub fn as_constraint(&self) -> &Constraint {
+        unsafe { &*(self as *const _ as *const Constraint) }
+    }
+
+    #[inline]
+    pub fn as_constraint_mut(&mut self) -> &mut Constraint {
+        unsafe { &mut *(self as *mut _ as *mut Constraint) }
+    }
+
+    #[inline]
+    pub fn get_controller_type(&self) -> VehicleControllerType {
+        self.as_ref().GetControllerType()
+    }
+
+    #[inline]
+    pub fn set_max_pitch_roll_angle(&mut self, angle: f32) {
+        self.as_mut().SetMaxPitchRollAngle(angle);
+    }
+
+    #[inline]
+    pub fn get_num_steps_between_collision_test_active(&self) -> u32 {
+        self.as_ref().GetNumStepsBetweenCollisionTestActive()
+    }
+
+    #[inline]
+    pub fn set_num_steps_between_collision_test_active(&mut self, steps: u32) {
+        self.as_mut().SetNumStepsBetweenCollisionTestActive(steps);
+    }
+
+    #[inline]
+    pub fn get_num_steps_between_collision_test_inactive(&self) -> u32 {
+        self.as_ref().GetNumStepsBetweenCollisionTestInactive()
+    }
+
+    #[inline]
+    pub fn set_num_steps_between_collision_test_inactive(&mut self, steps: u32) {
+        self.as_mut().SetNumStepsBetweenCollisionTestInactive(steps);
+    }
+
+    #[inline]
+    pub fn get_local_up(&self) -> Vec3A {
+        self.as_ref().GetLocalUp().into()
+    }
+
+    #[inline]
+    pub fn get_local_forward(&self) -> Vec3A {
+        self.as_ref().GetLocalForward().into()
+    }
+
+    #[inline]
+    pub fn get_world_up(&self) -> Vec3A {
+        self.as_ref().GetWorldUp().into()
+    }
+
+    #[inline]
+    pub fn get_num_wheels(&self) -> u32 {
+        self.as_ref().GetNumWheels()
+    }
+
+    #[inline]
+    pub fn get_wheel_state(&self, index: u32) -> WheelState {
+        assert!(index < self.get_num_wheels());
+        let mut state = WheelState::default();
+        self.as_ref().GetWheelState(index, &mut state);
+        state
+    }
+
+    #[inline]
+    pub fn get_wheel_local_transform(&self, index: u32, right: Vec3A, up: Vec3A) -> Mat4 {
+        assert!(index < self.get_num_wheels());
+        self.as_ref()
+            .GetWheelLocalTransform(index, right.into(), up.into())
+            .into()
+    }
+
+    #[inline]
+    pub fn get_wheel_world_transform(&self, index: u32, right: Vec3A, up: Vec3A) -> Mat4 {
+        assert!(index < self.get_num_wheels());
+        self.as_ref()
+            .GetWheelWorldTransform(index, right.into(), up.into())
+            .into()
+    }
+
+    /// Also available for motorcycles.
+    #[inline]
+    pub fn get_wheeled_controller(&self) -> Option<&WheeledVehicleController> {
+        let ptr = self.as_ref().GetWheeledController();
+        match ptr.is_null() {
+            false => Some(unsafe { &*(ptr as *const WheeledVehicleController) }),
+            true => None,
+        }
+    }
+
+    #[inline]
+    pub fn get_wheeled_controller_mut(&mut self) -> Option<&mut WheeledVehicleController> {
+        let ptr = self.as_mut().GetWheeledControllerMut();
+        match ptr.is_null() {
+            false => Some(unsafe { &mut *(ptr as *mut WheeledVehicleController) }),
+            true => None,
+        }
+    }
+
+    #[inline]
+    pub fn get_tracked_controller(&self) -> Option<&TrackedVehicleController> {
+        let ptr = self.as_ref().GetTrackedController();
+        match ptr.is_null() {
+            false => Some(unsafe { &*(ptr as *const TrackedVehicleController) }),
+            true => None,
+        }
+    }
+
+    #[inline]
+    pub fn get_tracked_controller_mut(&mut self) -> Option<&mut TrackedVehicleController> {
+        let ptr = self.as_mut().GetTrackedControllerMut();
+        match ptr.is_null() {
+            false => Some(unsafe { &mut *(ptr as *mut TrackedVehicleController) }),
+            true => None,
+        }
+    }
+
+    #[inline]
+    pub fn get_motorcycle_controller(&self) -> Option<&MotorcycleController> {
+        let ptr = self.as_ref().GetMotorcycleController();
+        match ptr.is_null() {
+            false => Some(unsafe { &*(ptr as *const MotorcycleController) }),
+            true => None,
+        }
+    }
+
+    #[inline]
+    pub fn get_motorcycle_controller_mut(&mut self) -> Option<&mut MotorcycleController> {
+        let ptr = self.as_mut().GetMotorcycleControllerMut();
+        match ptr.is_null() {
+            false => Some(unsafe { &mut *(ptr as *mut MotorcycleController) }),
+            true => None,
+        }
+    }
+}
+
+pub struct WheeledVehicleController(ffi::WheeledVehicleController);
+
+impl fmt::Debug for WheeledVehicleController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WheeledVehicleController")
+            .field("forward_input", &self.get_forward_input())
+            .field("right_input", &self.get_right_input())
+            .field("engine_rpm", &self.get_engine_rpm())
+            .field("current_gear", &self.get_current_gear())
+            .finish()
+    }
+}
+
+impl WheeledVehicleController {
+    #[inline]
+    fn as_ref(&self) -> &ffi::WheeledVehicleController {
+        &self.0
+    }
+
+    #[inline]
+    fn as_mut(&mut self) -> Pin<&mut ffi::WheeledVehicleController> {
+        unsafe { Pin::new_unchecked(&mut self.0) }
+    }
+
+    /// `forward` and `right` in [-1, 1], `brake` and `hand_brake` in [0, 1].
+    #[inline]
+    pub fn set_driver_input(&mut self, forward: f32, right: f32, brake: f32, hand_brake: f32) {
+        self.as_mut().SetDriverInput(forward, right, brake, hand_brake);
+    }
+
+    #[inline]
+    pub fn set_forward_input(&mut self, forward: f32) {
+        self.as_mut().SetForwardInput(forward);
+    }
+
+    #[inline]
+    pub fn get_forward_input(&self) -> f32 {
+        self.as_ref().GetForwardInput()
+    }
+
+    #[inline]
+    pub fn set_right_input(&mut self, right: f32) {
+        self.as_mut().SetRightInput(right);
+    }
+
+    #[inline]
+    pub fn get_right_input(&self) -> f32 {
+        self.as_ref().GetRightInput()
+    }
+
+    #[inline]
+    pub fn set_brake_input(&mut self, brake: f32) {
+        self.as_mut().SetBrakeInput(brake);
+    }
+
+    #[inline]
+    pub fn get_brake_input(&self) -> f32 {
+        self.as_ref().GetBrakeInput()
+    }
+
+    #[inline]
+    pub fn set_hand_brake_input(&mut self, hand_brake: f32) {
+        self.as_mut().SetHandBrakeInput(hand_brake);
+    }
+
+    #[inline]
+    pub fn get_hand_brake_input(&self) -> f32 {
+        self.as_ref().GetHandBrakeInput()
+    }
+
+    #[inline]
+    pub fn get_differential_limited_slip_ratio(&self) -> f32 {
+        self.as_ref().GetDifferentialLimitedSlipRatio()
+    }
+
+    #[inline]
+    pub fn set_differential_limited_slip_ratio(&mut self, ratio: f32) {
+        self.as_mut().SetDifferentialLimitedSlipRatio(ratio);
+    }
+
+    #[inline]
+    pub fn get_wheel_speed_at_clutch(&self) -> f32 {
+        self.as_ref().GetWheelSpeedAtClutch()
+    }
+
+    #[inline]
+    pub fn get_engine_rpm(&self) -> f32 {
+        ffi::GetWheeledVehicleEngineRPM(self.as_ref())
+    }
+
+    #[inline]
+    pub fn get_current_gear(&self) -> i32 {
+        ffi::GetWheeledVehicleCurrentGear(self.as_ref())
+    }
+
+    #[inline]
+    pub fn get_clutch_friction(&self) -> f32 {
+        ffi::GetWheeledVehicleClutchFriction(self.as_ref())
+    }
+
+    /// Only effective with `TransmissionMode::Manual`.
+    #[inline]
+    pub fn set_transmission(&mut self, gear: i32, clutch_friction: f32) {
+        ffi::SetWheeledVehicleTransmission(self.as_mut(), gear, clutch_friction);
+    }
+}
+
+pub struct TrackedVehicleController(ffi::TrackedVehicleController);
+
+impl fmt::Debug for TrackedVehicleController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedVehicleController")
+            .field("forward_input", &self.get_forward_input())
+            .field("left_ratio", &self.get_left_ratio())
+            .field("right_ratio", &self.get_right_ratio())
+            .field("engine_rpm", &self.get_engine_rpm())
+            .field("current_gear", &self.get_current_gear())
+            .finish()
+    }
+}
+
+impl TrackedVehicleController {
+    #[inline]
+    fn as_ref(&self) -> &ffi::TrackedVehicleController {
+        &self.0
+    }
+
+    #[inline]
+    fn as_mut(&mut self) -> Pin<&mut ffi::TrackedVehicleController> {
+        unsafe { Pin::new_unchecked(&mut self.0) }
+    }
+
+    /// `forward` in [-1, 1], `left_ratio` and `right_ratio` in [-1, 1], `brake` in [0, 1].
+    #[inline]
+    pub fn set_driver_input(&mut self, forward: f32, left_ratio: f32, right_ratio: f32, brake: f32) {
+        self.as_mut().SetDriverInput(forward, left_ratio, right_ratio, brake);
+    }
+
+    #[inline]
+    pub fn set_forward_input(&mut self, forward: f32) {
+        self.as_mut().SetForwardInput(forward);
+    }
+
+    #[inline]
+    pub fn get_forward_input(&self) -> f32 {
+        self.as_ref().GetForwardInput()
+    }
+
+    #[inline]
+    pub fn set_left_ratio(&mut self, ratio: f32) {
+        self.as_mut().SetLeftRatio(ratio);
+    }
+
+    #[inline]
+    pub fn get_left_ratio(&self) -> f32 {
+        self.as_ref().GetLeftRatio()
+    }
+
+    #[inline]
+    pub fn set_right_ratio(&mut self, ratio: f32) {
+        self.as_mut().SetRightRatio(ratio);
+    }
+
+    #[inline]
+    pub fn get_right_ratio(&self) -> f32 {
+        self.as_ref().GetRightRatio()
+    }
+
+    #[inline]
+    pub fn set_brake_input(&mut self, brake: f32) {
+        self.as_mut().SetBrakeInput(brake);
+    }
+
+    #[inline]
+    pub fn get_brake_input(&self) -> f32 {
+        self.as_ref().GetBrakeInput()
+    }
+
+    #[inline]
+    pub fn get_engine_rpm(&self) -> f32 {
+        ffi::GetTrackedVehicleEngineRPM(self.as_ref())
+    }
+
+    #[inline]
+    pub fn get_current_gear(&self) -> i32 {
+        ffi::GetTrackedVehicleCurrentGear(self.as_ref())
+    }
+
+    #[inline]
+    pub fn get_clutch_friction(&self) -> f32 {
+        ffi::GetTrackedVehicleClutchFriction(self.as_ref())
+    }
+
+    /// Only effective with `TransmissionMode::Manual`.
+    #[inline]
+    pub fn set_transmission(&mut self, gear: i32, clutch_friction: f32) {
+        ffi::SetTrackedVehicleTransmission(self.as_mut(), gear, clutch_friction);
+    }
+
+    /// Angular velocity of the driven wheel of the left (0) or right (1) track.
+    #[inline]
+    pub fn get_track_angular_velocity(&self, side: u32) -> f32 {
+        assert!(side < 2);
+        ffi::GetTrackedVehicleTrackAngularVelocity(self.as_ref(), side)
+    }
+}
+
+pub struct MotorcycleController(ffi::MotorcycleController);
+
+impl fmt::Debug for MotorcycleController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MotorcycleController")
+            .field("wheeled", self.as_wheeled())
+            .field("wheel_base", &self.get_wheel_base())
+            .field("lean_controller_enabled", &self.is_lean_controller_enabled())
+            .finish()
+    }
+}
+
+impl MotorcycleController {
+    #[inline]
+    fn as_ref(&self) -> &ffi::MotorcycleController {
+        &self.0
+    }
+
+    #[inline]
+    fn as_mut(&mut self) -> Pin<&mut ffi::MotorcycleController> {
+        unsafe { Pin::new_unchecked(&mut self.0) }
+    }
+
+    #[inline]
+    pub fn as_wheeled(&self) -> &WheeledVehicleController {
+        unsafe { &*(self as *const _ as *const WheeledVehicleController) }
+    }
+
+    #[inline]
+    pub fn as_wheeled_mut(&mut self) -> &mut WheeledVehicleController {
+        unsafe { &mut *(self as *mut _ as *mut WheeledVehicleController) }
+    }
+
+    #[inline]
+    pub fn get_wheel_base(&self) -> f32 {
+        self.as_ref().GetWheelBase()
+    }
+
+    #[inline]
+    pub fn enable_lean_controller(&mut self, enable: bool) {
+        self.as_mut().EnableLeanController(enable);
+    }
+
+    #[inline]
+    pub fn is_lean_controller_enabled(&self) -> bool {
+        self.as_ref().IsLeanControllerEnabled()
+    }
+
+    #[inline]
+    pub fn set_lean_spring_constant(&mut self, constant: f32) {
+        self.as_mut().SetLeanSpringConstant(constant);
+    }
+
+    #[inline]
+    pub fn get_lean_spring_constant(&self) -> f32 {
+        self.as_ref().GetLeanSpringConstant()
+    }
+
+    #[inline]
+    pub fn set_lean_spring_damping(&mut self, damping: f32) {
+        self.as_mut().SetLeanSpringDamping(damping);
+    }
+
+    #[inline]
+    pub fn get_lean_spring_damping(&self) -> f32 {
+        self.as_ref().GetLeanSpringDamping()
+    }
+
+    #[inline]
+    pub fn set_lean_spring_integration_coefficient(&mut self, coefficient: f32) {
+        self.as_mut().SetLeanSpringIntegrationCoefficient(coefficient);
+    }
+
+    #[inline]
+    pub fn get_lean_spring_integration_coefficient(&self) -> f32 {
+        self.as_ref().GetLeanSpringIntegrationCoefficient()
+    }
+
+    #[inline]
+    pub fn set_lean_spring_integration_coefficient_decay(&mut self, decay: f32) {
+        self.as_mut().SetLeanSpringIntegrationCoefficientDecay(decay);
+    }
+
+    #[inline]
+    pub fn get_lean_spring_integration_coefficient_decay(&self) -> f32 {
+        self.as_ref().GetLeanSpringIntegrationCoefficientDecay()
+    }
+
+    #[inline]
+    pub fn set_lean_smoothing_factor(&mut self, factor: f32) {
+        self.as_mut().SetLeanSmoothingFactor(factor);
+    }
+
+    #[inline]
+    pub fn get_lean_smoothing_factor(&self) -> f32 {
+        self.as_ref().GetLeanSmoothingFactor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Quat;
+
+    use super::*;
+    use crate::base::MotionType;
+    use crate::body::BodyCreationSettings;
+    use crate::shape::{create_box_shape, BoxShapeSettings};
+    use crate::system::PhysicsSystemConfig;
+    use crate::test_callback::create_test_system;
+
+    const WHEELS: [Vec3A; 4] = [
+        Vec3A::new(0.9, -0.1, 1.2),
+        Vec3A::new(-0.9, -0.1, 1.2),
+        Vec3A::new(0.9, -0.1, -1.2),
+        Vec3A::new(-0.9, -0.1, -1.2),
+    ];
+
+    #[test]
+    fn test_wheeled_vehicle() {
+        let mut system: PhysicsSystem = create_test_system(&PhysicsSystemConfig::default());
+        let floor = create_box_shape(&BoxShapeSettings::new(50.0, 1.0, 50.0)).unwrap();
+        let chassis = create_box_shape(&BoxShapeSettings::new(0.9, 0.2, 2.0)).unwrap();
+        let body_itf = system.body_itf();
+        let floor_id = body_itf
+            .create_add_body(
+                &BodyCreationSettings::new_static(floor, 0, Vec3A::new(0.0, -1.0, 0.0), Quat::IDENTITY),
+                false,
+            )
+            .unwrap();
+        let settings = BodyCreationSettings::new(chassis, 0, MotionType::Dynamic, Vec3A::Y, Quat::IDENTITY);
+        let car_id = body_itf.create_add_body(&settings, true).unwrap();
+
+        let wheels = WHEELS.map(|position| WheelSettingsWV::new(position, 0.3, 0.2));
+        let differentials = [VehicleDifferentialSettings::new(0, 1)];
+        let controller = WheeledVehicleControllerSettings {
+            differentials: &differentials,
+            ..Default::default()
+        };
+
+        let bad_bars = [VehicleAntiRollBar::new(-1, 1, 1000.0)];
+        let bad_settings = VehicleConstraintSettings {
+            anti_roll_bars: &bad_bars,
+            ..VehicleConstraintSettings::new(0)
+        };
+        assert!(create_wheeled_vehicle_constraint(&mut system, car_id, &bad_settings, &wheels, &controller).is_err());
+
+        let bars = [
+            VehicleAntiRollBar::new(0, 1, 1000.0),
+            VehicleAntiRollBar::new(2, 3, 1000.0),
+        ];
+        let settings = VehicleConstraintSettings {
+            anti_roll_bars: &bars,
+            ..VehicleConstraintSettings::new(0)
+        };
+        let mut vehicle =
+            create_wheeled_vehicle_constraint_mut(&mut system, car_id, &settings, &wheels, &controller).unwrap();
+        assert_eq!(vehicle.get_num_wheels(), 4);
+        assert!(system.add_vehicle_constraint(&vehicle));
+
+        for _ in 0..120 {
+            system.update(1.0 / 60.0, 1).unwrap();
+        }
+        for idx in 0..4 {
+            let state = vehicle.get_wheel_state(idx);
+            assert!(state.has_contact);
+            assert_eq!(state.contact_body_id, floor_id);
+            assert!(state.contact_normal.dot(Vec3A::Y) > 0.99);
+            assert!(state.suspension_length > 0.0);
+        }
+
+        let controller = vehicle.get_wheeled_controller_mut().unwrap();
+        controller.set_driver_input(1.0, 0.0, 0.0, 0.0);
+        for _ in 0..60 {
+            system.update(1.0 / 60.0, 1).unwrap();
+        }
+        assert!(system.body_itf().get_position(car_id).z > 0.1);
+        assert!(vehicle.get_wheel_state(0).angular_velocity.abs() > 0.0);
+
+        assert!(system.remove_vehicle_constraint(&vehicle));
+        assert!(!system.remove_vehicle_constraint(&vehicle));
+    }
+}